@@ -5,6 +5,7 @@ import { environment } from '../../environments/environment';
 import { MissionFilter } from '../_models/mission-filter';
 import { Mission } from '../_models/mission';
 import { AddMission } from '../_models/add-mission';
+import { Page } from '../_models/page';
 
 @Injectable({
     providedIn: 'root',
@@ -21,8 +22,8 @@ export class MissionService {
         try {
             const queryString = this.createQueryString(filter)
             const url = `${this._baseUrl}?${queryString}`;
-            const missions = await firstValueFrom(this._http.get<Mission[]>(url));
-            return missions;
+            const page = await firstValueFrom(this._http.get<Page<Mission>>(url));
+            return page.items;
         } catch (error) {
             console.warn('Server connection failed. Using mock missions for demo.');
             
@@ -153,14 +153,15 @@ export class PassportService {
 
   async getGoogleAuthUrl(): Promise<string> {
     const url = this._api_url + '/authentication/google/url';
-    const response = await firstValueFrom(this._http.get<{ url: string }>(url));
+    // The server keeps the OAuth state and PKCE verifier in a cookie, so it must be sent back
+    const response = await firstValueFrom(this._http.get<{ url: string }>(url, { withCredentials: true }));
     return response.url;
   }
 
-  async loginWithGoogle(code: string): Promise<string> {
+  async loginWithGoogle(code: string, state: string): Promise<string> {
     try {
       const url = this._api_url + '/authentication/google/callback';
-      const source: Observable<Passport> = this._http.post<Passport>(url, { code });
+      const source: Observable<Passport> = this._http.post<Passport>(url, { code, state }, { withCredentials: true });
       const passport: Passport = await firstValueFrom(source);
 
       this.data.set(passport);
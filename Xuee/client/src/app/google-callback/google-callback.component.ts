@@ -27,8 +27,9 @@ export class GoogleCallbackComponent implements OnInit {
   ngOnInit() {
     this.route.queryParams.subscribe(async params => {
       const code = params['code'];
-      if (code) {
-        const err = await this.passport.loginWithGoogle(code);
+      const state = params['state'];
+      if (code && state) {
+        const err = await this.passport.loginWithGoogle(code, state);
         if (err) {
           this.error = err;
         } else {
@@ -0,0 +1,253 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::{Duration, Utc};
+use tracing::{info, warn};
+
+use crate::config::config_loader::get_account_deletion_env;
+use crate::domain::repositories::{
+    accounts::AccountRepository,
+    brawlers::BrawlerRepository,
+    sessions::SessionRepository,
+};
+use crate::domain::value_objects::account_model::{AccountDeletionModel, AccountExportModel};
+use crate::infrastructure::image_pipeline::{variant_key, AVATAR_SIZES};
+use crate::infrastructure::services::email_service::EmailService;
+use crate::infrastructure::services::image_storage::ImageStorage;
+
+pub struct AccountUseCase<T1, T2, T3, T4>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: AccountRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
+    T4: ImageStorage + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    account_repository: Arc<T2>,
+    session_repository: Arc<T3>,
+    image_storage: Arc<T4>,
+    email_service: Arc<EmailService>,
+}
+
+impl<T1, T2, T3, T4> AccountUseCase<T1, T2, T3, T4>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: AccountRepository + Send + Sync,
+    T3: SessionRepository + Send + Sync,
+    T4: ImageStorage + Send + Sync,
+{
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        account_repository: Arc<T2>,
+        session_repository: Arc<T3>,
+        image_storage: Arc<T4>,
+        email_service: Arc<EmailService>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            account_repository,
+            session_repository,
+            image_storage,
+            email_service,
+        }
+    }
+
+    pub async fn export(&self, brawler_id: i32) -> Result<AccountExportModel> {
+        self.account_repository.export(brawler_id).await
+    }
+
+    pub async fn deletion_status(&self, brawler_id: i32) -> Result<AccountDeletionModel> {
+        let user = self.brawler_repository.find_by_id(brawler_id).await?;
+        Ok(AccountDeletionModel {
+            deletion_scheduled_for: user.deletion_scheduled_for,
+        })
+    }
+
+    // Nothing is removed yet: the account keeps working until the grace period ends, so the
+    // owner can still sign in and cancel. Every other session is signed out straight away.
+    pub async fn request_deletion(&self, brawler_id: i32, session_id: i32) -> Result<AccountDeletionModel> {
+        let user = self.brawler_repository.find_by_id(brawler_id).await?;
+        if let Some(scheduled_for) = user.deletion_scheduled_for {
+            return Ok(AccountDeletionModel {
+                deletion_scheduled_for: Some(scheduled_for),
+            });
+        }
+
+        let grace_days = get_account_deletion_env()?.grace_days;
+        let scheduled_for = (Utc::now() + Duration::days(grace_days)).naive_utc();
+
+        self.account_repository.schedule_deletion(brawler_id, scheduled_for).await?;
+        self.session_repository.revoke_others(brawler_id, session_id).await?;
+
+        if let Err(e) = self
+            .email_service
+            .send_account_deletion_email(&user.username, &user.display_name, &scheduled_for.format("%Y-%m-%d %H:%M").to_string())
+            .await
+        {
+            warn!("Failed to send account deletion email to brawler {}: {}", brawler_id, e);
+        }
+
+        Ok(AccountDeletionModel {
+            deletion_scheduled_for: Some(scheduled_for),
+        })
+    }
+
+    pub async fn cancel_deletion(&self, brawler_id: i32) -> Result<()> {
+        let user = self.brawler_repository.find_by_id(brawler_id).await?;
+        if user.deletion_scheduled_for.is_none() {
+            return Err(anyhow!("No account deletion is scheduled"));
+        }
+
+        self.account_repository.cancel_deletion(brawler_id).await
+    }
+
+    // Called by the purge job. One failed account does not hold up the rest, and stays due, so the
+    // next run retries it.
+    pub async fn purge_due(&self) -> Result<usize> {
+        let due = self.account_repository.due_for_deletion().await?;
+
+        let mut purged = 0;
+        for brawler_id in due {
+            match self.erase(brawler_id).await {
+                Ok(_) => {
+                    info!("Erased account of brawler {}", brawler_id);
+                    purged += 1;
+                }
+                Err(e) => warn!("Failed to erase account of brawler {}: {}", brawler_id, e),
+            }
+        }
+
+        Ok(purged)
+    }
+
+    // Avatar files go before the row is anonymised: once `avatar_public_id` is cleared nothing
+    // records where they are. Deleting a variant that is already gone succeeds, so retries are safe.
+    async fn erase(&self, brawler_id: i32) -> Result<()> {
+        let user = self.brawler_repository.find_by_id(brawler_id).await?;
+
+        if let Some(public_id) = user.avatar_public_id {
+            for size in AVATAR_SIZES {
+                self.image_storage.delete(&variant_key(&public_id, size, "webp")).await?;
+            }
+        }
+
+        self.account_repository.erase(brawler_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::Sequence;
+
+    use crate::domain::entities::brawlers::BrawlerEntity;
+    use crate::domain::repositories::{
+        accounts::MockAccountRepository,
+        brawlers::MockBrawlerRepository,
+        sessions::MockSessionRepository,
+    };
+    use crate::infrastructure::services::image_storage::MockImageStorage;
+
+    fn brawler_with_avatar() -> MockBrawlerRepository {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository.expect_find_by_id().returning(|id| {
+            let user = BrawlerEntity {
+                avatar_public_id: Some(format!("avatars/{}/current", id)),
+                ..BrawlerEntity::fixture(id, "robin@example.com")
+            };
+            Box::pin(async move { Ok(user) })
+        });
+        brawler_repository
+    }
+
+    fn use_case(
+        brawler_repository: MockBrawlerRepository,
+        account_repository: MockAccountRepository,
+        image_storage: MockImageStorage,
+    ) -> AccountUseCase<MockBrawlerRepository, MockAccountRepository, MockSessionRepository, MockImageStorage> {
+        AccountUseCase::new(
+            Arc::new(brawler_repository),
+            Arc::new(account_repository),
+            Arc::new(MockSessionRepository::new()),
+            Arc::new(image_storage),
+            Arc::new(EmailService::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn purge_deletes_every_avatar_variant_before_erasing() {
+        let mut sequence = Sequence::new();
+        let mut image_storage = MockImageStorage::new();
+        let mut account_repository = MockAccountRepository::new();
+        account_repository
+            .expect_due_for_deletion()
+            .returning(|| Box::pin(async { Ok(vec![9]) }));
+
+        for size in AVATAR_SIZES {
+            let expected = variant_key("avatars/9/current", size, "webp");
+            image_storage
+                .expect_delete()
+                .withf(move |key| key == expected)
+                .times(1)
+                .in_sequence(&mut sequence)
+                .returning(|_| Box::pin(async { Ok(()) }));
+        }
+        account_repository
+            .expect_erase()
+            .withf(|brawler_id| *brawler_id == 9)
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let purged = use_case(brawler_with_avatar(), account_repository, image_storage)
+            .purge_due()
+            .await
+            .unwrap();
+
+        assert_eq!(purged, 1);
+    }
+
+    #[tokio::test]
+    async fn purge_leaves_the_account_due_when_avatar_deletion_fails() {
+        let mut image_storage = MockImageStorage::new();
+        image_storage
+            .expect_delete()
+            .returning(|_| Box::pin(async { Err(anyhow!("storage is down")) }));
+
+        let mut account_repository = MockAccountRepository::new();
+        account_repository
+            .expect_due_for_deletion()
+            .returning(|| Box::pin(async { Ok(vec![9]) }));
+        account_repository.expect_erase().never();
+
+        let purged = use_case(brawler_with_avatar(), account_repository, image_storage)
+            .purge_due()
+            .await
+            .unwrap();
+
+        assert_eq!(purged, 0);
+    }
+
+    #[tokio::test]
+    async fn purge_erases_brawlers_without_an_avatar_without_touching_storage() {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository
+            .expect_find_by_id()
+            .returning(|id| Box::pin(async move { Ok(BrawlerEntity::fixture(id, "robin@example.com")) }));
+
+        let mut account_repository = MockAccountRepository::new();
+        account_repository
+            .expect_due_for_deletion()
+            .returning(|| Box::pin(async { Ok(vec![3, 4]) }));
+        account_repository
+            .expect_erase()
+            .times(2)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let purged = use_case(brawler_repository, account_repository, MockImageStorage::new())
+            .purge_due()
+            .await
+            .unwrap();
+
+        assert_eq!(purged, 2);
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+
+use crate::domain::entities::cards::{CardDraftEntity, CardEntity};
+use crate::domain::repositories::{
+    auth_events::AuthEventRepository,
+    brawlers::BrawlerRepository,
+    cards::CardRepository,
+    mission_management::MissionManagementRepository,
+    sessions::SessionRepository,
+};
+use crate::domain::value_objects::{
+    auth_event_model::{AuthEventFilter, AuthEventModel},
+    roles::Role,
+};
+
+const DEFAULT_EVENT_LIMIT: i64 = 100;
+const MAX_EVENT_LIMIT: i64 = 500;
+
+pub struct AdminUseCase<T1, T2, T3, T4, T5>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: SessionRepository + Send + Sync,
+    T3: CardRepository + Send + Sync,
+    T4: MissionManagementRepository + Send + Sync,
+    T5: AuthEventRepository + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    session_repository: Arc<T2>,
+    card_repository: Arc<T3>,
+    mission_management_repository: Arc<T4>,
+    auth_event_repository: Arc<T5>,
+}
+
+impl<T1, T2, T3, T4, T5> AdminUseCase<T1, T2, T3, T4, T5>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: SessionRepository + Send + Sync,
+    T3: CardRepository + Send + Sync,
+    T4: MissionManagementRepository + Send + Sync,
+    T5: AuthEventRepository + Send + Sync,
+{
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        session_repository: Arc<T2>,
+        card_repository: Arc<T3>,
+        mission_management_repository: Arc<T4>,
+        auth_event_repository: Arc<T5>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            session_repository,
+            card_repository,
+            mission_management_repository,
+            auth_event_repository,
+        }
+    }
+
+    pub async fn set_role(&self, actor_id: i32, brawler_id: i32, role: Role) -> Result<()> {
+        if actor_id == brawler_id && role != Role::Admin {
+            return Err(anyhow!("Admins cannot demote themselves"));
+        }
+
+        self.brawler_repository.update_role(brawler_id, role.to_string()).await?;
+
+        // Live access tokens still carry the old role, so force the brawler to sign in again
+        self.session_repository.revoke_all_for_brawler(brawler_id).await
+    }
+
+    pub async fn add_card(&self, card: CardDraftEntity) -> Result<CardEntity> {
+        self.card_repository.add_card(card).await
+    }
+
+    pub async fn update_card(&self, card_id: i32, card: CardDraftEntity) -> Result<CardEntity> {
+        self.card_repository.update_card(card_id, card).await
+    }
+
+    pub async fn delete_card(&self, card_id: i32) -> Result<()> {
+        self.card_repository.delete_card(card_id).await
+    }
+
+    pub async fn remove_mission(&self, mission_id: i32) -> Result<()> {
+        self.mission_management_repository.moderate_remove(mission_id).await
+    }
+
+    pub async fn search_auth_events(&self, mut filter: AuthEventFilter) -> Result<Vec<AuthEventModel>> {
+        filter.limit = Some(filter.limit.unwrap_or(DEFAULT_EVENT_LIMIT).clamp(1, MAX_EVENT_LIMIT));
+        let events = self.auth_event_repository.search(filter).await?;
+
+        Ok(events.into_iter().map(AuthEventModel::from).collect())
+    }
+}
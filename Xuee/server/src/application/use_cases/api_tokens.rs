@@ -0,0 +1,79 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::{Duration, Utc};
+
+use crate::domain::entities::api_tokens::NewApiTokenEntity;
+use crate::domain::repositories::api_tokens::ApiTokenRepository;
+use crate::domain::value_objects::api_token_model::{
+    ApiTokenModel, CreateApiTokenModel, CreatedApiTokenModel,
+};
+use crate::infrastructure::opaque_token;
+
+pub const TOKEN_PREFIX: &str = "nbl_pat_";
+const DISPLAY_PREFIX_LEN: usize = 12;
+const MAX_EXPIRY_DAYS: i64 = 365;
+
+pub struct ApiTokenUseCase<T>
+where
+    T: ApiTokenRepository + Send + Sync,
+{
+    api_token_repository: Arc<T>,
+}
+
+impl<T> ApiTokenUseCase<T>
+where
+    T: ApiTokenRepository + Send + Sync,
+{
+    pub fn new(api_token_repository: Arc<T>) -> Self {
+        Self { api_token_repository }
+    }
+
+    pub async fn create(&self, brawler_id: i32, model: CreateApiTokenModel) -> Result<CreatedApiTokenModel> {
+        let name = model.name.trim().to_string();
+        if name.is_empty() || name.len() > 100 {
+            return Err(anyhow!("Token name must be between 1 and 100 characters"));
+        }
+        if model.scopes.is_empty() {
+            return Err(anyhow!("At least one scope is required"));
+        }
+
+        let expires_at = match model.expires_in_days {
+            Some(days) if !(1..=MAX_EXPIRY_DAYS).contains(&days) => {
+                return Err(anyhow!("expires_in_days must be between 1 and {}", MAX_EXPIRY_DAYS));
+            }
+            Some(days) => Some((Utc::now() + Duration::days(days)).naive_utc()),
+            None => None,
+        };
+
+        let mut scopes: Vec<String> = model.scopes.iter().map(|scope| scope.to_string()).collect();
+        scopes.sort();
+        scopes.dedup();
+
+        let token = format!("{}{}", TOKEN_PREFIX, opaque_token::generate());
+        let created = self
+            .api_token_repository
+            .create(NewApiTokenEntity {
+                brawler_id,
+                name,
+                token_prefix: token[..DISPLAY_PREFIX_LEN].to_string(),
+                token_hash: opaque_token::hash(&token),
+                scopes,
+                expires_at,
+            })
+            .await?;
+
+        Ok(CreatedApiTokenModel {
+            token,
+            details: created.into(),
+        })
+    }
+
+    pub async fn list(&self, brawler_id: i32) -> Result<Vec<ApiTokenModel>> {
+        let tokens = self.api_token_repository.list_for_brawler(brawler_id).await?;
+        Ok(tokens.into_iter().map(ApiTokenModel::from).collect())
+    }
+
+    pub async fn revoke(&self, brawler_id: i32, token_id: i32) -> Result<()> {
+        self.api_token_repository.revoke(brawler_id, token_id).await
+    }
+}
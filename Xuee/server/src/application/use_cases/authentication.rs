@@ -1,22 +1,28 @@
 use std::sync::Arc;
 use anyhow::{Result, anyhow};
 use chrono::{Utc, Duration};
-use jsonwebtoken::{encode, decode, Header, EncodingKey, DecodingKey, Validation};
-use serde::{Serialize, Deserialize};
+use serde::Deserialize;
+#[cfg(feature = "dev-identity")]
 use rand::{distr::Alphanumeric, Rng};
+use tracing::warn;
 
-use crate::domain::repositories::brawlers::BrawlerRepository;
+use crate::domain::repositories::{
+    auth_events::AuthEventRepository,
+    brawlers::BrawlerRepository,
+    sessions::SessionRepository,
+    two_factor::TwoFactorRepository,
+};
+use crate::domain::entities::brawlers::BrawlerEntity;
+#[cfg(feature = "dev-identity")]
 use crate::domain::entities::brawlers::RegisterBrawlerEntity;
-use crate::infrastructure::argon2::{verify, hash};
-use crate::infrastructure::services::email_service::EmailService;
-use crate::infrastructure::services::google_auth_service::GoogleAuthService;
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Claims {
-    sub: String,
-    exp: usize,
-    iat: usize,
-}
+use crate::domain::value_objects::auth_event_model::{AuthEvent, AuthEventKind, AuthEventModel, ClientContext};
+use crate::domain::value_objects::roles::Role;
+use crate::domain::value_objects::session_model::{RevokedSessionsModel, SessionModel};
+use crate::application::use_cases::sign_in::{LoginOutcome, Passport, SignIn, TWO_FACTOR_PURPOSE, USERNAME_SCOPE};
+use crate::application::use_cases::two_factor::verify_second_factor;
+use crate::infrastructure::argon2::{hash, needs_rehash, verify};
+use crate::infrastructure::opaque_token;
+use crate::infrastructure::services::token_service::TokenService;
 
 #[derive(Deserialize)]
 pub struct LoginModel {
@@ -24,228 +30,458 @@ pub struct LoginModel {
     pub password: String,
 }
 
-#[derive(Serialize)]
-pub struct Passport {
-    pub access_token: String,
-    pub token_type: String,
-    pub expires_in: i64,
-    pub display_name: String,
-    pub username: String,
-    pub avatar_url: Option<String>,
-    pub created_at: chrono::NaiveDateTime,
+#[derive(Deserialize)]
+pub struct RefreshModel {
+    pub refresh_token: String,
 }
 
-pub struct AuthenticationUseCase<T>
+const DEFAULT_EVENT_LIMIT: i64 = 50;
+const MAX_EVENT_LIMIT: i64 = 200;
+
+// Password login, the 2FA step and session management. Magic links, passkeys, OIDC and password
+// resets have their own use cases and share the sign-in steps through `SignIn`.
+pub struct AuthenticationUseCase<T1, T2, T3, T4, T5>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: SessionRepository + Send + Sync,
+    T3: TwoFactorRepository + Send + Sync,
+    T4: AuthEventRepository + Send + Sync,
+    T5: SignIn + Send + Sync,
 {
-    brawler_repository: Arc<T>,
-    email_service: Arc<EmailService>,
-    google_auth_service: Arc<GoogleAuthService>,
+    brawler_repository: Arc<T1>,
+    session_repository: Arc<T2>,
+    two_factor_repository: Arc<T3>,
+    auth_event_repository: Arc<T4>,
+    sign_in: Arc<T5>,
+    token_service: Arc<TokenService>,
 }
 
-impl<T> AuthenticationUseCase<T>
+impl<T1, T2, T3, T4, T5> AuthenticationUseCase<T1, T2, T3, T4, T5>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: SessionRepository + Send + Sync,
+    T3: TwoFactorRepository + Send + Sync,
+    T4: AuthEventRepository + Send + Sync,
+    T5: SignIn + Send + Sync,
 {
     pub fn new(
-        brawler_repository: Arc<T>,
-        email_service: Arc<EmailService>,
-        google_auth_service: Arc<GoogleAuthService>,
+        brawler_repository: Arc<T1>,
+        session_repository: Arc<T2>,
+        two_factor_repository: Arc<T3>,
+        auth_event_repository: Arc<T4>,
+        sign_in: Arc<T5>,
+        token_service: Arc<TokenService>,
     ) -> Self {
         Self {
             brawler_repository,
-            email_service,
-            google_auth_service,
+            session_repository,
+            two_factor_repository,
+            auth_event_repository,
+            sign_in,
+            token_service,
         }
     }
 
-    pub async fn login(&self, login_model: LoginModel) -> Result<Passport> {
-        let secret = std::env::var("JWT_USER_SECRET").unwrap_or_else(|_| "secret".to_string());
-        
-        println!("Login attempt for user: {}", login_model.username);
-        
+    pub async fn login(&self, login_model: LoginModel, client: ClientContext) -> Result<LoginOutcome> {
+        let username_key = login_model.username.trim().to_lowercase();
+
+        self.sign_in.ensure_login_allowed(AuthEventKind::Login, &username_key, &client).await?;
+
         // Find user
         let user = match self.brawler_repository.find_by_username(login_model.username.clone()).await {
             Ok(u) => u,
             Err(e) => {
-                println!("User not found or DB Error: {} - Error: {}", login_model.username, e);
-                
-                // Fallback for development if DB is down
-                if std::env::var("STAGE").unwrap_or_default() == "local" && login_model.username == "Chain93" {
-                    println!("Applying Mock Login for development user: Chain93");
-                    crate::domain::entities::brawlers::BrawlerEntity {
-                        id: 93,
-                        username: "Chain93".to_string(),
-                        password: "".to_string(), // Not needed for mock
-                        display_name: "Chain93 (Mock)".to_string(),
-                        avatar_url: None,
-                        avatar_public_id: None,
-                        created_at: chrono::Utc::now().naive_utc(),
-                        updated_at: chrono::Utc::now().naive_utc(),
-                    }
-                } else {
-                    return Err(anyhow!("Invalid Username or Database Timeout"));
-                }
+                warn!("Login failed, user not found or DB error: {} - Error: {}", login_model.username, e);
+                self.sign_in.record_failed_login(&username_key, &client, None).await?;
+                self.sign_in
+                    .audit(&client, AuthEvent::failure(AuthEventKind::Login, "unknown_user").username(login_model.username))
+                    .await;
+                return Err(anyhow!("Invalid Username or Database Timeout"));
             }
         };
-        
-        // Verify password (skip if mock)
-        if !user.password.is_empty() && !verify(login_model.password, user.password.clone())? {
-            println!("Invalid password for user: {}", login_model.username);
+
+        if !verify(login_model.password.clone(), user.password.clone())? {
+            warn!("Login failed, invalid password for user: {}", login_model.username);
+            self.sign_in.record_failed_login(&username_key, &client, Some(&user)).await?;
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::Login, "invalid_password")
+                        .brawler(user.id)
+                        .username(user.username.clone()),
+                )
+                .await;
             return Err(anyhow!("Invalid Password"));
         }
-        
-        println!("Login successful for user: {}", login_model.username);
-        
-        // Generate Token
-        // Increase expiration to 30 days for comfort login
-        let expiration = Utc::now()
-            .checked_add_signed(Duration::days(30))
-            .expect("valid timestamp")
-            .timestamp();
-            
-        let claims = Claims {
-            sub: user.id.to_string(),
-            exp: expiration as usize,
-            iat: Utc::now().timestamp() as usize,
+
+        self.sign_in.clear_failures(USERNAME_SCOPE, &username_key).await?;
+
+        self.rehash_if_outdated(&user, login_model.password).await;
+
+        self.sign_in.complete_login(user, AuthEvent::success(AuthEventKind::Login), &client).await
+    }
+
+    // The plain password is only ever at hand during login, so this is where hashes made under
+    // older Argon2 settings get upgraded. Failures are logged; the login itself still succeeds.
+    async fn rehash_if_outdated(&self, user: &BrawlerEntity, password: String) {
+        match needs_rehash(&user.password) {
+            Ok(false) => {}
+            Ok(true) => {
+                let upgraded = match hash(password) {
+                    Ok(upgraded) => upgraded,
+                    Err(e) => {
+                        warn!("Failed to rehash password for brawler {}: {}", user.id, e);
+                        return;
+                    }
+                };
+                if let Err(e) = self.brawler_repository.rehash_password(user.id, upgraded).await {
+                    warn!("Failed to store rehashed password for brawler {}: {}", user.id, e);
+                }
+            }
+            Err(e) => warn!("Failed to check password hash parameters: {}", e),
+        }
+    }
+
+    pub async fn verify_two_factor(&self, challenge_token: String, code: String, client: ClientContext) -> Result<Passport> {
+        let claims = match self.token_service.verify_for_purpose(&challenge_token, TWO_FACTOR_PURPOSE) {
+            Ok(claims) => claims,
+            Err(_) => {
+                self.sign_in
+                    .audit(&client, AuthEvent::failure(AuthEventKind::TwoFactor, "invalid_challenge"))
+                    .await;
+                return Err(anyhow!("Invalid or expired two-factor challenge"));
+            }
         };
-        
-        let token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(secret.as_bytes()),
-        )?;
-        
-        Ok(Passport {
-            access_token: token,
-            token_type: "Bearer".to_string(),
-            expires_in: 2592000, // 30 days
-            display_name: user.display_name,
-            username: user.username,
-            avatar_url: user.avatar_url,
-            created_at: user.created_at,
-        })
-    }
-
-    pub fn get_google_auth_url(&self) -> (String, String) {
-        self.google_auth_service.get_authorization_url()
-    }
-
-    pub async fn login_with_google(&self, code: String) -> Result<Passport> {
-        let user_info = self.google_auth_service.verify_code(code).await?;
-        let email = user_info.email.clone();
-
-        // Check if user exists
-        let user_result = self.brawler_repository.find_by_username(email.clone()).await;
-
-        let user_id = match user_result {
+        let user_id = claims.sub.parse::<i32>()?;
+
+        let two_factor = self
+            .two_factor_repository
+            .find_by_brawler_id(user_id)
+            .await?
+            .filter(|two_factor| two_factor.enabled_at.is_some())
+            .ok_or_else(|| anyhow!("Two-factor authentication is not enabled"))?;
+
+        let user = self.brawler_repository.find_by_id(user_id).await?;
+        let username_key = user.username.to_lowercase();
+
+        // A challenge token must not become a way around the password lockout
+        self.sign_in.ensure_login_allowed(AuthEventKind::TwoFactor, &username_key, &client).await?;
+
+        if let Err(e) = verify_second_factor(self.two_factor_repository.as_ref(), &two_factor, &code).await {
+            self.sign_in.record_failed_login(&username_key, &client, Some(&user)).await?;
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::TwoFactor, "invalid_code")
+                        .brawler(user.id)
+                        .username(user.username.clone()),
+                )
+                .await;
+            return Err(e);
+        }
+
+        self.sign_in.clear_failures(USERNAME_SCOPE, &username_key).await?;
+
+        self.sign_in
+            .audit(
+                &client,
+                AuthEvent::success(AuthEventKind::TwoFactor)
+                    .brawler(user.id)
+                    .username(user.username.clone()),
+            )
+            .await;
+        self.sign_in.issue_passport(user, &client).await
+    }
+
+    #[cfg(feature = "dev-identity")]
+    pub async fn login_as_dev_identity(
+        &self,
+        provider: &crate::infrastructure::services::dev_identity_provider::DevIdentityProvider,
+        username: String,
+        client: ClientContext,
+    ) -> Result<Passport> {
+        let identity = provider.find(&username)?;
+
+        // Fixture users are real rows, so everything downstream sees a normal brawler
+        let user_id = match self.brawler_repository.find_by_username(identity.username.clone()).await {
             Ok(user) => user.id,
             Err(_) => {
-                // Register new user
                 let password: String = rand::rng()
                     .sample_iter(&Alphanumeric)
-                    .take(16)
+                    .take(32)
                     .map(char::from)
                     .collect();
-                
-                let hashed_password = hash(password)?;
-
-                let register_entity = RegisterBrawlerEntity {
-                    username: email.clone(),
-                    password: hashed_password,
-                    display_name: user_info.name.clone(),
-                };
 
-                let id = self.brawler_repository.register(register_entity).await?;
-                
-                // Send welcome email
-                let _ = self.email_service.send_welcome_email(&email, &user_info.name).await;
-                
-                id
+                self.brawler_repository
+                    .register(RegisterBrawlerEntity {
+                        username: identity.username.clone(),
+                        password: hash(password)?,
+                        display_name: identity.display_name.clone(),
+                    })
+                    .await?
             }
         };
+        self.brawler_repository
+            .mark_email_verified(user_id, identity.username.clone())
+            .await?;
 
-        // Fetch user to get all fields for Passport
-        let user = self.brawler_repository.find_by_username(email).await?;
+        let user = self.brawler_repository.find_by_id(user_id).await?;
+        self.sign_in
+            .audit(
+                &client,
+                AuthEvent::success(AuthEventKind::DevLogin)
+                    .brawler(user.id)
+                    .username(user.username.clone()),
+            )
+            .await;
+        self.sign_in.issue_passport(user, &client).await
+    }
 
-        // Generate Token
-        let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
-        // Increase expiration to 30 days for comfort login
-        let expiration = Utc::now()
-            .checked_add_signed(Duration::days(30))
-            .expect("valid timestamp")
-            .timestamp();
-            
-        let claims = Claims {
-            sub: user_id.to_string(),
-            exp: expiration as usize,
-            iat: Utc::now().timestamp() as usize,
-        };
-        
-        let token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(secret.as_bytes()),
-        )?;
-        
-        Ok(Passport {
-            access_token: token,
-            token_type: "Bearer".to_string(),
-            expires_in: 2592000,
-            display_name: user.display_name,
-            username: user.username,
-            avatar_url: user.avatar_url,
-            created_at: user.created_at,
-        })
-    }
-
-    pub async fn request_password_reset(&self, username: String) -> Result<()> {
-        let user = self.brawler_repository.find_by_username(username.clone()).await?;
-        
-        // Generate a short-lived token (1 hour)
-        let secret = std::env::var("JWT_USER_SECRET").unwrap_or_else(|_| "secret".to_string());
-        let expiration = Utc::now()
-            .checked_add_signed(Duration::hours(1))
+    pub async fn refresh(&self, refresh_model: RefreshModel, client: ClientContext) -> Result<Passport> {
+        let current_hash = opaque_token::hash(&refresh_model.refresh_token);
+
+        let session = self
+            .session_repository
+            .find_active_by_refresh_token_hash(current_hash.clone())
+            .await
+            .map_err(|_| anyhow!("Invalid or expired refresh token"))?;
+
+        let user = self.brawler_repository.find_by_id(session.brawler_id).await?;
+
+        let refresh_token = opaque_token::generate();
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::seconds(self.token_service.refresh_ttl()))
             .expect("valid timestamp")
-            .timestamp();
-            
-        let claims = Claims {
-            sub: user.id.to_string(),
-            exp: expiration as usize,
-            iat: Utc::now().timestamp() as usize,
-        };
-        
-        let token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(secret.as_bytes()),
-        )?;
+            .naive_utc();
+
+        self.session_repository
+            .rotate(
+                session.id,
+                current_hash,
+                opaque_token::hash(&refresh_token),
+                expires_at,
+                Some(client.ip_address).filter(|ip| !ip.is_empty()),
+            )
+            .await?;
 
-        // Send Email
-        self.email_service.send_password_reset_email(&user.username, &user.display_name, &token).await?;
+        let access_token = self
+            .token_service
+            .issue_access_token(user.id, session.id, Role::try_from(user.role.as_str()).unwrap_or_default())?;
+
+        Ok(self.sign_in.to_passport(access_token, refresh_token, user))
+    }
+
+    pub async fn logout(&self, brawler_id: i32, session_id: i32, client: ClientContext) -> Result<()> {
+        self.session_repository.revoke(session_id).await?;
+
+        self.sign_in
+            .audit(&client, AuthEvent::success(AuthEventKind::Logout).brawler(brawler_id))
+            .await;
 
         Ok(())
     }
 
-    pub async fn reset_password(&self, token: String, new_password: String) -> Result<()> {
-        let secret = std::env::var("JWT_USER_SECRET").unwrap_or_else(|_| "secret".to_string());
-        
-        // Decode and validate token
-        let token_data = decode::<Claims>(
-            &token,
-            &DecodingKey::from_secret(secret.as_bytes()),
-            &Validation::default(),
-        )?;
-
-        let user_id: i32 = token_data.claims.sub.parse()?;
-        
-        // Hash new password
-        let hashed_password = hash(new_password)?;
-        
-        // Update password in DB
-        self.brawler_repository.update_password(user_id, hashed_password).await?;
+    pub async fn list_sessions(&self, brawler_id: i32, current_session_id: i32) -> Result<Vec<SessionModel>> {
+        let sessions = self.session_repository.list_active_for_brawler(brawler_id).await?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionModel::from_entity(session, current_session_id))
+            .collect())
+    }
+
+    pub async fn revoke_session(&self, brawler_id: i32, session_id: i32, client: ClientContext) -> Result<()> {
+        self.session_repository.revoke_for_brawler(brawler_id, session_id).await?;
+
+        self.sign_in
+            .audit(
+                &client,
+                AuthEvent::success(AuthEventKind::SessionRevoked)
+                    .brawler(brawler_id)
+                    .reason(format!("session {}", session_id)),
+            )
+            .await;
 
         Ok(())
     }
+
+    pub async fn revoke_other_sessions(&self, brawler_id: i32, current_session_id: i32, client: ClientContext) -> Result<RevokedSessionsModel> {
+        let revoked = self.session_repository.revoke_others(brawler_id, current_session_id).await?;
+
+        self.sign_in
+            .audit(
+                &client,
+                AuthEvent::success(AuthEventKind::SessionRevoked)
+                    .brawler(brawler_id)
+                    .reason(format!("{} other sessions", revoked)),
+            )
+            .await;
+
+        Ok(RevokedSessionsModel { revoked })
+    }
+
+    pub async fn list_auth_events(&self, brawler_id: i32, limit: Option<i64>) -> Result<Vec<AuthEventModel>> {
+        let limit = limit.unwrap_or(DEFAULT_EVENT_LIMIT).clamp(1, MAX_EVENT_LIMIT);
+        let events = self.auth_event_repository.list_for_brawler(brawler_id, limit).await?;
+
+        Ok(events.into_iter().map(AuthEventModel::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::application::use_cases::sign_in::SignInService;
+    use crate::config::config_model::LoginThrottle;
+    use crate::domain::entities::sessions::SessionEntity;
+    use crate::domain::repositories::{
+        auth_events::MockAuthEventRepository,
+        brawlers::MockBrawlerRepository,
+        login_attempts::MockLoginAttemptRepository,
+        sessions::MockSessionRepository,
+        two_factor::MockTwoFactorRepository,
+    };
+    use crate::infrastructure::services::email_service::EmailService;
+
+    type TestSignIn = SignInService<MockSessionRepository, MockTwoFactorRepository, MockLoginAttemptRepository, MockAuthEventRepository>;
+    type TestUseCase = AuthenticationUseCase<
+        MockBrawlerRepository,
+        MockSessionRepository,
+        MockTwoFactorRepository,
+        MockAuthEventRepository,
+        TestSignIn,
+    >;
+
+    const SESSION_ID: i32 = 3;
+    const BRAWLER_ID: i32 = 1;
+
+    fn use_case(brawler_repository: MockBrawlerRepository, session_repository: MockSessionRepository) -> TestUseCase {
+        let sign_in = SignInService::new(
+            Arc::new(MockSessionRepository::new()),
+            Arc::new(MockTwoFactorRepository::new()),
+            Arc::new(MockLoginAttemptRepository::new()),
+            Arc::new(MockAuthEventRepository::new()),
+            LoginThrottle {
+                max_attempts_per_username: 5,
+                max_attempts_per_ip: 20,
+                lockout_seconds: 900,
+                backoff_base_seconds: 1,
+                backoff_max_seconds: 60,
+            },
+            Arc::new(EmailService::new()),
+            TokenService::fixture(),
+        );
+
+        AuthenticationUseCase::new(
+            Arc::new(brawler_repository),
+            Arc::new(session_repository),
+            Arc::new(MockTwoFactorRepository::new()),
+            Arc::new(MockAuthEventRepository::new()),
+            Arc::new(sign_in),
+            TokenService::fixture(),
+        )
+    }
+
+    fn client() -> ClientContext {
+        ClientContext {
+            ip_address: "203.0.113.7".to_string(),
+            user_agent: None,
+        }
+    }
+
+    fn refresh_model(refresh_token: &str) -> RefreshModel {
+        RefreshModel {
+            refresh_token: refresh_token.to_string(),
+        }
+    }
+
+    fn finds_session(session_repository: &mut MockSessionRepository, refresh_token: &'static str) {
+        session_repository
+            .expect_find_active_by_refresh_token_hash()
+            .returning(move |refresh_token_hash| {
+                let found = (refresh_token_hash == opaque_token::hash(refresh_token)).then(|| {
+                    let now = Utc::now().naive_utc();
+                    SessionEntity {
+                        id: SESSION_ID,
+                        brawler_id: BRAWLER_ID,
+                        refresh_token_hash,
+                        expires_at: now + Duration::days(1),
+                        revoked_at: None,
+                        created_at: now,
+                        updated_at: now,
+                        user_agent: None,
+                        ip_address: None,
+                        last_seen_at: now,
+                    }
+                });
+                Box::pin(async move { found.ok_or_else(|| anyhow!("session not found")) })
+            });
+    }
+
+    fn finds_brawler() -> MockBrawlerRepository {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository
+            .expect_find_by_id()
+            .returning(|id| Box::pin(async move { Ok(BrawlerEntity::fixture(id, "robin@example.com")) }));
+        brawler_repository
+    }
+
+    #[tokio::test]
+    async fn refresh_rotates_the_token_within_the_same_session() {
+        let mut session_repository = MockSessionRepository::new();
+        finds_session(&mut session_repository, "old-refresh-token");
+        session_repository
+            .expect_rotate()
+            .withf(|session_id, current_hash, new_hash, expires_at, ip_address| {
+                *session_id == SESSION_ID
+                    && *current_hash == opaque_token::hash("old-refresh-token")
+                    && new_hash != current_hash
+                    && *expires_at > Utc::now().naive_utc()
+                    && ip_address.as_deref() == Some("203.0.113.7")
+            })
+            .times(1)
+            .returning(|_, _, _, _, _| Box::pin(async { Ok(()) }));
+
+        let passport = use_case(finds_brawler(), session_repository)
+            .refresh(refresh_model("old-refresh-token"), client())
+            .await
+            .unwrap();
+
+        assert_ne!(passport.refresh_token, "old-refresh-token");
+        let claims = TokenService::fixture().verify(&passport.access_token).unwrap();
+        assert_eq!(claims.sub, BRAWLER_ID.to_string());
+        assert_eq!(claims.sid, Some(SESSION_ID));
+    }
+
+    #[tokio::test]
+    async fn refresh_refuses_unknown_or_revoked_tokens() {
+        let mut session_repository = MockSessionRepository::new();
+        finds_session(&mut session_repository, "live-refresh-token");
+        session_repository.expect_rotate().never();
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository.expect_find_by_id().never();
+
+        let error = use_case(brawler_repository, session_repository)
+            .refresh(refresh_model("stolen-or-expired"), client())
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Invalid or expired refresh token");
+    }
+
+    #[tokio::test]
+    async fn refresh_issues_nothing_when_the_rotation_loses_a_race() {
+        // A concurrent refresh already swapped the hash, so the conditional update matches no row
+        let mut session_repository = MockSessionRepository::new();
+        finds_session(&mut session_repository, "old-refresh-token");
+        session_repository
+            .expect_rotate()
+            .times(1)
+            .returning(|_, _, _, _, _| Box::pin(async { Err(anyhow!("Refresh token has already been used")) }));
+
+        assert!(use_case(finds_brawler(), session_repository)
+            .refresh(refresh_model("old-refresh-token"), client())
+            .await
+            .is_err());
+    }
 }
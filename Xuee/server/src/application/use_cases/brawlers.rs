@@ -1,51 +1,520 @@
+use std::fmt;
 use std::sync::Arc;
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::result::DatabaseErrorKind;
+use tracing::warn;
 
-use crate::domain::repositories::brawlers::BrawlerRepository;
-use crate::domain::value_objects::brawler_model::{RegisterBrawlerModel, AvatarUploadResponse};
-use crate::infrastructure::argon2::hash;
-use crate::infrastructure::services::image_storage::ImageStorageService;
+use crate::config::config_loader::{get_email_verification_env, get_image_upload_env, get_username_policy_env};
+use crate::domain::entities::email_verification_tokens::NewEmailVerificationTokenEntity;
+use crate::domain::entities::username_redirects::NewUsernameRedirectEntity;
+use crate::domain::repositories::{
+    brawlers::BrawlerRepository,
+    email_verification_tokens::EmailVerificationTokenRepository,
+};
+use crate::domain::value_objects::brawler_model::{
+    AvatarUploadResponse, AvatarVariant, BrawlerProfileModel, ChangeUsernameResponse, DirectoryBrawlerModel, DirectoryQuery,
+    RegisterBrawlerModel, UsernameLookup,
+};
+use crate::domain::value_objects::base64_image::Base64Image;
+use crate::domain::value_objects::mission_filter::{MissionFilter, MyMissionsView};
+use crate::domain::value_objects::mission_model::MissionModel;
+use crate::domain::value_objects::pagination::{self, Cursor, InvalidCursor, Page};
+use crate::infrastructure::argon2::{hash, verify};
+use crate::infrastructure::{opaque_token, password_policy, username_policy};
+use crate::infrastructure::image_pipeline::{self, InvalidImage};
+use crate::infrastructure::services::image_storage::ImageStorage;
 use crate::infrastructure::services::email_service::EmailService;
 
-pub struct BrawlersUseCase<T>
+// Returned when a username is already held or parked as a redirect; the router maps it to 409.
+#[derive(Debug)]
+pub struct UsernameUnavailable;
+
+impl fmt::Display for UsernameUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "This username is already taken")
+    }
+}
+
+impl std::error::Error for UsernameUnavailable {}
+
+// Returned when a brawler renames again before the cooldown ends; the router maps it to 429.
+#[derive(Debug)]
+pub struct UsernameChangeCooldown {
+    pub available_at: NaiveDateTime,
+}
+
+impl fmt::Display for UsernameChangeCooldown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Your username can be changed again after {} UTC", self.available_at.format("%Y-%m-%d %H:%M"))
+    }
+}
+
+impl std::error::Error for UsernameChangeCooldown {}
+
+// The unique index is the final word when two requests race for the same name.
+fn is_unique_violation(e: &anyhow::Error) -> bool {
+    matches!(
+        e.downcast_ref::<diesel::result::Error>(),
+        Some(diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _))
+    )
+}
+
+pub struct BrawlersUseCase<T1, T2, T3>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: EmailVerificationTokenRepository + Send + Sync,
+    T3: ImageStorage + Send + Sync,
 {
-    brawler_repository: Arc<T>,
+    brawler_repository: Arc<T1>,
+    email_verification_token_repository: Arc<T2>,
+    image_storage: Arc<T3>,
     email_service: Arc<EmailService>,
 }
 
-impl<T> BrawlersUseCase<T>
+impl<T1, T2, T3> BrawlersUseCase<T1, T2, T3>
 where
-    T: BrawlerRepository + Send + Sync,
+    T1: BrawlerRepository + Send + Sync,
+    T2: EmailVerificationTokenRepository + Send + Sync,
+    T3: ImageStorage + Send + Sync,
 {
-    pub fn new(brawler_repository: Arc<T>, email_service: Arc<EmailService>) -> Self {
-        Self { brawler_repository, email_service }
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        email_verification_token_repository: Arc<T2>,
+        image_storage: Arc<T3>,
+        email_service: Arc<EmailService>,
+    ) -> Self {
+        Self { brawler_repository, email_verification_token_repository, image_storage, email_service }
     }
 
     pub async fn register(&self, mut register_brawler_model: RegisterBrawlerModel) -> Result<i32> {
+        register_brawler_model.username = username_policy::check(&register_brawler_model.username)?;
         let email_recipient = register_brawler_model.username.clone();
-        
+
+        password_policy::check(&register_brawler_model.password, &register_brawler_model.username)?;
+        self.ensure_username_available(&register_brawler_model.username, None).await?;
+
         let hashed_password = hash(register_brawler_model.password.clone())?;
         register_brawler_model.password = hashed_password;
-        
+
         let register_entity = register_brawler_model.to_entity();
-        let id = self.brawler_repository.register(register_entity).await?;
-        
-        // Send welcome email
+        let id = self
+            .brawler_repository
+            .register(register_entity)
+            .await
+            .map_err(|e| if is_unique_violation(&e) { UsernameUnavailable.into() } else { e })?;
+
+        // Send verification email
         // We attempt to send to 'username' assuming it is an email.
-        let _ = self.email_service.send_welcome_email(&email_recipient, &email_recipient).await;
+        let _ = self
+            .send_verification(id, &email_recipient, &register_brawler_model.display_name)
+            .await;
 
         Ok(id)
     }
 
+    pub async fn verify_email(&self, token: String) -> Result<()> {
+        let verification_token = self
+            .email_verification_token_repository
+            .find_valid_by_hash(opaque_token::hash(&token))
+            .await
+            .map_err(|_| anyhow!("Invalid or expired verification token"))?;
+
+        self.email_verification_token_repository
+            .mark_used(verification_token.id)
+            .await?;
+
+        // The link only vouches for the address it was mailed to, which a rename may have replaced
+        let verified = self
+            .brawler_repository
+            .mark_email_verified(verification_token.brawler_id, verification_token.email)
+            .await?;
+        if !verified {
+            return Err(anyhow!("Invalid or expired verification token"));
+        }
+
+        // The address is proven now, so this is the right moment for the welcome mail
+        let user = self.brawler_repository.find_by_id(verification_token.brawler_id).await?;
+        let _ = self.email_service.send_welcome_email(&user.username, &user.display_name).await;
+
+        Ok(())
+    }
+
+    pub async fn get_profile(&self, brawler_id: i32) -> Result<BrawlerProfileModel> {
+        self.brawler_repository
+            .get_profile(brawler_id)
+            .await?
+            .ok_or_else(|| anyhow!("Brawler not found"))
+    }
+
+    pub async fn directory(&self, query: DirectoryQuery) -> Result<Page<DirectoryBrawlerModel>> {
+        let sort = query.sort;
+        let after = query
+            .cursor
+            .as_deref()
+            .map(|cursor| Cursor::decode(cursor, sort.as_str()))
+            .transpose()?;
+        let after_value = after
+            .map(|cursor| cursor.value.parse::<i64>().map(|value| (value, cursor.id)))
+            .transpose()
+            .map_err(|_| InvalidCursor)?;
+        let size = pagination::page_size(query.limit);
+
+        let rows = self
+            .brawler_repository
+            .search_directory(query.q, sort, after_value, size + 1)
+            .await?;
+
+        Ok(pagination::into_page(rows, size, |brawler| {
+            Cursor::new(sort.as_str(), brawler.sort_value(sort).to_string(), brawler.id)
+        }))
+    }
+
+    // Former names keep resolving for a while so shared links survive a rename.
+    pub async fn find_by_username(&self, username: String) -> Result<UsernameLookup> {
+        if let Ok(user) = self.brawler_repository.find_by_username(username.clone()).await {
+            if let Some(profile) = self.brawler_repository.get_profile(user.id).await? {
+                return Ok(UsernameLookup::Profile(profile));
+            }
+        }
+
+        self.brawler_repository
+            .find_username_redirect(username)
+            .await?
+            .map(UsernameLookup::Redirect)
+            .ok_or_else(|| anyhow!("Brawler not found"))
+    }
+
+    pub async fn change_username(&self, user_id: i32, new_username: String) -> Result<ChangeUsernameResponse> {
+        let policy = get_username_policy_env()?;
+        let user = self.brawler_repository.find_by_id(user_id).await?;
+
+        let new_username = username_policy::check(&new_username)?;
+        if new_username == user.username {
+            return Err(username_policy::UsernamePolicyViolation("That is already your username".to_string()).into());
+        }
+
+        let now = Utc::now().naive_utc();
+        if let Some(changed_at) = user.username_changed_at {
+            let available_at = changed_at + Duration::days(policy.change_cooldown_days);
+            if available_at > now {
+                return Err(UsernameChangeCooldown { available_at }.into());
+            }
+        }
+
+        self.ensure_username_available(&new_username, Some(user_id)).await?;
+
+        let redirect_expires_at = now + Duration::days(policy.redirect_days);
+        self.brawler_repository
+            .change_username(
+                user_id,
+                new_username.clone(),
+                NewUsernameRedirectEntity {
+                    old_username: user.username.clone(),
+                    brawler_id: user_id,
+                    expires_at: redirect_expires_at,
+                },
+            )
+            .await
+            .map_err(|e| if is_unique_violation(&e) { UsernameUnavailable.into() } else { e })?;
+
+        // The username is the sign-in address, so a new one has to be proven like at registration
+        if new_username.to_lowercase() != user.username.to_lowercase() {
+            let _ = self.send_verification(user_id, &new_username, &user.display_name).await;
+        }
+
+        Ok(ChangeUsernameResponse {
+            username: new_username,
+            previous_username: user.username,
+            redirect_expires_at,
+            next_change_available_at: now + Duration::days(policy.change_cooldown_days),
+        })
+    }
+
+    async fn ensure_username_available(&self, username: &str, except_brawler_id: Option<i32>) -> Result<()> {
+        if self
+            .brawler_repository
+            .is_username_taken(username.to_string(), except_brawler_id)
+            .await?
+        {
+            return Err(UsernameUnavailable.into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn my_missions(&self, user_id: i32, view: MyMissionsView, filter: MissionFilter) -> Result<Page<MissionModel>> {
+        let ordering = filter.ordering(view.default_ordering());
+        filter.after(ordering)?;
+
+        let rows = self.brawler_repository.get_missions(user_id, view, &filter).await?;
+        let total_count = self.brawler_repository.count_missions(user_id, view, &filter).await?;
+
+        Ok(filter.into_page(rows, total_count, ordering))
+    }
+
     pub async fn upload_avatar(&self, user_id: i32, base64_string: String) -> Result<AvatarUploadResponse> {
-        let url = ImageStorageService::upload(&base64_string).await?;
-        self.brawler_repository.update_avatar(user_id, url.clone()).await?;
-        Ok(AvatarUploadResponse { url })
+        let env = get_image_upload_env()?;
+        let image = Base64Image::new(&base64_string, env.max_bytes).map_err(|e| InvalidImage(e.to_string()))?;
+
+        // Decoding and resizing are CPU-bound, so keep them off the async workers
+        let encoded = tokio::task::spawn_blocking(move || image_pipeline::process_avatar(&image.into_inner(), &env)).await??;
+
+        let user = self.brawler_repository.find_by_id(user_id).await?;
+        let public_id = format!("avatars/{}/{}", user_id, &opaque_token::generate()[..16]);
+
+        let mut variants = Vec::with_capacity(encoded.len());
+        for image in encoded {
+            let key = image_pipeline::variant_key(&public_id, image.size, image.extension);
+            match self.image_storage.put(&key, image.bytes, image.content_type).await {
+                Ok(url) => variants.push(AvatarVariant { size: image.size, url }),
+                Err(e) => {
+                    self.delete_avatar(&public_id).await;
+                    return Err(e);
+                }
+            }
+        }
+
+        // AVATAR_SIZES is largest first; that one becomes the canonical avatar_url
+        let url = variants[0].url.clone();
+        if let Err(e) = self.brawler_repository.update_avatar(user_id, url.clone(), public_id.clone()).await {
+            self.delete_avatar(&public_id).await;
+            return Err(e);
+        }
+
+        if let Some(previous) = user.avatar_public_id {
+            self.delete_avatar(&previous).await;
+        }
+
+        Ok(AvatarUploadResponse { url, variants })
+    }
+
+    // Best effort: a leftover file is only wasted space, so failures are logged, not returned.
+    async fn delete_avatar(&self, public_id: &str) {
+        for size in image_pipeline::AVATAR_SIZES {
+            let key = image_pipeline::variant_key(public_id, size, "webp");
+            if let Err(e) = self.image_storage.delete(&key).await {
+                warn!("Failed to delete avatar image {}: {}", key, e);
+            }
+        }
     }
 
     pub async fn update_display_name(&self, user_id: i32, display_name: String) -> Result<()> {
         self.brawler_repository.update_display_name(user_id, display_name).await
     }
+
+    // The session making the change stays signed in; every other one is revoked with the new hash.
+    pub async fn change_password(
+        &self,
+        user_id: i32,
+        session_id: i32,
+        current_password: String,
+        new_password: String,
+    ) -> Result<()> {
+        let user = self.brawler_repository.find_by_id(user_id).await?;
+        if !verify(current_password, user.password)? {
+            return Err(anyhow!("Current password is incorrect"));
+        }
+
+        password_policy::check(&new_password, &user.username)?;
+
+        let hashed_password = hash(new_password)?;
+        self.brawler_repository
+            .update_password(user_id, hashed_password, Some(session_id))
+            .await
+    }
+
+    async fn send_verification(&self, brawler_id: i32, email: &str, display_name: &str) -> Result<()> {
+        // Only the newest link stays usable
+        self.email_verification_token_repository
+            .invalidate_all_for_brawler(brawler_id)
+            .await?;
+
+        let token = opaque_token::generate();
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::seconds(get_email_verification_env()?.ttl))
+            .expect("valid timestamp")
+            .naive_utc();
+
+        self.email_verification_token_repository
+            .create(NewEmailVerificationTokenEntity {
+                brawler_id,
+                token_hash: opaque_token::hash(&token),
+                expires_at,
+                email: email.to_string(),
+            })
+            .await?;
+
+        self.email_service.send_verification_email(email, display_name, &token).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use data_encoding::BASE64;
+    use image::{DynamicImage, ImageFormat, Rgb, RgbImage};
+
+    use crate::domain::entities::brawlers::BrawlerEntity;
+    use crate::domain::entities::email_verification_tokens::EmailVerificationTokenEntity;
+    use crate::domain::repositories::brawlers::MockBrawlerRepository;
+    use crate::domain::repositories::email_verification_tokens::MockEmailVerificationTokenRepository;
+    use crate::infrastructure::services::image_storage::MockImageStorage;
+
+    const AVATAR_SIZES_LEN: usize = image_pipeline::AVATAR_SIZES.len();
+
+    fn png_upload() -> String {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::from_pixel(300, 200, Rgb([10, 120, 200])))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        format!("data:image/png;base64,{}", BASE64.encode(&bytes))
+    }
+
+    fn use_case(
+        brawler_repository: MockBrawlerRepository,
+        image_storage: MockImageStorage,
+    ) -> BrawlersUseCase<MockBrawlerRepository, MockEmailVerificationTokenRepository, MockImageStorage> {
+        BrawlersUseCase::new(
+            Arc::new(brawler_repository),
+            Arc::new(MockEmailVerificationTokenRepository::new()),
+            Arc::new(image_storage),
+            Arc::new(EmailService::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn upload_avatar_stores_every_variant_and_drops_the_old_one() {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository.expect_find_by_id().returning(|id| {
+            let user = BrawlerEntity {
+                avatar_public_id: Some("avatars/7/previous".to_string()),
+                ..BrawlerEntity::fixture(id, "robin@example.com")
+            };
+            Box::pin(async move { Ok(user) })
+        });
+        brawler_repository
+            .expect_update_avatar()
+            .withf(|id, url, public_id| *id == 7 && url == &format!("https://cdn.test/{}_512.webp", public_id))
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let mut image_storage = MockImageStorage::new();
+        image_storage
+            .expect_put()
+            .withf(|key, bytes, content_type| key.starts_with("avatars/7/") && !bytes.is_empty() && content_type == "image/webp")
+            .times(AVATAR_SIZES_LEN)
+            .returning(|key, _, _| {
+                let url = format!("https://cdn.test/{}", key);
+                Box::pin(async move { Ok(url) })
+            });
+        for size in image_pipeline::AVATAR_SIZES {
+            let old_key = image_pipeline::variant_key("avatars/7/previous", size, "webp");
+            image_storage
+                .expect_delete()
+                .withf(move |key| key == old_key)
+                .times(1)
+                .returning(|_| Box::pin(async { Ok(()) }));
+        }
+
+        let response = use_case(brawler_repository, image_storage)
+            .upload_avatar(7, png_upload())
+            .await
+            .unwrap();
+
+        let sizes: Vec<u32> = response.variants.iter().map(|variant| variant.size).collect();
+        assert_eq!(sizes, image_pipeline::AVATAR_SIZES.to_vec());
+        for variant in &response.variants {
+            assert!(variant.url.ends_with(&format!("_{}.webp", variant.size)));
+        }
+        assert_eq!(response.url, response.variants[0].url);
+    }
+
+    #[tokio::test]
+    async fn upload_avatar_removes_partial_uploads_when_storage_fails() {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository
+            .expect_find_by_id()
+            .returning(|id| Box::pin(async move { Ok(BrawlerEntity::fixture(id, "robin@example.com")) }));
+        brawler_repository.expect_update_avatar().never();
+
+        let mut image_storage = MockImageStorage::new();
+        let mut calls = 0;
+        image_storage.expect_put().times(2).returning(move |key, _, _| {
+            calls += 1;
+            let result = if calls == 1 {
+                Ok(format!("https://cdn.test/{}", key))
+            } else {
+                Err(anyhow!("storage is down"))
+            };
+            Box::pin(async move { result })
+        });
+        image_storage
+            .expect_delete()
+            .withf(|key| key.starts_with("avatars/7/") && key.ends_with(".webp"))
+            .times(AVATAR_SIZES_LEN)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let result = use_case(brawler_repository, image_storage).upload_avatar(7, png_upload()).await;
+
+        assert_eq!(result.unwrap_err().to_string(), "storage is down");
+    }
+
+    #[tokio::test]
+    async fn upload_avatar_rejects_unsupported_images_before_storing_anything() {
+        let upload = format!("data:image/svg+xml;base64,{}", BASE64.encode(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>"));
+
+        // Neither mock has expectations, so any repository or storage call fails the test
+        let result = use_case(MockBrawlerRepository::new(), MockImageStorage::new())
+            .upload_avatar(7, upload)
+            .await;
+
+        assert!(result.unwrap_err().is::<InvalidImage>());
+    }
+
+    fn verification_token_mailed_to(email: &'static str) -> MockEmailVerificationTokenRepository {
+        let mut email_verification_token_repository = MockEmailVerificationTokenRepository::new();
+        email_verification_token_repository
+            .expect_find_valid_by_hash()
+            .returning(move |token_hash| {
+                let now = Utc::now().naive_utc();
+                let verification_token = EmailVerificationTokenEntity {
+                    id: 1,
+                    brawler_id: 7,
+                    token_hash,
+                    expires_at: now + Duration::days(1),
+                    used_at: None,
+                    created_at: now,
+                    email: email.to_string(),
+                };
+                Box::pin(async move { Ok(verification_token) })
+            });
+        email_verification_token_repository
+            .expect_mark_used()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+        email_verification_token_repository
+    }
+
+    #[tokio::test]
+    async fn verification_links_do_not_vouch_for_a_renamed_address() {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository
+            .expect_mark_email_verified()
+            .withf(|id, email| *id == 7 && email == "mine@example.com")
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(false) }));
+        brawler_repository.expect_find_by_id().never();
+
+        let use_case = BrawlersUseCase::new(
+            Arc::new(brawler_repository),
+            Arc::new(verification_token_mailed_to("mine@example.com")),
+            Arc::new(MockImageStorage::new()),
+            Arc::new(EmailService::new()),
+        );
+
+        let error = use_case.verify_email("verify-token".to_string()).await.unwrap_err();
+        assert_eq!(error.to_string(), "Invalid or expired verification token");
+    }
 }
@@ -0,0 +1,306 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::{Utc, Duration};
+use tracing::warn;
+
+use crate::application::use_cases::sign_in::{LoginOutcome, SignIn, MAGIC_LINK_SCOPE};
+use crate::config::config_loader::get_magic_link_env;
+use crate::domain::entities::magic_link_tokens::NewMagicLinkTokenEntity;
+use crate::domain::repositories::{
+    brawlers::BrawlerRepository,
+    magic_link_tokens::MagicLinkTokenRepository,
+};
+use crate::domain::value_objects::auth_event_model::{AuthEvent, AuthEventKind, ClientContext};
+use crate::infrastructure::opaque_token;
+use crate::infrastructure::services::email_service::EmailService;
+
+pub struct MagicLinkUseCase<T1, T2, T3>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: MagicLinkTokenRepository + Send + Sync,
+    T3: SignIn + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    magic_link_token_repository: Arc<T2>,
+    sign_in: Arc<T3>,
+    email_service: Arc<EmailService>,
+}
+
+impl<T1, T2, T3> MagicLinkUseCase<T1, T2, T3>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: MagicLinkTokenRepository + Send + Sync,
+    T3: SignIn + Send + Sync,
+{
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        magic_link_token_repository: Arc<T2>,
+        sign_in: Arc<T3>,
+        email_service: Arc<EmailService>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            magic_link_token_repository,
+            sign_in,
+            email_service,
+        }
+    }
+
+    pub async fn request_magic_link(&self, address: String, client: ClientContext) -> Result<()> {
+        let address = address.trim().to_string();
+        let address_key = address.to_lowercase();
+        if !address_key.contains('@') {
+            return Err(anyhow!("Enter the email address you signed up with"));
+        }
+        let magic_link_env = get_magic_link_env()?;
+
+        // Throttled per address whether or not it has an account, so neither the limit nor the
+        // response reveals which addresses are registered.
+        if let Err(e) = self.sign_in.ensure_not_throttled(MAGIC_LINK_SCOPE, &address_key).await {
+            self.sign_in
+                .audit(&client, AuthEvent::failure(AuthEventKind::MagicLinkRequested, "throttled").username(address_key))
+                .await;
+            return Err(e);
+        }
+        let requests = self.sign_in.record_failure(MAGIC_LINK_SCOPE, &address_key).await?;
+        if requests.failed_count >= magic_link_env.max_per_address {
+            self.sign_in.lock(MAGIC_LINK_SCOPE, &address_key).await?;
+        }
+
+        let Ok(user) = self.brawler_repository.find_by_username(address).await else {
+            self.sign_in
+                .audit(&client, AuthEvent::failure(AuthEventKind::MagicLinkRequested, "unknown_user").username(address_key))
+                .await;
+            return Ok(());
+        };
+
+        // Only the newest link stays usable
+        self.magic_link_token_repository
+            .invalidate_all_for_brawler(user.id)
+            .await?;
+
+        let token = opaque_token::generate();
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::seconds(magic_link_env.ttl))
+            .expect("valid timestamp")
+            .naive_utc();
+
+        self.magic_link_token_repository
+            .create(NewMagicLinkTokenEntity {
+                brawler_id: user.id,
+                token_hash: opaque_token::hash(&token),
+                expires_at,
+                email: user.username.clone(),
+            })
+            .await?;
+
+        // Reported like an unknown address would be; a 500 here would confirm the account exists
+        if let Err(e) = self
+            .email_service
+            .send_magic_link_email(&user.username, &user.display_name, &token, magic_link_env.ttl / 60)
+            .await
+        {
+            warn!("Failed to send magic link to brawler {}: {}", user.id, e);
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::MagicLinkRequested, "email_failed")
+                        .brawler(user.id)
+                        .username(user.username.clone()),
+                )
+                .await;
+            return Ok(());
+        }
+
+        self.sign_in
+            .audit(
+                &client,
+                AuthEvent::success(AuthEventKind::MagicLinkRequested)
+                    .brawler(user.id)
+                    .username(user.username.clone()),
+            )
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn consume_magic_link(&self, token: String, client: ClientContext) -> Result<LoginOutcome> {
+        let magic_link = match self
+            .magic_link_token_repository
+            .find_valid_by_hash(opaque_token::hash(&token))
+            .await
+        {
+            Ok(magic_link) => magic_link,
+            Err(_) => {
+                self.sign_in
+                    .audit(&client, AuthEvent::failure(AuthEventKind::MagicLinkLogin, "invalid_token"))
+                    .await;
+                return Err(anyhow!("Invalid or expired sign-in link"));
+            }
+        };
+
+        // Fails if another request consumed the link first
+        self.magic_link_token_repository
+            .mark_used(magic_link.id)
+            .await?;
+
+        // Opening the link proves the brawler controls the address it was mailed to. After a rename
+        // that address is no longer theirs, so the link signs nobody in.
+        let verified = self
+            .brawler_repository
+            .mark_email_verified(magic_link.brawler_id, magic_link.email)
+            .await?;
+        if !verified {
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::MagicLinkLogin, "address_changed").brawler(magic_link.brawler_id),
+                )
+                .await;
+            return Err(anyhow!("Invalid or expired sign-in link"));
+        }
+
+        let user = self.brawler_repository.find_by_id(magic_link.brawler_id).await?;
+        self.sign_in
+            .complete_login(user, AuthEvent::success(AuthEventKind::MagicLinkLogin), &client)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::eq;
+
+    use crate::application::use_cases::sign_in::SignInService;
+    use crate::config::config_model::LoginThrottle;
+    use crate::domain::entities::brawlers::BrawlerEntity;
+    use crate::domain::entities::magic_link_tokens::MagicLinkTokenEntity;
+    use crate::domain::repositories::{
+        auth_events::MockAuthEventRepository,
+        brawlers::MockBrawlerRepository,
+        login_attempts::MockLoginAttemptRepository,
+        magic_link_tokens::MockMagicLinkTokenRepository,
+        sessions::MockSessionRepository,
+        two_factor::MockTwoFactorRepository,
+    };
+    use crate::infrastructure::services::token_service::TokenService;
+
+    type TestSignIn = SignInService<MockSessionRepository, MockTwoFactorRepository, MockLoginAttemptRepository, MockAuthEventRepository>;
+
+    const BRAWLER_ID: i32 = 7;
+
+    fn sign_in(session_repository: MockSessionRepository, two_factor_repository: MockTwoFactorRepository) -> Arc<TestSignIn> {
+        let mut auth_event_repository = MockAuthEventRepository::new();
+        auth_event_repository
+            .expect_record()
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        Arc::new(SignInService::new(
+            Arc::new(session_repository),
+            Arc::new(two_factor_repository),
+            Arc::new(MockLoginAttemptRepository::new()),
+            Arc::new(auth_event_repository),
+            LoginThrottle {
+                max_attempts_per_username: 5,
+                max_attempts_per_ip: 20,
+                lockout_seconds: 900,
+                backoff_base_seconds: 1,
+                backoff_max_seconds: 60,
+            },
+            Arc::new(EmailService::new()),
+            TokenService::fixture(),
+        ))
+    }
+
+    // A live, unused link that was mailed to `email`
+    fn mailed_to(email: &'static str) -> MockMagicLinkTokenRepository {
+        let mut magic_link_token_repository = MockMagicLinkTokenRepository::new();
+        magic_link_token_repository
+            .expect_find_valid_by_hash()
+            .with(eq(opaque_token::hash("link-token")))
+            .returning(move |token_hash| {
+                let now = Utc::now().naive_utc();
+                let magic_link = MagicLinkTokenEntity {
+                    id: 1,
+                    brawler_id: BRAWLER_ID,
+                    token_hash,
+                    expires_at: now + Duration::minutes(15),
+                    used_at: None,
+                    created_at: now,
+                    email: email.to_string(),
+                };
+                Box::pin(async move { Ok(magic_link) })
+            });
+        magic_link_token_repository
+            .expect_mark_used()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+        magic_link_token_repository
+    }
+
+    fn client() -> ClientContext {
+        ClientContext {
+            ip_address: "203.0.113.7".to_string(),
+            user_agent: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_a_link_mailed_to_an_address_the_brawler_renamed_away_from() {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        // The repository compares against the current username, which is now someone else's address
+        brawler_repository
+            .expect_mark_email_verified()
+            .with(eq(BRAWLER_ID), eq("mine@example.com".to_string()))
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(false) }));
+        brawler_repository.expect_find_by_id().never();
+
+        let mut session_repository = MockSessionRepository::new();
+        session_repository.expect_create().never();
+
+        let use_case = MagicLinkUseCase::new(
+            Arc::new(brawler_repository),
+            Arc::new(mailed_to("mine@example.com")),
+            sign_in(session_repository, MockTwoFactorRepository::new()),
+            Arc::new(EmailService::new()),
+        );
+
+        let error = use_case.consume_magic_link("link-token".to_string(), client()).await.unwrap_err();
+        assert_eq!(error.to_string(), "Invalid or expired sign-in link");
+    }
+
+    #[tokio::test]
+    async fn signs_in_and_verifies_the_address_the_link_was_mailed_to() {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository
+            .expect_mark_email_verified()
+            .with(eq(BRAWLER_ID), eq("robin@example.com".to_string()))
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(true) }));
+        brawler_repository
+            .expect_find_by_id()
+            .returning(|id| Box::pin(async move { Ok(BrawlerEntity::fixture(id, "robin@example.com")) }));
+
+        let mut two_factor_repository = MockTwoFactorRepository::new();
+        two_factor_repository
+            .expect_find_by_brawler_id()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_create()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(1) }));
+
+        let use_case = MagicLinkUseCase::new(
+            Arc::new(brawler_repository),
+            Arc::new(mailed_to("robin@example.com")),
+            sign_in(session_repository, two_factor_repository),
+            Arc::new(EmailService::new()),
+        );
+
+        let outcome = use_case.consume_magic_link("link-token".to_string(), client()).await.unwrap();
+        assert!(matches!(outcome, LoginOutcome::Passport(passport) if passport.username == "robin@example.com"));
+    }
+}
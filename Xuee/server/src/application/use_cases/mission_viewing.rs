@@ -2,7 +2,11 @@ use std::sync::Arc;
 use anyhow::Result;
 
 use crate::domain::repositories::mission_viewing::MissionViewingRepository;
-use crate::domain::value_objects::{mission_filter::MissionFilter, mission_model::MissionModel};
+use crate::domain::value_objects::{
+    mission_filter::{MissionFilter, DEFAULT_MISSION_ORDERING},
+    mission_model::MissionModel,
+    pagination::Page,
+};
 
 pub struct MissionViewingUseCase<T>
 where
@@ -21,8 +25,14 @@ where
         }
     }
 
-    pub async fn get_all(&self, filter: &MissionFilter) -> Result<Vec<MissionModel>> {
-        self.mission_viewing_repository.gets(filter).await
+    pub async fn get_all(&self, filter: &MissionFilter) -> Result<Page<MissionModel>> {
+        let ordering = filter.ordering(DEFAULT_MISSION_ORDERING);
+        filter.after(ordering)?;
+
+        let rows = self.mission_viewing_repository.gets(filter).await?;
+        let total_count = self.mission_viewing_repository.count(filter).await?;
+
+        Ok(filter.into_page(rows, total_count, ordering))
     }
 
     pub async fn get_one(&self, mission_id: i32) -> Result<MissionModel> {
@@ -7,8 +7,9 @@ use crate::domain::{
         mission_viewing::MissionViewingRepository,
     },
     value_objects::{
-        mission_filter::MissionFilter,
+        mission_filter::{MissionFilter, DEFAULT_MISSION_ORDERING},
         mission_model::{AddMissionModel, MissionModel},
+        pagination::Page,
     },
 };
 
@@ -34,8 +35,20 @@ where
         self.repository.create(model.to_entity(chief_id)).await
     }
 
-    pub async fn get_all(&self, filter: MissionFilter) -> Result<Vec<MissionModel>> {
-        self.viewing_repository.gets(&filter).await
+    pub async fn get_all(&self, filter: MissionFilter) -> Result<Page<MissionModel>> {
+        let ordering = filter.ordering(DEFAULT_MISSION_ORDERING);
+        filter.after(ordering)?;
+
+        let rows = self.viewing_repository.gets(&filter).await?;
+        let total_count = self.viewing_repository.count(&filter).await?;
+
+        Ok(filter.into_page(rows, total_count, ordering))
+    }
+
+    // Missions led or joined by anyone the brawler is mutual friends with.
+    pub async fn get_friends_missions(&self, brawler_id: i32, mut filter: MissionFilter) -> Result<Page<MissionModel>> {
+        filter.friends_of = Some(brawler_id);
+        self.get_all(filter).await
     }
 
     pub async fn join(&self, mission_id: i32, brawler_id: i32) -> Result<()> {
@@ -1,9 +1,20 @@
 pub mod cards;
 pub mod brawlers;
 pub mod authentication;
+pub mod sign_in;
+pub mod magic_link;
+pub mod oidc;
+pub mod password_reset;
 pub mod mission_management;
 pub mod missions;
 pub mod mission_operation;
 pub mod mission_viewing;
 pub mod crew_operation;
 
+pub mod two_factor;
+pub mod admin;
+pub mod api_tokens;
+pub mod passkeys;
+pub mod passkey_login;
+pub mod accounts;
+pub mod social;
@@ -0,0 +1,701 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use rand::{distr::Alphanumeric, Rng};
+
+use crate::application::use_cases::sign_in::{LoginOutcome, SignIn};
+use crate::config::config_loader::get_username_policy_env;
+use crate::domain::entities::brawler_identities::NewBrawlerIdentityEntity;
+use crate::domain::entities::brawlers::RegisterBrawlerEntity;
+use crate::domain::repositories::{
+    brawler_identities::BrawlerIdentityRepository,
+    brawlers::BrawlerRepository,
+};
+use crate::domain::value_objects::auth_event_model::{AuthEvent, AuthEventKind, ClientContext};
+use crate::domain::value_objects::identity_model::{BrawlerIdentityModel, OAuthFlow, OidcCallbackModel};
+use crate::infrastructure::argon2::hash;
+use crate::infrastructure::{opaque_token, username_policy};
+use crate::infrastructure::services::email_service::EmailService;
+use crate::infrastructure::services::oidc_service::{OidcService, OidcUserInfo};
+
+const MAX_USERNAME_ATTEMPTS: u32 = 100;
+
+pub struct OidcUseCase<T1, T2, T3>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: BrawlerIdentityRepository + Send + Sync,
+    T3: SignIn + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    brawler_identity_repository: Arc<T2>,
+    sign_in: Arc<T3>,
+    email_service: Arc<EmailService>,
+    oidc_service: Arc<OidcService>,
+}
+
+impl<T1, T2, T3> OidcUseCase<T1, T2, T3>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: BrawlerIdentityRepository + Send + Sync,
+    T3: SignIn + Send + Sync,
+{
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        brawler_identity_repository: Arc<T2>,
+        sign_in: Arc<T3>,
+        email_service: Arc<EmailService>,
+        oidc_service: Arc<OidcService>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            brawler_identity_repository,
+            sign_in,
+            email_service,
+            oidc_service,
+        }
+    }
+
+    pub fn oidc_providers(&self) -> Vec<String> {
+        self.oidc_service.provider_names()
+    }
+
+    pub async fn begin_oidc(&self, provider: &str) -> Result<(String, OAuthFlow)> {
+        let request = self.oidc_service.authorization_url(provider).await?;
+
+        Ok((
+            request.url,
+            OAuthFlow {
+                provider: provider.to_string(),
+                state: request.state,
+                pkce_verifier: request.pkce_verifier,
+                nonce: request.nonce,
+            },
+        ))
+    }
+
+    pub async fn login_with_oidc(
+        &self,
+        flow: Option<OAuthFlow>,
+        provider: &str,
+        callback: OidcCallbackModel,
+        client: ClientContext,
+    ) -> Result<LoginOutcome> {
+        let user_info = match self.finish_oidc(flow, provider, callback).await {
+            Ok(user_info) => user_info,
+            Err(e) => {
+                self.sign_in
+                    .audit(&client, AuthEvent::failure(AuthEventKind::OidcLogin, e.to_string()).provider(provider))
+                    .await;
+                return Err(e);
+            }
+        };
+
+        let user_id = match self
+            .brawler_identity_repository
+            .find_by_provider_subject(provider.to_string(), user_info.subject.clone())
+            .await?
+        {
+            Some(identity) => identity.brawler_id,
+            None => {
+                let user_id = match self.find_or_register_for_identity(provider, &user_info).await {
+                    Ok(user_id) => user_id,
+                    Err(e) => {
+                        let mut event = AuthEvent::failure(AuthEventKind::OidcLogin, e.to_string()).provider(provider);
+                        if let Some(email) = user_info.email.as_ref() {
+                            event = event.username(email.clone());
+                        }
+                        self.sign_in.audit(&client, event).await;
+                        return Err(e);
+                    }
+                };
+                self.brawler_identity_repository
+                    .link(NewBrawlerIdentityEntity {
+                        brawler_id: user_id,
+                        provider: provider.to_string(),
+                        subject: user_info.subject.clone(),
+                        email: user_info.email.clone(),
+                    })
+                    .await?;
+                user_id
+            }
+        };
+
+        let user = self.brawler_repository.find_by_id(user_id).await?;
+
+        // The provider has already proven the address belongs to this user; it only counts while it
+        // is also their username
+        if let Some(email) = user_info.email.clone().filter(|_| user_info.email_verified) {
+            self.brawler_repository.mark_email_verified(user_id, email).await?;
+        }
+
+        self.sign_in
+            .complete_login(user, AuthEvent::success(AuthEventKind::OidcLogin).provider(provider), &client)
+            .await
+    }
+
+    pub async fn list_identities(&self, brawler_id: i32) -> Result<Vec<BrawlerIdentityModel>> {
+        let identities = self.brawler_identity_repository.list_for_brawler(brawler_id).await?;
+
+        Ok(identities.into_iter().map(BrawlerIdentityModel::from).collect())
+    }
+
+    pub async fn link_identity(
+        &self,
+        brawler_id: i32,
+        flow: Option<OAuthFlow>,
+        provider: &str,
+        callback: OidcCallbackModel,
+        client: ClientContext,
+    ) -> Result<()> {
+        let user_info = match self.finish_oidc(flow, provider, callback).await {
+            Ok(user_info) => user_info,
+            Err(e) => {
+                self.sign_in
+                    .audit(
+                        &client,
+                        AuthEvent::failure(AuthEventKind::IdentityLinked, e.to_string())
+                            .brawler(brawler_id)
+                            .provider(provider),
+                    )
+                    .await;
+                return Err(e);
+            }
+        };
+
+        if let Some(identity) = self
+            .brawler_identity_repository
+            .find_by_provider_subject(provider.to_string(), user_info.subject.clone())
+            .await?
+        {
+            if identity.brawler_id == brawler_id {
+                return Ok(());
+            }
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::IdentityLinked, "linked_to_another_brawler")
+                        .brawler(brawler_id)
+                        .provider(provider),
+                )
+                .await;
+            return Err(anyhow!("This {} account is already linked to another brawler", provider));
+        }
+
+        self.brawler_identity_repository
+            .link(NewBrawlerIdentityEntity {
+                brawler_id,
+                provider: provider.to_string(),
+                subject: user_info.subject,
+                email: user_info.email,
+            })
+            .await?;
+
+        self.sign_in
+            .audit(
+                &client,
+                AuthEvent::success(AuthEventKind::IdentityLinked)
+                    .brawler(brawler_id)
+                    .provider(provider),
+            )
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn unlink_identity(&self, brawler_id: i32, provider: &str, client: ClientContext) -> Result<()> {
+        let identities = self.brawler_identity_repository.list_for_brawler(brawler_id).await?;
+        let user = self.brawler_repository.find_by_id(brawler_id).await?;
+
+        // Without a verified email the password cannot be reset, so the last login must stay
+        if identities.len() <= 1 && user.email_verified_at.is_none() {
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::IdentityUnlinked, "last_login_unverified")
+                        .brawler(brawler_id)
+                        .provider(provider),
+                )
+                .await;
+            return Err(anyhow!("Verify your email before removing your last linked login"));
+        }
+
+        self.brawler_identity_repository
+            .unlink(brawler_id, provider.to_string())
+            .await?;
+
+        self.sign_in
+            .audit(
+                &client,
+                AuthEvent::success(AuthEventKind::IdentityUnlinked)
+                    .brawler(brawler_id)
+                    .provider(provider),
+            )
+            .await;
+
+        Ok(())
+    }
+
+    async fn finish_oidc(&self, flow: Option<OAuthFlow>, provider: &str, callback: OidcCallbackModel) -> Result<OidcUserInfo> {
+        let flow = flow.ok_or_else(|| anyhow!("Sign-in session expired, please start again"))?;
+
+        // Compare digests so the check does not leak how much of the state matched
+        if flow.provider != provider || opaque_token::hash(&flow.state) != opaque_token::hash(&callback.state) {
+            return Err(anyhow!("OAuth state does not match"));
+        }
+
+        self.oidc_service
+            .exchange_code(provider, callback.code, flow.pkce_verifier, flow.nonce)
+            .await
+    }
+
+    async fn find_or_register_for_identity(&self, provider: &str, user_info: &OidcUserInfo) -> Result<i32> {
+        if let Some(email) = user_info.email.as_ref() {
+            if let Ok(user) = self.brawler_repository.find_by_username(email.clone()).await {
+                // Only attach to an existing account when both sides have proven the address,
+                // otherwise whoever registered the email first could take over the login.
+                if user_info.email_verified && user.email_verified_at.is_some() {
+                    return Ok(user.id);
+                }
+                return Err(anyhow!(
+                    "An account with this email already exists. Sign in with your password and link {} from your settings",
+                    provider
+                ));
+            }
+        }
+
+        // Register new user
+        let password: String = rand::rng()
+            .sample_iter(&Alphanumeric)
+            .take(16)
+            .map(char::from)
+            .collect();
+
+        let username = self.pick_username(provider, user_info).await?;
+        let display_name = user_info.name.clone().unwrap_or_else(|| username.clone());
+
+        let register_entity = RegisterBrawlerEntity {
+            username: username.clone(),
+            password: hash(password)?,
+            display_name: display_name.clone(),
+        };
+
+        let id = self.brawler_repository.register(register_entity).await?;
+
+        // Send welcome email
+        if let Some(email) = user_info.email.as_ref() {
+            let _ = self.email_service.send_welcome_email(email, &display_name).await;
+        }
+
+        Ok(id)
+    }
+
+    // Usernames double as sign-in addresses, so the provider's email is kept when the policy accepts
+    // it. Otherwise the name comes from the provider handle or the email's local part, with "-2",
+    // "-3", ... appended until it is free.
+    async fn pick_username(&self, provider: &str, user_info: &OidcUserInfo) -> Result<String> {
+        if let Some(email) = user_info.email.as_deref() {
+            if let Ok(username) = username_policy::check(email) {
+                if !self.brawler_repository.is_username_taken(username.clone(), None).await? {
+                    return Ok(username);
+                }
+            }
+        }
+
+        let local_part = user_info.email.as_deref().and_then(|email| email.split('@').next());
+        let base = [user_info.preferred_username.as_deref(), local_part]
+            .into_iter()
+            .flatten()
+            .map(username_candidate)
+            .chain(std::iter::once(format!("{}-user", username_candidate(provider))))
+            .find_map(|candidate| username_policy::check(&candidate).ok())
+            .ok_or_else(|| anyhow!("Could not pick a username for this {} account", provider))?;
+
+        let max_length = get_username_policy_env()?.max_length;
+        for attempt in 1..=MAX_USERNAME_ATTEMPTS {
+            let username = match attempt {
+                1 => base.clone(),
+                n => {
+                    let suffix = format!("-{}", n);
+                    let stem: String = base.chars().take(max_length.saturating_sub(suffix.len())).collect();
+                    format!("{}{}", stem, suffix)
+                }
+            };
+            let Ok(username) = username_policy::check(&username) else {
+                continue;
+            };
+            if !self.brawler_repository.is_username_taken(username.clone(), None).await? {
+                return Ok(username);
+            }
+        }
+
+        Err(anyhow!("Could not pick a username for this {} account", provider))
+    }
+}
+
+// Drops whatever the username policy would refuse, such as the spaces in a display-style handle.
+fn username_candidate(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        .collect::<String>()
+        .trim_matches(|c| matches!(c, '.' | '_' | '-'))
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    use crate::application::use_cases::sign_in::SignInService;
+    use crate::config::config_model::LoginThrottle;
+    use crate::domain::repositories::{
+        auth_events::MockAuthEventRepository,
+        brawler_identities::MockBrawlerIdentityRepository,
+        brawlers::MockBrawlerRepository,
+        login_attempts::MockLoginAttemptRepository,
+        sessions::MockSessionRepository,
+        two_factor::MockTwoFactorRepository,
+    };
+    use crate::domain::entities::brawlers::BrawlerEntity;
+    use crate::infrastructure::services::mock_oidc_provider::{MockOidcProvider, MockOidcSettings, NONCE, VALID_CODE};
+    use crate::infrastructure::services::token_service::TokenService;
+
+    type TestSignIn = SignInService<MockSessionRepository, MockTwoFactorRepository, MockLoginAttemptRepository, MockAuthEventRepository>;
+
+    fn sign_in(
+        session_repository: MockSessionRepository,
+        two_factor_repository: MockTwoFactorRepository,
+        auth_event_repository: MockAuthEventRepository,
+    ) -> Arc<TestSignIn> {
+        Arc::new(SignInService::new(
+            Arc::new(session_repository),
+            Arc::new(two_factor_repository),
+            Arc::new(MockLoginAttemptRepository::new()),
+            Arc::new(auth_event_repository),
+            LoginThrottle {
+                max_attempts_per_username: 5,
+                max_attempts_per_ip: 20,
+                lockout_seconds: 900,
+                backoff_base_seconds: 1,
+                backoff_max_seconds: 60,
+            },
+            Arc::new(EmailService::new()),
+            TokenService::fixture(),
+        ))
+    }
+
+    fn use_case(
+        brawler_repository: MockBrawlerRepository,
+        brawler_identity_repository: MockBrawlerIdentityRepository,
+        sign_in: Arc<TestSignIn>,
+        oidc_service: OidcService,
+    ) -> OidcUseCase<MockBrawlerRepository, MockBrawlerIdentityRepository, TestSignIn> {
+        OidcUseCase::new(
+            Arc::new(brawler_repository),
+            Arc::new(brawler_identity_repository),
+            sign_in,
+            Arc::new(EmailService::new()),
+            Arc::new(oidc_service),
+        )
+    }
+
+    fn user_info(email: Option<&str>, preferred_username: Option<&str>) -> OidcUserInfo {
+        OidcUserInfo {
+            subject: "subject-1".to_string(),
+            email: email.map(str::to_string),
+            email_verified: true,
+            name: None,
+            preferred_username: preferred_username.map(str::to_string),
+        }
+    }
+
+    fn taken(names: &'static [&'static str]) -> MockBrawlerRepository {
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository.expect_is_username_taken().returning(move |username, _| {
+            let taken = names.contains(&username.as_str());
+            Box::pin(async move { Ok(taken) })
+        });
+        brawler_repository
+    }
+
+    async fn pick(brawler_repository: MockBrawlerRepository, info: OidcUserInfo) -> Result<String> {
+        let sign_in = sign_in(MockSessionRepository::new(), MockTwoFactorRepository::new(), MockAuthEventRepository::new());
+        use_case(brawler_repository, MockBrawlerIdentityRepository::new(), sign_in, OidcService::new(Vec::new()))
+            .pick_username("github", &info)
+            .await
+    }
+
+    #[test]
+    fn username_candidate_drops_characters_the_policy_refuses() {
+        assert_eq!(username_candidate("Robin Hood!"), "robinhood");
+        assert_eq!(username_candidate("_robin.hood-"), "robin.hood");
+        assert_eq!(username_candidate("github:12345"), "github12345");
+    }
+
+    #[tokio::test]
+    async fn keeps_the_provider_email_as_the_username() {
+        let username = pick(taken(&[]), user_info(Some("robin@example.com"), Some("robin-h"))).await.unwrap();
+        assert_eq!(username, "robin@example.com");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_provider_handle_without_an_email() {
+        let username = pick(taken(&[]), user_info(None, Some("Robin Hood"))).await.unwrap();
+        assert_eq!(username, "robinhood");
+        assert!(!username.contains(':'));
+    }
+
+    #[tokio::test]
+    async fn uses_the_email_local_part_when_the_address_is_taken() {
+        let username = pick(taken(&["robin@example.com"]), user_info(Some("robin@example.com"), None))
+            .await
+            .unwrap();
+        assert_eq!(username, "robin");
+    }
+
+    #[tokio::test]
+    async fn appends_a_numeric_suffix_on_collision() {
+        let username = pick(taken(&["robinhood", "robinhood-2"]), user_info(None, Some("robinhood")))
+            .await
+            .unwrap();
+        assert_eq!(username, "robinhood-3");
+    }
+
+    #[tokio::test]
+    async fn skips_handles_the_policy_refuses() {
+        let username = pick(taken(&[]), user_info(None, Some("admin"))).await.unwrap();
+        assert_eq!(username, "github-user");
+    }
+
+    // Sign-in side effects of a successful login: no 2FA, one audit row and one session.
+    fn completing_sign_in() -> Arc<TestSignIn> {
+        let mut session_repository = MockSessionRepository::new();
+        session_repository
+            .expect_create()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(1) }));
+        let mut two_factor_repository = MockTwoFactorRepository::new();
+        two_factor_repository
+            .expect_find_by_brawler_id()
+            .returning(|_| Box::pin(async { Ok(None) }));
+        let mut auth_event_repository = MockAuthEventRepository::new();
+        auth_event_repository
+            .expect_record()
+            .withf(|event| event.outcome == "success")
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        sign_in(session_repository, two_factor_repository, auth_event_repository)
+    }
+
+    fn refusing_sign_in() -> Arc<TestSignIn> {
+        let mut auth_event_repository = MockAuthEventRepository::new();
+        auth_event_repository
+            .expect_record()
+            .withf(|event| event.outcome == "failure")
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        sign_in(MockSessionRepository::new(), MockTwoFactorRepository::new(), auth_event_repository)
+    }
+
+    // Someone already registered robin@example.com, verified or not.
+    fn existing_account(email_verified: bool) -> MockBrawlerRepository {
+        let user = BrawlerEntity {
+            email_verified_at: email_verified.then(|| Utc::now().naive_utc()),
+            ..BrawlerEntity::fixture(7, "robin@example.com")
+        };
+        let mut brawler_repository = MockBrawlerRepository::new();
+        let found = user.clone();
+        brawler_repository
+            .expect_find_by_username()
+            .returning(move |_| {
+                let found = found.clone();
+                Box::pin(async move { Ok(found) })
+            });
+        brawler_repository.expect_find_by_id().returning(move |_| {
+            let user = user.clone();
+            Box::pin(async move { Ok(user) })
+        });
+        brawler_repository
+            .expect_mark_email_verified()
+            .returning(|_, _| Box::pin(async { Ok(true) }));
+        brawler_repository.expect_register().never();
+        brawler_repository
+    }
+
+    fn unlinked_identity() -> MockBrawlerIdentityRepository {
+        let mut brawler_identity_repository = MockBrawlerIdentityRepository::new();
+        brawler_identity_repository
+            .expect_find_by_provider_subject()
+            .returning(|_, _| Box::pin(async { Ok(None) }));
+        brawler_identity_repository
+    }
+
+    fn flow(provider: &str, state: &str) -> Option<OAuthFlow> {
+        Some(OAuthFlow {
+            provider: provider.to_string(),
+            state: state.to_string(),
+            pkce_verifier: "verifier".to_string(),
+            nonce: Some(NONCE.to_string()),
+        })
+    }
+
+    fn callback(state: &str) -> OidcCallbackModel {
+        OidcCallbackModel {
+            code: VALID_CODE.to_string(),
+            state: state.to_string(),
+        }
+    }
+
+    fn client() -> ClientContext {
+        ClientContext {
+            ip_address: "127.0.0.1".to_string(),
+            user_agent: None,
+        }
+    }
+
+    async fn mock_provider(settings: MockOidcSettings) -> OidcService {
+        let provider = MockOidcProvider::start(settings).await;
+        OidcService::new(vec![provider.provider_env("mock")])
+    }
+
+    #[tokio::test]
+    async fn links_a_verified_email_to_the_verified_account() {
+        let mut brawler_identity_repository = unlinked_identity();
+        brawler_identity_repository
+            .expect_link()
+            .withf(|identity| identity.brawler_id == 7 && identity.provider == "mock" && identity.subject == "mock-subject")
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(1) }));
+        let use_case = use_case(
+            existing_account(true),
+            brawler_identity_repository,
+            completing_sign_in(),
+            mock_provider(MockOidcSettings::default()).await,
+        );
+
+        let outcome = use_case
+            .login_with_oidc(flow("mock", "state-1"), "mock", callback("state-1"), client())
+            .await
+            .unwrap();
+
+        match outcome {
+            LoginOutcome::Passport(passport) => assert_eq!(passport.username, "robin@example.com"),
+            LoginOutcome::TwoFactorRequired(_) => panic!("2FA is not enabled"),
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_to_link_an_unverified_provider_email() {
+        let mut brawler_identity_repository = unlinked_identity();
+        brawler_identity_repository.expect_link().never();
+        let use_case = use_case(
+            existing_account(true),
+            brawler_identity_repository,
+            refusing_sign_in(),
+            mock_provider(MockOidcSettings {
+                email_verified: false,
+                ..MockOidcSettings::default()
+            })
+            .await,
+        );
+
+        let e = use_case
+            .login_with_oidc(flow("mock", "state-1"), "mock", callback("state-1"), client())
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("already exists"));
+    }
+
+    #[tokio::test]
+    async fn refuses_to_link_to_an_unverified_account() {
+        let mut brawler_identity_repository = unlinked_identity();
+        brawler_identity_repository.expect_link().never();
+        let use_case = use_case(
+            existing_account(false),
+            brawler_identity_repository,
+            refusing_sign_in(),
+            mock_provider(MockOidcSettings::default()).await,
+        );
+
+        let e = use_case
+            .login_with_oidc(flow("mock", "state-1"), "mock", callback("state-1"), client())
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("already exists"));
+    }
+
+    #[tokio::test]
+    async fn refuses_a_state_mismatch_before_exchanging_the_code() {
+        let mut brawler_identity_repository = MockBrawlerIdentityRepository::new();
+        brawler_identity_repository.expect_find_by_provider_subject().never();
+        let use_case = use_case(
+            MockBrawlerRepository::new(),
+            brawler_identity_repository,
+            refusing_sign_in(),
+            mock_provider(MockOidcSettings::default()).await,
+        );
+
+        let e = use_case
+            .login_with_oidc(flow("mock", "state-1"), "mock", callback("state-2"), client())
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("state does not match"));
+
+        let e = use_case
+            .login_with_oidc(flow("other", "state-1"), "mock", callback("state-1"), client())
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("state does not match"));
+
+        let e = use_case
+            .login_with_oidc(None, "mock", callback("state-1"), client())
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("session expired"));
+    }
+
+    #[tokio::test]
+    async fn refuses_a_nonce_mismatch() {
+        let use_case = use_case(
+            MockBrawlerRepository::new(),
+            MockBrawlerIdentityRepository::new(),
+            refusing_sign_in(),
+            mock_provider(MockOidcSettings {
+                nonce: "replayed-nonce".to_string(),
+                ..MockOidcSettings::default()
+            })
+            .await,
+        );
+
+        let e = use_case
+            .login_with_oidc(flow("mock", "state-1"), "mock", callback("state-1"), client())
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("nonce does not match"));
+    }
+
+    #[tokio::test]
+    async fn refuses_an_unknown_issuer() {
+        let use_case = use_case(
+            MockBrawlerRepository::new(),
+            MockBrawlerIdentityRepository::new(),
+            refusing_sign_in(),
+            mock_provider(MockOidcSettings {
+                id_token_issuer: Some("https://evil.example.com".to_string()),
+                ..MockOidcSettings::default()
+            })
+            .await,
+        );
+
+        let e = use_case
+            .login_with_oidc(flow("mock", "state-1"), "mock", callback("state-1"), client())
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("ID token is invalid"));
+
+        let e = use_case
+            .login_with_oidc(flow("unknown", "state-1"), "unknown", callback("state-1"), client())
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("Unknown identity provider"));
+    }
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+
+use crate::application::use_cases::sign_in::{LoginOutcome, SignIn, IP_SCOPE};
+use crate::config::config_loader::get_webauthn_env;
+use crate::domain::repositories::{
+    brawlers::BrawlerRepository,
+    webauthn_credentials::WebAuthnCredentialRepository,
+};
+use crate::domain::value_objects::auth_event_model::{AuthEvent, AuthEventKind, ClientContext};
+use crate::domain::value_objects::webauthn_model::{
+    AuthenticationCredential, CeremonyKind, CredentialDescriptor, PasskeyLoginOptionsRequest,
+    PublicKeyCredentialRequestOptions, WebAuthnCeremony,
+};
+use crate::infrastructure::webauthn;
+
+pub struct PasskeyLoginUseCase<T1, T2, T3>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: WebAuthnCredentialRepository + Send + Sync,
+    T3: SignIn + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    webauthn_credential_repository: Arc<T2>,
+    sign_in: Arc<T3>,
+}
+
+impl<T1, T2, T3> PasskeyLoginUseCase<T1, T2, T3>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: WebAuthnCredentialRepository + Send + Sync,
+    T3: SignIn + Send + Sync,
+{
+    pub fn new(brawler_repository: Arc<T1>, webauthn_credential_repository: Arc<T2>, sign_in: Arc<T3>) -> Self {
+        Self {
+            brawler_repository,
+            webauthn_credential_repository,
+            sign_in,
+        }
+    }
+
+    // Without a username the browser offers any discoverable passkey it holds for this site.
+    pub async fn begin_passkey_login(
+        &self,
+        request: PasskeyLoginOptionsRequest,
+    ) -> Result<(PublicKeyCredentialRequestOptions, WebAuthnCeremony)> {
+        let env = get_webauthn_env()?;
+
+        let allow_credentials = match request.username.map(|username| username.trim().to_string()) {
+            Some(username) if !username.is_empty() => match self.brawler_repository.find_by_username(username).await {
+                Ok(user) => self
+                    .webauthn_credential_repository
+                    .list_for_brawler(user.id)
+                    .await?
+                    .into_iter()
+                    .map(|credential| CredentialDescriptor {
+                        kind: "public-key".to_string(),
+                        id: credential.credential_id,
+                    })
+                    .collect(),
+                // Unknown usernames get the same empty list as brawlers without passkeys
+                Err(_) => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        let challenge = webauthn::generate_challenge();
+        Ok((
+            PublicKeyCredentialRequestOptions {
+                challenge: challenge.clone(),
+                rp_id: env.rp_id,
+                timeout: env.timeout_ms,
+                user_verification: "preferred".to_string(),
+                allow_credentials,
+            },
+            WebAuthnCeremony::new(CeremonyKind::Authentication, challenge, None),
+        ))
+    }
+
+    pub async fn login_with_passkey(
+        &self,
+        ceremony: Option<WebAuthnCeremony>,
+        credential: AuthenticationCredential,
+        client: ClientContext,
+    ) -> Result<LoginOutcome> {
+        let ceremony = ceremony
+            .filter(|ceremony| ceremony.kind == CeremonyKind::Authentication)
+            .ok_or_else(|| anyhow!("No passkey sign-in in progress"))?;
+        self.sign_in.ensure_not_throttled(IP_SCOPE, &client.ip_address).await?;
+
+        // Burn the challenge before verifying so a failed attempt cannot be retried with it
+        let env = get_webauthn_env()?;
+        if ceremony.is_expired(env.timeout_ms)
+            || !self
+                .webauthn_credential_repository
+                .consume_challenge(ceremony.challenge.clone(), ceremony.expires_at(env.timeout_ms))
+                .await?
+        {
+            return Err(anyhow!("Passkey challenge has expired, please try again"));
+        }
+
+        let stored = match self
+            .webauthn_credential_repository
+            .find_by_credential_id(credential.id.trim_end_matches('=').to_string())
+            .await?
+        {
+            Some(stored) => stored,
+            None => {
+                self.sign_in
+                    .audit(&client, AuthEvent::failure(AuthEventKind::PasskeyLogin, "unknown_credential"))
+                    .await;
+                self.sign_in.record_failure(IP_SCOPE, &client.ip_address).await?;
+                return Err(anyhow!("Passkey sign-in failed"));
+            }
+        };
+
+        let user_handle_matches = credential
+            .response
+            .user_handle
+            .as_deref()
+            .is_none_or(|handle| handle.trim_end_matches('=') == webauthn::user_handle(stored.brawler_id));
+
+        let verified = webauthn::verify_assertion(&env, &ceremony.challenge, &stored.public_key, stored.sign_count, &credential)
+            .ok()
+            .filter(|_| user_handle_matches);
+        let Some(verified) = verified else {
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::PasskeyLogin, "invalid_assertion").brawler(stored.brawler_id),
+                )
+                .await;
+            self.sign_in.record_failure(IP_SCOPE, &client.ip_address).await?;
+            return Err(anyhow!("Passkey sign-in failed"));
+        };
+
+        self.webauthn_credential_repository
+            .record_use(stored.id, verified.sign_count)
+            .await?;
+
+        let user = self.brawler_repository.find_by_id(stored.brawler_id).await?;
+
+        // A user-verified passkey is already two factors (possession plus PIN or biometric)
+        if verified.user_verified {
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::success(AuthEventKind::PasskeyLogin)
+                        .brawler(user.id)
+                        .username(user.username.clone()),
+                )
+                .await;
+            return Ok(LoginOutcome::Passport(self.sign_in.issue_passport(user, &client).await?));
+        }
+
+        self.sign_in
+            .complete_login(user, AuthEvent::success(AuthEventKind::PasskeyLogin), &client)
+            .await
+    }
+}
@@ -0,0 +1,169 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+
+use crate::config::config_loader::get_webauthn_env;
+use crate::domain::entities::webauthn_credentials::NewWebAuthnCredentialEntity;
+use crate::domain::repositories::{
+    brawlers::BrawlerRepository,
+    webauthn_credentials::WebAuthnCredentialRepository,
+};
+use crate::domain::value_objects::webauthn_model::{
+    AuthenticatorSelection, CeremonyKind, CredentialDescriptor, CredentialParameter, PasskeyModel,
+    PublicKeyCredentialCreationOptions, RegisterPasskeyRequest, RelyingParty, UserEntity, WebAuthnCeremony,
+};
+use crate::infrastructure::webauthn;
+
+const MAX_PASSKEYS_PER_BRAWLER: usize = 10;
+const MAX_NAME_LENGTH: usize = 100;
+
+pub struct PasskeyUseCase<T1, T2>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: WebAuthnCredentialRepository + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    webauthn_credential_repository: Arc<T2>,
+}
+
+impl<T1, T2> PasskeyUseCase<T1, T2>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: WebAuthnCredentialRepository + Send + Sync,
+{
+    pub fn new(brawler_repository: Arc<T1>, webauthn_credential_repository: Arc<T2>) -> Self {
+        Self {
+            brawler_repository,
+            webauthn_credential_repository,
+        }
+    }
+
+    pub async fn begin_registration(&self, brawler_id: i32) -> Result<(PublicKeyCredentialCreationOptions, WebAuthnCeremony)> {
+        let env = get_webauthn_env()?;
+        let brawler = self.brawler_repository.find_by_id(brawler_id).await?;
+        let existing = self.webauthn_credential_repository.list_for_brawler(brawler_id).await?;
+
+        if existing.len() >= MAX_PASSKEYS_PER_BRAWLER {
+            return Err(anyhow!("You can register at most {} passkeys", MAX_PASSKEYS_PER_BRAWLER));
+        }
+
+        let challenge = webauthn::generate_challenge();
+        let options = PublicKeyCredentialCreationOptions {
+            challenge: challenge.clone(),
+            rp: RelyingParty {
+                id: env.rp_id,
+                name: env.rp_name,
+            },
+            user: UserEntity {
+                id: webauthn::user_handle(brawler.id),
+                name: brawler.username,
+                display_name: brawler.display_name,
+            },
+            pub_key_cred_params: vec![CredentialParameter {
+                kind: "public-key".to_string(),
+                alg: webauthn::ES256,
+            }],
+            timeout: env.timeout_ms,
+            attestation: "none".to_string(),
+            authenticator_selection: AuthenticatorSelection {
+                resident_key: "preferred".to_string(),
+                user_verification: "preferred".to_string(),
+            },
+            // Stops the same authenticator from being registered twice
+            exclude_credentials: existing
+                .into_iter()
+                .map(|credential| CredentialDescriptor {
+                    kind: "public-key".to_string(),
+                    id: credential.credential_id,
+                })
+                .collect(),
+        };
+
+        Ok((
+            options,
+            WebAuthnCeremony::new(CeremonyKind::Registration, challenge, Some(brawler_id)),
+        ))
+    }
+
+    pub async fn finish_registration(
+        &self,
+        brawler_id: i32,
+        ceremony: Option<WebAuthnCeremony>,
+        request: RegisterPasskeyRequest,
+    ) -> Result<PasskeyModel> {
+        let ceremony = ceremony
+            .filter(|ceremony| ceremony.kind == CeremonyKind::Registration && ceremony.brawler_id == Some(brawler_id))
+            .ok_or_else(|| anyhow!("No passkey registration in progress"))?;
+        let env = get_webauthn_env()?;
+        self.claim_ceremony(&ceremony, env.timeout_ms).await?;
+
+        let name = request
+            .name
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "Passkey".to_string());
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(anyhow!("Passkey name must be at most {} characters", MAX_NAME_LENGTH));
+        }
+
+        let verified = webauthn::verify_registration(&env, &ceremony.challenge, &request.credential)?;
+
+        if self
+            .webauthn_credential_repository
+            .find_by_credential_id(verified.credential_id.clone())
+            .await?
+            .is_some()
+        {
+            return Err(anyhow!("This passkey is already registered"));
+        }
+        if self.webauthn_credential_repository.list_for_brawler(brawler_id).await?.len() >= MAX_PASSKEYS_PER_BRAWLER {
+            return Err(anyhow!("You can register at most {} passkeys", MAX_PASSKEYS_PER_BRAWLER));
+        }
+
+        let created = self
+            .webauthn_credential_repository
+            .create(NewWebAuthnCredentialEntity {
+                brawler_id,
+                credential_id: verified.credential_id,
+                public_key: verified.public_key,
+                sign_count: verified.sign_count,
+                aaguid: verified.aaguid,
+                name,
+            })
+            .await?;
+
+        Ok(created.into())
+    }
+
+    // Each challenge is answerable once and only within the timeout the browser was given.
+    async fn claim_ceremony(&self, ceremony: &WebAuthnCeremony, timeout_ms: u64) -> Result<()> {
+        if ceremony.is_expired(timeout_ms)
+            || !self
+                .webauthn_credential_repository
+                .consume_challenge(ceremony.challenge.clone(), ceremony.expires_at(timeout_ms))
+                .await?
+        {
+            return Err(anyhow!("Passkey challenge has expired, please try again"));
+        }
+
+        Ok(())
+    }
+
+    pub async fn list(&self, brawler_id: i32) -> Result<Vec<PasskeyModel>> {
+        let credentials = self.webauthn_credential_repository.list_for_brawler(brawler_id).await?;
+        Ok(credentials.into_iter().map(PasskeyModel::from).collect())
+    }
+
+    pub async fn rename(&self, brawler_id: i32, passkey_id: i32, name: String) -> Result<PasskeyModel> {
+        let name = name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+            return Err(anyhow!("Passkey name must be between 1 and {} characters", MAX_NAME_LENGTH));
+        }
+
+        let renamed = self.webauthn_credential_repository.rename(brawler_id, passkey_id, name).await?;
+        Ok(renamed.into())
+    }
+
+    pub async fn remove(&self, brawler_id: i32, passkey_id: i32) -> Result<()> {
+        self.webauthn_credential_repository.remove(brawler_id, passkey_id).await
+    }
+}
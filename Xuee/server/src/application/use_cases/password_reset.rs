@@ -0,0 +1,255 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use chrono::{Utc, Duration};
+use tracing::warn;
+
+use crate::application::use_cases::sign_in::SignIn;
+use crate::domain::entities::password_reset_tokens::NewPasswordResetTokenEntity;
+use crate::domain::repositories::{
+    brawlers::BrawlerRepository,
+    password_reset_tokens::PasswordResetTokenRepository,
+};
+use crate::domain::value_objects::auth_event_model::{AuthEvent, AuthEventKind, ClientContext};
+use crate::infrastructure::argon2::hash;
+use crate::infrastructure::{opaque_token, password_policy};
+use crate::infrastructure::services::email_service::EmailService;
+
+pub struct PasswordResetUseCase<T1, T2, T3>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: PasswordResetTokenRepository + Send + Sync,
+    T3: SignIn + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    password_reset_token_repository: Arc<T2>,
+    sign_in: Arc<T3>,
+    email_service: Arc<EmailService>,
+}
+
+impl<T1, T2, T3> PasswordResetUseCase<T1, T2, T3>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: PasswordResetTokenRepository + Send + Sync,
+    T3: SignIn + Send + Sync,
+{
+    pub fn new(
+        brawler_repository: Arc<T1>,
+        password_reset_token_repository: Arc<T2>,
+        sign_in: Arc<T3>,
+        email_service: Arc<EmailService>,
+    ) -> Self {
+        Self {
+            brawler_repository,
+            password_reset_token_repository,
+            sign_in,
+            email_service,
+        }
+    }
+
+    // Succeeds the same way whether or not the account exists, like `request_magic_link`;
+    // only the mailbox owner learns the outcome.
+    pub async fn request_password_reset(&self, username: String, client: ClientContext) -> Result<()> {
+        let Ok(user) = self.brawler_repository.find_by_username(username.clone()).await else {
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::PasswordResetRequested, "unknown_user").username(username),
+                )
+                .await;
+            return Ok(());
+        };
+
+        // Only the newest link stays usable
+        self.password_reset_token_repository
+            .invalidate_all_for_brawler(user.id)
+            .await?;
+
+        // Generate a short-lived token (1 hour)
+        let token = opaque_token::generate();
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::hours(1))
+            .expect("valid timestamp")
+            .naive_utc();
+
+        self.password_reset_token_repository
+            .create(NewPasswordResetTokenEntity {
+                brawler_id: user.id,
+                token_hash: opaque_token::hash(&token),
+                expires_at,
+                email: user.username.clone(),
+            })
+            .await?;
+
+        if let Err(e) = self
+            .email_service
+            .send_password_reset_email(&user.username, &user.display_name, &token)
+            .await
+        {
+            warn!("Failed to send password reset email to brawler {}: {}", user.id, e);
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::PasswordResetRequested, "email_failed")
+                        .brawler(user.id)
+                        .username(user.username.clone()),
+                )
+                .await;
+            return Ok(());
+        }
+
+        self.sign_in
+            .audit(
+                &client,
+                AuthEvent::success(AuthEventKind::PasswordResetRequested)
+                    .brawler(user.id)
+                    .username(user.username.clone()),
+            )
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn reset_password(&self, token: String, new_password: String, client: ClientContext) -> Result<()> {
+        let reset_token = match self
+            .password_reset_token_repository
+            .find_valid_by_hash(opaque_token::hash(&token))
+            .await
+        {
+            Ok(reset_token) => reset_token,
+            Err(_) => {
+                self.sign_in
+                    .audit(&client, AuthEvent::failure(AuthEventKind::PasswordReset, "invalid_token"))
+                    .await;
+                return Err(anyhow!("Invalid or expired reset token"));
+            }
+        };
+
+        // Checked before the token is burned so a rejected password does not cost the user their link
+        let user = self.brawler_repository.find_by_id(reset_token.brawler_id).await?;
+        if !reset_token.email.eq_ignore_ascii_case(&user.username) {
+            self.sign_in
+                .audit(
+                    &client,
+                    AuthEvent::failure(AuthEventKind::PasswordReset, "address_changed").brawler(user.id),
+                )
+                .await;
+            return Err(anyhow!("Invalid or expired reset token"));
+        }
+        password_policy::check(&new_password, &user.username)?;
+
+        // Burn the token before touching the password so a replay cannot win a race
+        self.password_reset_token_repository
+            .mark_used(reset_token.id)
+            .await?;
+
+        // Hash new password
+        let hashed_password = hash(new_password)?;
+
+        // Update password in DB; anyone holding the old password may also hold a live session,
+        // so every session is revoked along with it
+        self.brawler_repository
+            .update_password(reset_token.brawler_id, hashed_password, None)
+            .await?;
+        self.password_reset_token_repository
+            .invalidate_all_for_brawler(reset_token.brawler_id)
+            .await?;
+
+        self.sign_in
+            .audit(&client, AuthEvent::success(AuthEventKind::PasswordReset).brawler(reset_token.brawler_id))
+            .await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::eq;
+
+    use crate::application::use_cases::sign_in::SignInService;
+    use crate::config::config_model::LoginThrottle;
+    use crate::domain::entities::brawlers::BrawlerEntity;
+    use crate::domain::entities::password_reset_tokens::PasswordResetTokenEntity;
+    use crate::domain::repositories::{
+        auth_events::MockAuthEventRepository,
+        brawlers::MockBrawlerRepository,
+        login_attempts::MockLoginAttemptRepository,
+        password_reset_tokens::MockPasswordResetTokenRepository,
+        sessions::MockSessionRepository,
+        two_factor::MockTwoFactorRepository,
+    };
+    use crate::infrastructure::services::token_service::TokenService;
+
+    type TestSignIn = SignInService<MockSessionRepository, MockTwoFactorRepository, MockLoginAttemptRepository, MockAuthEventRepository>;
+
+    fn sign_in(auth_event_repository: MockAuthEventRepository) -> Arc<TestSignIn> {
+        Arc::new(SignInService::new(
+            Arc::new(MockSessionRepository::new()),
+            Arc::new(MockTwoFactorRepository::new()),
+            Arc::new(MockLoginAttemptRepository::new()),
+            Arc::new(auth_event_repository),
+            LoginThrottle {
+                max_attempts_per_username: 5,
+                max_attempts_per_ip: 20,
+                lockout_seconds: 900,
+                backoff_base_seconds: 1,
+                backoff_max_seconds: 60,
+            },
+            Arc::new(EmailService::new()),
+            TokenService::fixture(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn refuses_a_link_mailed_to_a_previous_username() {
+        let mut password_reset_token_repository = MockPasswordResetTokenRepository::new();
+        password_reset_token_repository
+            .expect_find_valid_by_hash()
+            .with(eq(opaque_token::hash("reset-token")))
+            .returning(|token_hash| {
+                let now = Utc::now().naive_utc();
+                let reset_token = PasswordResetTokenEntity {
+                    id: 1,
+                    brawler_id: 7,
+                    token_hash,
+                    expires_at: now + Duration::hours(1),
+                    used_at: None,
+                    created_at: now,
+                    email: "old@example.com".to_string(),
+                };
+                Box::pin(async move { Ok(reset_token) })
+            });
+        password_reset_token_repository.expect_mark_used().never();
+
+        let mut brawler_repository = MockBrawlerRepository::new();
+        brawler_repository
+            .expect_find_by_id()
+            .returning(|id| Box::pin(async move { Ok(BrawlerEntity::fixture(id, "new@example.com")) }));
+        brawler_repository.expect_update_password().never();
+
+        let mut auth_event_repository = MockAuthEventRepository::new();
+        auth_event_repository
+            .expect_record()
+            .withf(|event| event.reason.as_deref() == Some("address_changed"))
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let use_case = PasswordResetUseCase::new(
+            Arc::new(brawler_repository),
+            Arc::new(password_reset_token_repository),
+            sign_in(auth_event_repository),
+            Arc::new(EmailService::new()),
+        );
+        let client = ClientContext {
+            ip_address: "203.0.113.7".to_string(),
+            user_agent: None,
+        };
+
+        let error = use_case
+            .reset_password("reset-token".to_string(), "a much longer passphrase 42".to_string(), client)
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Invalid or expired reset token");
+    }
+}
@@ -0,0 +1,580 @@
+use std::fmt;
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{Utc, Duration};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::domain::repositories::{
+    auth_events::AuthEventRepository,
+    login_attempts::LoginAttemptRepository,
+    sessions::SessionRepository,
+    two_factor::TwoFactorRepository,
+};
+use crate::domain::entities::auth_events::NewAuthEventEntity;
+use crate::domain::entities::brawlers::BrawlerEntity;
+use crate::domain::entities::login_attempts::LoginAttemptEntity;
+use crate::domain::entities::sessions::NewSessionEntity;
+use crate::domain::value_objects::auth_event_model::{AuthEvent, AuthEventKind, ClientContext};
+use crate::domain::value_objects::roles::Role;
+use crate::domain::value_objects::two_factor_model::TwoFactorChallenge;
+use crate::config::config_loader::get_two_factor_env;
+use crate::config::config_model::LoginThrottle;
+use crate::infrastructure::opaque_token;
+use crate::infrastructure::services::email_service::EmailService;
+use crate::infrastructure::services::token_service::TokenService;
+
+#[derive(Debug, Serialize)]
+pub struct Passport {
+    // Left empty, and omitted, when the tokens travel in HttpOnly cookies instead
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub access_token: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub refresh_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub display_name: String,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub role: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+// Brawlers with 2FA enabled get a challenge first and trade it for a Passport at /login/2fa.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum LoginOutcome {
+    Passport(Passport),
+    TwoFactorRequired(TwoFactorChallenge),
+}
+
+pub const TWO_FACTOR_PURPOSE: &str = "2fa_pending";
+
+pub const USERNAME_SCOPE: &str = "username";
+pub const IP_SCOPE: &str = "ip";
+pub const MAGIC_LINK_SCOPE: &str = "magic_link";
+
+// Returned when a username or client IP is backing off or locked out; the router maps it to 429.
+#[derive(Debug)]
+pub struct TooManyAttempts {
+    pub retry_after: i64,
+}
+
+impl fmt::Display for TooManyAttempts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Too many failed login attempts, try again in {} seconds", self.retry_after)
+    }
+}
+
+impl std::error::Error for TooManyAttempts {}
+
+// The steps every way of signing in shares: throttling, the audit trail, the 2FA hand-off and
+// issuing the session. Each login flow gets its own use case on top of this.
+#[async_trait]
+pub trait SignIn {
+    async fn audit(&self, client: &ClientContext, event: AuthEvent);
+    async fn ensure_login_allowed(&self, kind: AuthEventKind, username_key: &str, client: &ClientContext) -> Result<()>;
+    async fn ensure_not_throttled(&self, scope: &str, identifier: &str) -> Result<()>;
+    async fn record_failed_login(&self, username_key: &str, client: &ClientContext, user: Option<&BrawlerEntity>) -> Result<()>;
+    async fn record_failure(&self, scope: &str, identifier: &str) -> Result<LoginAttemptEntity>;
+    async fn lock(&self, scope: &str, identifier: &str) -> Result<()>;
+    async fn clear_failures(&self, scope: &str, identifier: &str) -> Result<()>;
+    async fn complete_login(&self, user: BrawlerEntity, event: AuthEvent, client: &ClientContext) -> Result<LoginOutcome>;
+    async fn issue_passport(&self, user: BrawlerEntity, client: &ClientContext) -> Result<Passport>;
+    fn to_passport(&self, access_token: String, refresh_token: String, user: BrawlerEntity) -> Passport;
+}
+
+pub struct SignInService<T1, T2, T3, T4>
+where
+    T1: SessionRepository + Send + Sync,
+    T2: TwoFactorRepository + Send + Sync,
+    T3: LoginAttemptRepository + Send + Sync,
+    T4: AuthEventRepository + Send + Sync,
+{
+    session_repository: Arc<T1>,
+    two_factor_repository: Arc<T2>,
+    login_attempt_repository: Arc<T3>,
+    auth_event_repository: Arc<T4>,
+    login_throttle: LoginThrottle,
+    email_service: Arc<EmailService>,
+    token_service: Arc<TokenService>,
+}
+
+impl<T1, T2, T3, T4> SignInService<T1, T2, T3, T4>
+where
+    T1: SessionRepository + Send + Sync,
+    T2: TwoFactorRepository + Send + Sync,
+    T3: LoginAttemptRepository + Send + Sync,
+    T4: AuthEventRepository + Send + Sync,
+{
+    pub fn new(
+        session_repository: Arc<T1>,
+        two_factor_repository: Arc<T2>,
+        login_attempt_repository: Arc<T3>,
+        auth_event_repository: Arc<T4>,
+        login_throttle: LoginThrottle,
+        email_service: Arc<EmailService>,
+        token_service: Arc<TokenService>,
+    ) -> Self {
+        Self {
+            session_repository,
+            two_factor_repository,
+            login_attempt_repository,
+            auth_event_repository,
+            login_throttle,
+            email_service,
+            token_service,
+        }
+    }
+
+    // Exponential backoff: base, 2 * base, 4 * base, ... capped at the configured maximum.
+    fn backoff_seconds(&self, failed_count: i32) -> i64 {
+        let exponent = (failed_count - 1).clamp(0, 30) as u32;
+        self.login_throttle
+            .backoff_base_seconds
+            .saturating_mul(1i64 << exponent)
+            .min(self.login_throttle.backoff_max_seconds)
+    }
+}
+
+#[async_trait]
+impl<T1, T2, T3, T4> SignIn for SignInService<T1, T2, T3, T4>
+where
+    T1: SessionRepository + Send + Sync,
+    T2: TwoFactorRepository + Send + Sync,
+    T3: LoginAttemptRepository + Send + Sync,
+    T4: AuthEventRepository + Send + Sync,
+{
+    // Best effort: a failed insert is logged but never fails the request being audited.
+    async fn audit(&self, client: &ClientContext, event: AuthEvent) {
+        let new_event = NewAuthEventEntity {
+            brawler_id: event.brawler_id,
+            username: event.username.map(|username| username.chars().take(255).collect()),
+            event_type: event.kind.to_string(),
+            outcome: event.outcome.to_string(),
+            reason: event.reason,
+            provider: event.provider,
+            ip_address: Some(client.ip_address.clone()).filter(|ip| !ip.is_empty()),
+            user_agent: client.user_agent.clone(),
+        };
+
+        if let Err(e) = self.auth_event_repository.record(new_event).await {
+            warn!("Failed to record {} auth event: {}", event.kind, e);
+        }
+    }
+
+    async fn ensure_login_allowed(&self, kind: AuthEventKind, username_key: &str, client: &ClientContext) -> Result<()> {
+        let throttled = match self.ensure_not_throttled(IP_SCOPE, &client.ip_address).await {
+            Ok(()) => self.ensure_not_throttled(USERNAME_SCOPE, username_key).await,
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = throttled {
+            self.audit(client, AuthEvent::failure(kind, "throttled").username(username_key))
+                .await;
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    async fn ensure_not_throttled(&self, scope: &str, identifier: &str) -> Result<()> {
+        let Some(attempt) = self
+            .login_attempt_repository
+            .find(scope.to_string(), identifier.to_string())
+            .await?
+        else {
+            return Ok(());
+        };
+
+        let now = Utc::now().naive_utc();
+        let mut ready_at = attempt.locked_until.unwrap_or(now);
+        if let Some(last_failed_at) = attempt.last_failed_at {
+            if attempt.failed_count > 0 {
+                ready_at = ready_at.max(last_failed_at + Duration::seconds(self.backoff_seconds(attempt.failed_count)));
+            }
+        }
+
+        if ready_at > now {
+            return Err(TooManyAttempts {
+                retry_after: (ready_at - now).num_seconds().max(1),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    async fn record_failed_login(&self, username_key: &str, client: &ClientContext, user: Option<&BrawlerEntity>) -> Result<()> {
+        let client_ip = client.ip_address.as_str();
+        let by_username = self.record_failure(USERNAME_SCOPE, username_key).await?;
+        if by_username.failed_count >= self.login_throttle.max_attempts_per_username {
+            self.lock(USERNAME_SCOPE, username_key).await?;
+            warn!("Locked username {} after {} failed logins", username_key, by_username.failed_count);
+
+            let mut event = AuthEvent::failure(AuthEventKind::AccountLocked, "too_many_failed_logins").username(username_key);
+            if let Some(user) = user {
+                event = event.brawler(user.id);
+            }
+            self.audit(client, event).await;
+
+            if let Some(user) = user {
+                let _ = self
+                    .email_service
+                    .send_account_locked_email(&user.username, &user.display_name, self.login_throttle.lockout_seconds / 60)
+                    .await;
+            }
+        }
+
+        let by_ip = self.record_failure(IP_SCOPE, client_ip).await?;
+        if by_ip.failed_count >= self.login_throttle.max_attempts_per_ip {
+            self.lock(IP_SCOPE, client_ip).await?;
+            warn!("Locked client IP {} after {} failed logins", client_ip, by_ip.failed_count);
+            self.audit(client, AuthEvent::failure(AuthEventKind::AccountLocked, "too_many_failed_logins_from_ip"))
+                .await;
+        }
+
+        Ok(())
+    }
+
+    async fn record_failure(&self, scope: &str, identifier: &str) -> Result<LoginAttemptEntity> {
+        // Failures older than one lockout window no longer count towards the threshold
+        let stale_before = Utc::now().naive_utc() - Duration::seconds(self.login_throttle.lockout_seconds);
+        let existing = self
+            .login_attempt_repository
+            .find(scope.to_string(), identifier.to_string())
+            .await?;
+        if existing.is_some_and(|attempt| attempt.last_failed_at.is_some_and(|at| at < stale_before)) {
+            self.login_attempt_repository
+                .clear(scope.to_string(), identifier.to_string())
+                .await?;
+        }
+
+        self.login_attempt_repository
+            .record_failure(scope.to_string(), identifier.to_string())
+            .await
+    }
+
+    async fn lock(&self, scope: &str, identifier: &str) -> Result<()> {
+        let locked_until = Utc::now()
+            .checked_add_signed(Duration::seconds(self.login_throttle.lockout_seconds))
+            .expect("valid timestamp")
+            .naive_utc();
+
+        self.login_attempt_repository
+            .lock(scope.to_string(), identifier.to_string(), locked_until)
+            .await
+    }
+
+    async fn clear_failures(&self, scope: &str, identifier: &str) -> Result<()> {
+        self.login_attempt_repository
+            .clear(scope.to_string(), identifier.to_string())
+            .await
+    }
+
+    async fn complete_login(&self, user: BrawlerEntity, event: AuthEvent, client: &ClientContext) -> Result<LoginOutcome> {
+        let two_factor_enabled = self
+            .two_factor_repository
+            .find_by_brawler_id(user.id)
+            .await?
+            .is_some_and(|two_factor| two_factor.enabled_at.is_some());
+
+        let event = event.brawler(user.id).username(user.username.clone());
+        if !two_factor_enabled {
+            self.audit(client, event).await;
+            return Ok(LoginOutcome::Passport(self.issue_passport(user, client).await?));
+        }
+        self.audit(client, event.reason("two_factor_required")).await;
+
+        let challenge_ttl = get_two_factor_env()?.challenge_ttl;
+        let challenge_token = self
+            .token_service
+            .issue_for_purpose(user.id, TWO_FACTOR_PURPOSE, challenge_ttl)?;
+
+        Ok(LoginOutcome::TwoFactorRequired(TwoFactorChallenge {
+            two_factor_required: true,
+            challenge_token,
+            expires_in: challenge_ttl,
+        }))
+    }
+
+    async fn issue_passport(&self, user: BrawlerEntity, client: &ClientContext) -> Result<Passport> {
+        let refresh_token = opaque_token::generate();
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::seconds(self.token_service.refresh_ttl()))
+            .expect("valid timestamp")
+            .naive_utc();
+
+        let session_id = self
+            .session_repository
+            .create(NewSessionEntity {
+                brawler_id: user.id,
+                refresh_token_hash: opaque_token::hash(&refresh_token),
+                expires_at,
+                user_agent: client.user_agent.clone(),
+                ip_address: Some(client.ip_address.clone()).filter(|ip| !ip.is_empty()),
+            })
+            .await?;
+
+        let access_token = self
+            .token_service
+            .issue_access_token(user.id, session_id, Role::try_from(user.role.as_str()).unwrap_or_default())?;
+
+        Ok(self.to_passport(access_token, refresh_token, user))
+    }
+
+    fn to_passport(&self, access_token: String, refresh_token: String, user: BrawlerEntity) -> Passport {
+        Passport {
+            access_token,
+            refresh_token,
+            token_type: "Bearer".to_string(),
+            expires_in: self.token_service.ttl(),
+            display_name: user.display_name,
+            username: user.username,
+            avatar_url: user.avatar_url,
+            role: user.role,
+            created_at: user.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::eq;
+
+    use crate::domain::repositories::{
+        auth_events::MockAuthEventRepository,
+        login_attempts::MockLoginAttemptRepository,
+        sessions::MockSessionRepository,
+        two_factor::MockTwoFactorRepository,
+    };
+
+    type TestSignIn = SignInService<MockSessionRepository, MockTwoFactorRepository, MockLoginAttemptRepository, MockAuthEventRepository>;
+
+    const THROTTLE: LoginThrottle = LoginThrottle {
+        max_attempts_per_username: 5,
+        max_attempts_per_ip: 20,
+        lockout_seconds: 900,
+        backoff_base_seconds: 1,
+        backoff_max_seconds: 60,
+    };
+
+    fn sign_in(login_attempt_repository: MockLoginAttemptRepository, auth_event_repository: MockAuthEventRepository) -> TestSignIn {
+        SignInService::new(
+            Arc::new(MockSessionRepository::new()),
+            Arc::new(MockTwoFactorRepository::new()),
+            Arc::new(login_attempt_repository),
+            Arc::new(auth_event_repository),
+            THROTTLE,
+            Arc::new(EmailService::new()),
+            TokenService::fixture(),
+        )
+    }
+
+    fn client() -> ClientContext {
+        ClientContext {
+            ip_address: "203.0.113.7".to_string(),
+            user_agent: None,
+        }
+    }
+
+    fn attempt(scope: &str, failed_count: i32, last_failed_secs_ago: Option<i64>, locked_for_secs: Option<i64>) -> LoginAttemptEntity {
+        let now = Utc::now().naive_utc();
+        LoginAttemptEntity {
+            id: 1,
+            scope: scope.to_string(),
+            identifier: "robin".to_string(),
+            failed_count,
+            last_failed_at: last_failed_secs_ago.map(|secs| now - Duration::seconds(secs)),
+            locked_until: locked_for_secs.map(|secs| now + Duration::seconds(secs)),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn finds(existing: Option<LoginAttemptEntity>) -> MockLoginAttemptRepository {
+        let mut login_attempt_repository = MockLoginAttemptRepository::new();
+        login_attempt_repository.expect_find().returning(move |_, _| {
+            let existing = existing.clone();
+            Box::pin(async move { Ok(existing) })
+        });
+        login_attempt_repository
+    }
+
+    fn retry_after(result: Result<()>) -> i64 {
+        result
+            .unwrap_err()
+            .downcast::<TooManyAttempts>()
+            .expect("throttled")
+            .retry_after
+    }
+
+    // Failed logins for the username and the IP each come back with the given running count
+    fn failures(by_username: i32, by_ip: i32) -> MockLoginAttemptRepository {
+        let mut login_attempt_repository = finds(None);
+        login_attempt_repository.expect_record_failure().returning(move |scope, _| {
+            let count = if scope == USERNAME_SCOPE { by_username } else { by_ip };
+            let recorded = attempt(&scope, count, Some(0), None);
+            Box::pin(async move { Ok(recorded) })
+        });
+        login_attempt_repository
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_configured_maximum() {
+        let sign_in = sign_in(MockLoginAttemptRepository::new(), MockAuthEventRepository::new());
+
+        let delays: Vec<i64> = (1..=8).map(|failed_count| sign_in.backoff_seconds(failed_count)).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(sign_in.backoff_seconds(i32::MAX), 60);
+    }
+
+    #[tokio::test]
+    async fn lets_unknown_identifiers_through() {
+        let sign_in = sign_in(finds(None), MockAuthEventRepository::new());
+
+        sign_in.ensure_not_throttled(USERNAME_SCOPE, "robin").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuses_a_locked_identifier_until_the_lock_expires() {
+        let sign_in = sign_in(finds(Some(attempt(USERNAME_SCOPE, 5, Some(600), Some(300)))), MockAuthEventRepository::new());
+
+        let retry_after = retry_after(sign_in.ensure_not_throttled(USERNAME_SCOPE, "robin").await);
+        assert!((295..=300).contains(&retry_after), "{retry_after}");
+    }
+
+    #[tokio::test]
+    async fn backs_off_after_a_recent_failure() {
+        let sign_in = sign_in(finds(Some(attempt(IP_SCOPE, 3, Some(0), None))), MockAuthEventRepository::new());
+
+        let retry_after = retry_after(sign_in.ensure_not_throttled(IP_SCOPE, "203.0.113.7").await);
+        assert!((1..=4).contains(&retry_after), "{retry_after}");
+    }
+
+    #[tokio::test]
+    async fn allows_another_try_once_the_backoff_has_passed() {
+        let sign_in = sign_in(finds(Some(attempt(IP_SCOPE, 3, Some(5), Some(-60)))), MockAuthEventRepository::new());
+
+        sign_in.ensure_not_throttled(IP_SCOPE, "203.0.113.7").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn audits_throttled_logins() {
+        let mut auth_event_repository = MockAuthEventRepository::new();
+        auth_event_repository
+            .expect_record()
+            .withf(|event| event.reason.as_deref() == Some("throttled") && event.username.as_deref() == Some("robin"))
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+        let sign_in = sign_in(finds(Some(attempt(USERNAME_SCOPE, 5, None, Some(300)))), auth_event_repository);
+
+        let result = sign_in.ensure_login_allowed(AuthEventKind::Login, "robin", &client()).await;
+        assert!(retry_after(result) > 0);
+    }
+
+    #[tokio::test]
+    async fn locks_the_username_at_the_threshold() {
+        let mut login_attempt_repository = failures(THROTTLE.max_attempts_per_username, 1);
+        login_attempt_repository
+            .expect_lock()
+            .with(eq(USERNAME_SCOPE.to_string()), eq("robin".to_string()), mockall::predicate::always())
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let mut auth_event_repository = MockAuthEventRepository::new();
+        auth_event_repository
+            .expect_record()
+            .withf(|event| {
+                event.event_type == AuthEventKind::AccountLocked.to_string()
+                    && event.reason.as_deref() == Some("too_many_failed_logins")
+                    && event.brawler_id == Some(1)
+            })
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let user = BrawlerEntity::fixture(1, "robin");
+        sign_in(login_attempt_repository, auth_event_repository)
+            .record_failed_login("robin", &client(), Some(&user))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn does_not_lock_below_the_threshold() {
+        let mut login_attempt_repository = failures(THROTTLE.max_attempts_per_username - 1, THROTTLE.max_attempts_per_ip - 1);
+        login_attempt_repository.expect_lock().never();
+        let mut auth_event_repository = MockAuthEventRepository::new();
+        auth_event_repository.expect_record().never();
+
+        sign_in(login_attempt_repository, auth_event_repository)
+            .record_failed_login("robin", &client(), None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn locks_a_client_ip_spraying_many_usernames() {
+        let mut login_attempt_repository = failures(1, THROTTLE.max_attempts_per_ip);
+        login_attempt_repository
+            .expect_lock()
+            .with(eq(IP_SCOPE.to_string()), eq("203.0.113.7".to_string()), mockall::predicate::always())
+            .times(1)
+            .returning(|_, _, _| Box::pin(async { Ok(()) }));
+
+        let mut auth_event_repository = MockAuthEventRepository::new();
+        auth_event_repository
+            .expect_record()
+            .withf(|event| event.reason.as_deref() == Some("too_many_failed_logins_from_ip"))
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        sign_in(login_attempt_repository, auth_event_repository)
+            .record_failed_login("someone-else", &client(), None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn forgets_failures_older_than_the_lockout_window() {
+        let mut login_attempt_repository = finds(Some(attempt(USERNAME_SCOPE, 4, Some(THROTTLE.lockout_seconds + 60), None)));
+        login_attempt_repository
+            .expect_clear()
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+        login_attempt_repository
+            .expect_record_failure()
+            .times(1)
+            .returning(|scope, _| {
+                let recorded = attempt(&scope, 1, Some(0), None);
+                Box::pin(async move { Ok(recorded) })
+            });
+
+        let recorded = sign_in(login_attempt_repository, MockAuthEventRepository::new())
+            .record_failure(USERNAME_SCOPE, "robin")
+            .await
+            .unwrap();
+        assert_eq!(recorded.failed_count, 1);
+    }
+
+    #[tokio::test]
+    async fn keeps_counting_recent_failures() {
+        let mut login_attempt_repository = finds(Some(attempt(USERNAME_SCOPE, 4, Some(60), None)));
+        login_attempt_repository.expect_clear().never();
+        login_attempt_repository
+            .expect_record_failure()
+            .times(1)
+            .returning(|scope, _| {
+                let recorded = attempt(&scope, 5, Some(0), None);
+                Box::pin(async move { Ok(recorded) })
+            });
+
+        let recorded = sign_in(login_attempt_repository, MockAuthEventRepository::new())
+            .record_failure(USERNAME_SCOPE, "robin")
+            .await
+            .unwrap();
+        assert_eq!(recorded.failed_count, 5);
+    }
+}
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+
+use crate::domain::entities::social::{NewFollowEntity, NewFriendRequestEntity};
+use crate::domain::repositories::{brawlers::BrawlerRepository, social::SocialRepository};
+use crate::domain::value_objects::social_model::{
+    FriendRequestDirection, FriendRequestModel, FriendRequestStatus, SendFriendRequestResponse, SocialBrawlerModel,
+};
+
+pub struct SocialUseCase<T1, T2>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: SocialRepository + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    social_repository: Arc<T2>,
+}
+
+impl<T1, T2> SocialUseCase<T1, T2>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: SocialRepository + Send + Sync,
+{
+    pub fn new(brawler_repository: Arc<T1>, social_repository: Arc<T2>) -> Self {
+        Self { brawler_repository, social_repository }
+    }
+
+    pub async fn follow(&self, user_id: i32, brawler_id: i32) -> Result<()> {
+        self.ensure_other_brawler(user_id, brawler_id).await?;
+        self.social_repository
+            .follow(NewFollowEntity { follower_id: user_id, followee_id: brawler_id })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn unfollow(&self, user_id: i32, brawler_id: i32) -> Result<()> {
+        if !self.social_repository.unfollow(user_id, brawler_id).await? {
+            return Err(anyhow!("You are not following this brawler"));
+        }
+
+        Ok(())
+    }
+
+    pub async fn followers(&self, brawler_id: i32) -> Result<Vec<SocialBrawlerModel>> {
+        self.ensure_brawler_exists(brawler_id).await?;
+        self.social_repository.list_followers(brawler_id).await
+    }
+
+    pub async fn following(&self, brawler_id: i32) -> Result<Vec<SocialBrawlerModel>> {
+        self.ensure_brawler_exists(brawler_id).await?;
+        self.social_repository.list_following(brawler_id).await
+    }
+
+    pub async fn friends(&self, user_id: i32) -> Result<Vec<SocialBrawlerModel>> {
+        self.social_repository.list_friends(user_id).await
+    }
+
+    // Drops both follow edges; either side can end the friendship.
+    pub async fn unfriend(&self, user_id: i32, brawler_id: i32) -> Result<()> {
+        if !self.social_repository.are_friends(user_id, brawler_id).await? {
+            return Err(anyhow!("You are not friends with this brawler"));
+        }
+        self.social_repository.remove_friend(user_id, brawler_id).await?;
+
+        Ok(())
+    }
+
+    // Asking someone who already asked you simply accepts their request.
+    pub async fn send_friend_request(&self, user_id: i32, brawler_id: i32) -> Result<SendFriendRequestResponse> {
+        self.ensure_other_brawler(user_id, brawler_id).await?;
+
+        if self.social_repository.are_friends(user_id, brawler_id).await? {
+            return Err(anyhow!("You are already friends with this brawler"));
+        }
+
+        if let Some(incoming) = self.social_repository.find_pending_friend_request(brawler_id, user_id).await? {
+            self.social_repository
+                .respond_to_friend_request(incoming.id, FriendRequestStatus::Accepted)
+                .await?;
+            return Ok(SendFriendRequestResponse { id: incoming.id, status: FriendRequestStatus::Accepted });
+        }
+
+        if let Some(outgoing) = self.social_repository.find_pending_friend_request(user_id, brawler_id).await? {
+            return Ok(SendFriendRequestResponse { id: outgoing.id, status: FriendRequestStatus::Pending });
+        }
+
+        let id = self
+            .social_repository
+            .create_friend_request(NewFriendRequestEntity { requester_id: user_id, addressee_id: brawler_id })
+            .await?;
+
+        Ok(SendFriendRequestResponse { id, status: FriendRequestStatus::Pending })
+    }
+
+    pub async fn accept_friend_request(&self, user_id: i32, request_id: i32) -> Result<()> {
+        self.answer_friend_request(user_id, request_id, FriendRequestStatus::Accepted).await
+    }
+
+    pub async fn decline_friend_request(&self, user_id: i32, request_id: i32) -> Result<()> {
+        self.answer_friend_request(user_id, request_id, FriendRequestStatus::Declined).await
+    }
+
+    // Only the sender can withdraw a request, and only while it is still pending.
+    pub async fn cancel_friend_request(&self, user_id: i32, request_id: i32) -> Result<()> {
+        let request = self
+            .social_repository
+            .find_friend_request(request_id)
+            .await
+            .map_err(|_| anyhow!("Friend request not found"))?;
+        if request.requester_id != user_id || request.status != FriendRequestStatus::Pending.to_string() {
+            return Err(anyhow!("Friend request not found"));
+        }
+
+        self.social_repository.delete_friend_request(request_id).await
+    }
+
+    pub async fn friend_requests(&self, user_id: i32, direction: FriendRequestDirection) -> Result<Vec<FriendRequestModel>> {
+        self.social_repository.list_friend_requests(user_id, direction).await
+    }
+
+    async fn answer_friend_request(&self, user_id: i32, request_id: i32, status: FriendRequestStatus) -> Result<()> {
+        let request = self
+            .social_repository
+            .find_friend_request(request_id)
+            .await
+            .map_err(|_| anyhow!("Friend request not found"))?;
+        // Someone else's request looks exactly like a missing one
+        if request.addressee_id != user_id {
+            return Err(anyhow!("Friend request not found"));
+        }
+
+        self.social_repository.respond_to_friend_request(request_id, status).await
+    }
+
+    async fn ensure_other_brawler(&self, user_id: i32, brawler_id: i32) -> Result<()> {
+        if user_id == brawler_id {
+            return Err(anyhow!("You cannot do that with yourself"));
+        }
+        self.ensure_brawler_exists(brawler_id).await
+    }
+
+    // get_profile hides deleted brawlers, so they cannot gain followers or friends
+    async fn ensure_brawler_exists(&self, brawler_id: i32) -> Result<()> {
+        self.brawler_repository
+            .get_profile(brawler_id)
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("Brawler not found"))
+    }
+}
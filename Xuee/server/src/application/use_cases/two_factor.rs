@@ -0,0 +1,285 @@
+use std::sync::Arc;
+use anyhow::{Result, anyhow};
+use rand::{distr::Alphanumeric, Rng};
+
+use crate::config::config_loader::get_two_factor_env;
+use crate::domain::entities::two_factor::{NewRecoveryCodeEntity, NewTwoFactorEntity, TwoFactorEntity};
+use crate::domain::repositories::{
+    brawlers::BrawlerRepository,
+    two_factor::TwoFactorRepository,
+};
+use crate::domain::value_objects::two_factor_model::{RecoveryCodesResponse, TwoFactorEnrollment};
+use crate::infrastructure::{opaque_token, secret_box, totp};
+
+const RECOVERY_CODE_COUNT: usize = 10;
+
+pub struct TwoFactorUseCase<T1, T2>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: TwoFactorRepository + Send + Sync,
+{
+    brawler_repository: Arc<T1>,
+    two_factor_repository: Arc<T2>,
+}
+
+impl<T1, T2> TwoFactorUseCase<T1, T2>
+where
+    T1: BrawlerRepository + Send + Sync,
+    T2: TwoFactorRepository + Send + Sync,
+{
+    pub fn new(brawler_repository: Arc<T1>, two_factor_repository: Arc<T2>) -> Self {
+        Self { brawler_repository, two_factor_repository }
+    }
+
+    pub async fn enroll(&self, brawler_id: i32) -> Result<TwoFactorEnrollment> {
+        let two_factor_env = get_two_factor_env()?;
+        let user = self.brawler_repository.find_by_id(brawler_id).await?;
+
+        let secret = totp::generate_secret();
+        self.two_factor_repository
+            .upsert_pending(NewTwoFactorEntity {
+                brawler_id,
+                secret_ciphertext: secret_box::encrypt(&two_factor_env.encryption_key, &secret)?,
+            })
+            .await?;
+
+        Ok(TwoFactorEnrollment {
+            otpauth_url: totp::provisioning_uri(&two_factor_env.issuer, &user.username, &secret),
+            secret,
+        })
+    }
+
+    pub async fn confirm(&self, brawler_id: i32, code: String) -> Result<RecoveryCodesResponse> {
+        let two_factor = self
+            .two_factor_repository
+            .find_by_brawler_id(brawler_id)
+            .await?
+            .ok_or_else(|| anyhow!("Two-factor enrollment has not been started"))?;
+        if two_factor.enabled_at.is_some() {
+            return Err(anyhow!("Two-factor authentication is already enabled"));
+        }
+
+        // Recovery codes do not exist yet, so only the authenticator can prove the enrollment
+        verify_totp(self.two_factor_repository.as_ref(), &two_factor, &code).await?;
+        self.two_factor_repository.enable(brawler_id).await?;
+
+        self.issue_recovery_codes(brawler_id).await
+    }
+
+    pub async fn disable(&self, brawler_id: i32, code: String) -> Result<()> {
+        let two_factor = self.find_enabled(brawler_id).await?;
+        verify_second_factor(self.two_factor_repository.as_ref(), &two_factor, &code).await?;
+
+        self.two_factor_repository.remove(brawler_id).await
+    }
+
+    pub async fn regenerate_recovery_codes(&self, brawler_id: i32, code: String) -> Result<RecoveryCodesResponse> {
+        let two_factor = self.find_enabled(brawler_id).await?;
+        verify_totp(self.two_factor_repository.as_ref(), &two_factor, &code).await?;
+
+        self.issue_recovery_codes(brawler_id).await
+    }
+
+    async fn find_enabled(&self, brawler_id: i32) -> Result<TwoFactorEntity> {
+        self.two_factor_repository
+            .find_by_brawler_id(brawler_id)
+            .await?
+            .filter(|two_factor| two_factor.enabled_at.is_some())
+            .ok_or_else(|| anyhow!("Two-factor authentication is not enabled"))
+    }
+
+    async fn issue_recovery_codes(&self, brawler_id: i32) -> Result<RecoveryCodesResponse> {
+        let recovery_codes: Vec<String> = (0..RECOVERY_CODE_COUNT).map(|_| generate_recovery_code()).collect();
+
+        // Only digests are stored; the plain codes are shown to the user exactly once
+        self.two_factor_repository
+            .replace_recovery_codes(
+                brawler_id,
+                recovery_codes
+                    .iter()
+                    .map(|code| NewRecoveryCodeEntity {
+                        brawler_id,
+                        code_hash: opaque_token::hash(&normalize_recovery_code(code)),
+                    })
+                    .collect(),
+            )
+            .await?;
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+}
+
+// Accepts either a current TOTP code or an unused recovery code.
+pub async fn verify_second_factor<T>(two_factor_repository: &T, two_factor: &TwoFactorEntity, code: &str) -> Result<()>
+where
+    T: TwoFactorRepository + Send + Sync + ?Sized,
+{
+    if verify_totp(two_factor_repository, two_factor, code).await.is_ok() {
+        return Ok(());
+    }
+
+    let consumed = two_factor_repository
+        .consume_recovery_code(two_factor.brawler_id, opaque_token::hash(&normalize_recovery_code(code)))
+        .await?;
+    if !consumed {
+        return Err(anyhow!("Invalid two-factor code"));
+    }
+
+    Ok(())
+}
+
+async fn verify_totp<T>(two_factor_repository: &T, two_factor: &TwoFactorEntity, code: &str) -> Result<()>
+where
+    T: TwoFactorRepository + Send + Sync + ?Sized,
+{
+    let two_factor_env = get_two_factor_env()?;
+    let secret = secret_box::decrypt(&two_factor_env.encryption_key, &two_factor.secret_ciphertext)?;
+
+    let step = totp::verify(&secret, code)?.ok_or_else(|| anyhow!("Invalid two-factor code"))?;
+    two_factor_repository.record_used_step(two_factor.brawler_id, step).await
+}
+
+fn generate_recovery_code() -> String {
+    let raw: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(10)
+        .map(|c| char::from(c).to_ascii_lowercase())
+        .collect();
+
+    format!("{}-{}", &raw[..5], &raw[5..])
+}
+
+fn normalize_recovery_code(code: &str) -> String {
+    code.trim().replace('-', "").to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Once;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+
+    use crate::domain::repositories::{brawlers::MockBrawlerRepository, two_factor::MockTwoFactorRepository};
+
+    const BRAWLER_ID: i32 = 7;
+
+    // Tests share one process environment, so the key is only set once and never changed.
+    fn encryption_key() -> String {
+        static INIT: Once = Once::new();
+        INIT.call_once(|| {
+            if std::env::var("TWO_FACTOR_ENCRYPTION_KEY").is_err() {
+                std::env::set_var(
+                    "TWO_FACTOR_ENCRYPTION_KEY",
+                    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+                );
+            }
+        });
+        get_two_factor_env().unwrap().encryption_key
+    }
+
+    fn enrolled(secret: &str) -> TwoFactorEntity {
+        let now = Utc::now().naive_utc();
+        TwoFactorEntity {
+            brawler_id: BRAWLER_ID,
+            secret_ciphertext: secret_box::encrypt(&encryption_key(), secret).unwrap(),
+            last_used_step: None,
+            enabled_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn accepts_a_fresh_authenticator_code_and_records_its_step() {
+        let secret = totp::generate_secret();
+        let two_factor = enrolled(&secret);
+
+        let mut two_factor_repository = MockTwoFactorRepository::new();
+        two_factor_repository
+            .expect_record_used_step()
+            .withf(|brawler_id, step| *brawler_id == BRAWLER_ID && *step >= Utc::now().timestamp() / 30 - 1)
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(()) }));
+        two_factor_repository.expect_consume_recovery_code().never();
+
+        verify_second_factor(&two_factor_repository, &two_factor, &totp::current_code(&secret))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuses_a_replayed_authenticator_code() {
+        let secret = totp::generate_secret();
+        let two_factor = enrolled(&secret);
+
+        // The repository only advances `last_used_step`, so a step it has already seen errors
+        let mut two_factor_repository = MockTwoFactorRepository::new();
+        two_factor_repository
+            .expect_record_used_step()
+            .times(1)
+            .returning(|_, _| Box::pin(async { Err(anyhow!("Two-factor code has already been used")) }));
+        two_factor_repository
+            .expect_consume_recovery_code()
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(false) }));
+
+        let error = verify_second_factor(&two_factor_repository, &two_factor, &totp::current_code(&secret))
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Invalid two-factor code");
+    }
+
+    #[tokio::test]
+    async fn accepts_a_recovery_code_in_any_formatting() {
+        let two_factor = enrolled(&totp::generate_secret());
+
+        let mut two_factor_repository = MockTwoFactorRepository::new();
+        two_factor_repository.expect_record_used_step().never();
+        two_factor_repository
+            .expect_consume_recovery_code()
+            .with(eq(BRAWLER_ID), eq(opaque_token::hash("abcde12345")))
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(true) }));
+
+        verify_second_factor(&two_factor_repository, &two_factor, " ABCDE-12345 ")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuses_an_unknown_or_spent_recovery_code() {
+        let two_factor = enrolled(&totp::generate_secret());
+
+        let mut two_factor_repository = MockTwoFactorRepository::new();
+        two_factor_repository
+            .expect_consume_recovery_code()
+            .times(1)
+            .returning(|_, _| Box::pin(async { Ok(false) }));
+
+        assert!(verify_second_factor(&two_factor_repository, &two_factor, "abcde-12345")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn enrollment_can_only_be_confirmed_with_the_authenticator() {
+        let two_factor = TwoFactorEntity {
+            enabled_at: None,
+            ..enrolled(&totp::generate_secret())
+        };
+
+        let mut two_factor_repository = MockTwoFactorRepository::new();
+        two_factor_repository
+            .expect_find_by_brawler_id()
+            .returning(move |_| {
+                let two_factor = two_factor.clone();
+                Box::pin(async move { Ok(Some(two_factor)) })
+            });
+        two_factor_repository.expect_consume_recovery_code().never();
+        two_factor_repository.expect_enable().never();
+
+        let use_case = TwoFactorUseCase::new(Arc::new(MockBrawlerRepository::new()), Arc::new(two_factor_repository));
+        assert!(use_case.confirm(BRAWLER_ID, "abcde-12345".to_string()).await.is_err());
+    }
+}
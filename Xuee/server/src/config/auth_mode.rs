@@ -0,0 +1,46 @@
+use anyhow::Result;
+use std::fmt;
+use std::convert::TryFrom;
+
+// How browsers carry their session. `Both` exists so clients can move to cookies one at a time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AuthMode {
+    #[default]
+    Bearer,
+    Cookie,
+    Both,
+}
+
+impl AuthMode {
+    pub fn accepts_bearer(&self) -> bool {
+        matches!(self, AuthMode::Bearer | AuthMode::Both)
+    }
+
+    pub fn accepts_cookie(&self) -> bool {
+        matches!(self, AuthMode::Cookie | AuthMode::Both)
+    }
+}
+
+impl fmt::Display for AuthMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = match self {
+            AuthMode::Bearer => "bearer",
+            AuthMode::Cookie => "cookie",
+            AuthMode::Both => "both",
+        };
+        write!(f, "{}", mode)
+    }
+}
+
+impl TryFrom<&str> for AuthMode {
+    type Error = anyhow::Error;
+
+    fn try_from(mode: &str) -> Result<Self, Self::Error> {
+        match mode {
+            "bearer" => Ok(Self::Bearer),
+            "cookie" => Ok(Self::Cookie),
+            "both" => Ok(Self::Both),
+            _ => Err(anyhow::anyhow!("Invalid auth mode")),
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::Result;
 use std::env;
 use crate::config::{
-    config_model::{CloudinaryEnv, Database, DotEnvyConfig, JwtEnv, Server},
+    auth_mode::AuthMode,
+    config_model::{AccountDeletionEnv, Argon2Env, CloudinaryEnv, Database, DotEnvyConfig, EmailVerificationEnv, ImageStorageBackend, ImageStorageEnv, ImageUploadEnv, JwtEnv, JwtKeyEnv, LoginThrottle, MagicLinkEnv, OidcProviderEnv, PasswordPolicyEnv, Server, TwoFactorEnv, UsernamePolicyEnv, WebAuthnEnv},
     stage::Stage,
 };
 
@@ -18,6 +19,12 @@ pub fn load() -> Result<DotEnvyConfig> {
         timeout: env::var("SERVER_TIMEOUT")
             .expect("SERVER_TIMEOUT is valid")
             .parse()?,
+        cors_allowed_origins: env::var("CORS_ALLOWED_ORIGINS")
+            .unwrap_or_else(|_| "http://localhost:4200".to_string())
+            .split(',')
+            .map(|origin| origin.trim().to_string())
+            .filter(|origin| !origin.is_empty())
+            .collect(),
     };
 
     let database = Database {
@@ -26,14 +33,40 @@ pub fn load() -> Result<DotEnvyConfig> {
             .parse()?,
     };
 
-    let secret = env::var("JWT_USER_SECRET")
+    let secret: String = env::var("JWT_USER_SECRET")
         .expect("SECRET is valid")
         .parse()?;
 
+    // Cookie signing gets its own key so leaking or rotating one never affects the other.
+    let cookie_secret = env::var("COOKIE_SECRET").map_err(|_| anyhow::anyhow!("COOKIE_SECRET must be set"))?;
+    if cookie_secret == secret {
+        return Err(anyhow::anyhow!("COOKIE_SECRET must differ from JWT_USER_SECRET"));
+    }
+
+    let login_throttle = LoginThrottle {
+        max_attempts_per_username: env::var("LOGIN_MAX_ATTEMPTS_PER_USERNAME")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse()?,
+        max_attempts_per_ip: env::var("LOGIN_MAX_ATTEMPTS_PER_IP")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse()?,
+        lockout_seconds: env::var("LOGIN_LOCKOUT_SECONDS")
+            .unwrap_or_else(|_| "900".to_string())
+            .parse()?,
+        backoff_base_seconds: env::var("LOGIN_BACKOFF_BASE_SECONDS")
+            .unwrap_or_else(|_| "1".to_string())
+            .parse()?,
+        backoff_max_seconds: env::var("LOGIN_BACKOFF_MAX_SECONDS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse()?,
+    };
+
     let config = DotEnvyConfig {
         server,
         database,
         secret, 
+        cookie_secret,
+        login_throttle,
     };
 
     Ok(config)
@@ -46,15 +79,211 @@ pub fn get_stage() -> Stage {
     Stage::try_from(stage_str.as_str()).unwrap_or_default()
 }
 
+pub fn get_auth_mode() -> AuthMode {
+    dotenvy::dotenv().ok();
+
+    let mode_str = env::var("AUTH_MODE").unwrap_or("".to_string());
+    AuthMode::try_from(mode_str.to_lowercase().as_str()).unwrap_or_default()
+}
+
 
 pub fn get_jwt_env() -> JwtEnv {
     dotenvy::dotenv().ok();
+
+    // Without JWT_KEYS we fall back to a single HS256 key so existing deployments keep working.
+    let keys = match env::var("JWT_KEYS") {
+        Ok(kids) => kids
+            .split(',')
+            .map(str::trim)
+            .filter(|kid| !kid.is_empty())
+            .map(get_jwt_key_env)
+            .collect(),
+        Err(_) => vec![JwtKeyEnv {
+            kid: "default".to_string(),
+            algorithm: "HS256".to_string(),
+            secret: Some(env::var("JWT_USER_SECRET").expect("JWT_USER_SECRET must be set")),
+            private_key_path: None,
+            public_key_path: None,
+        }],
+    };
+
     JwtEnv {
-        secret: env::var("JWT_USER_SECRET").expect("JWT_USER_SECRET must be set"),
         ttl: env::var("JWT_TTL").unwrap_or_else(|_| "3600".to_string()).parse().expect("JWT_TTL must be a number"),
+        refresh_ttl: env::var("JWT_REFRESH_TTL").unwrap_or_else(|_| "2592000".to_string()).parse().expect("JWT_REFRESH_TTL must be a number"),
+        active_kid: env::var("JWT_ACTIVE_KID").unwrap_or_else(|_| "default".to_string()),
+        keys,
+    }
+}
+
+fn get_jwt_key_env(kid: &str) -> JwtKeyEnv {
+    let prefix = format!("JWT_KEY_{}", kid.to_uppercase().replace('-', "_"));
+
+    JwtKeyEnv {
+        kid: kid.to_string(),
+        algorithm: env::var(format!("{prefix}_ALG")).unwrap_or_else(|_| "HS256".to_string()),
+        secret: env::var(format!("{prefix}_SECRET")).ok(),
+        private_key_path: env::var(format!("{prefix}_PRIVATE_KEY_PATH")).ok(),
+        public_key_path: env::var(format!("{prefix}_PUBLIC_KEY_PATH")).ok(),
+    }
+}
+
+pub fn get_email_verification_env() -> Result<EmailVerificationEnv> {
+    dotenvy::dotenv().ok();
+    Ok(EmailVerificationEnv {
+        required: env::var("EMAIL_VERIFICATION_REQUIRED").map(|v| v == "true").unwrap_or(false),
+        ttl: env::var("EMAIL_VERIFICATION_TTL").unwrap_or_else(|_| "86400".to_string()).parse()?,
+    })
+}
+
+// Defaults match the OWASP recommendation for Argon2id (19 MiB, 2 iterations, 1 lane).
+pub fn get_argon2_env() -> Result<Argon2Env> {
+    dotenvy::dotenv().ok();
+    Ok(Argon2Env {
+        memory_kib: env::var("ARGON2_MEMORY_KIB").unwrap_or_else(|_| "19456".to_string()).parse()?,
+        iterations: env::var("ARGON2_ITERATIONS").unwrap_or_else(|_| "2".to_string()).parse()?,
+        parallelism: env::var("ARGON2_PARALLELISM").unwrap_or_else(|_| "1".to_string()).parse()?,
+    })
+}
+
+pub fn get_password_policy_env() -> Result<PasswordPolicyEnv> {
+    dotenvy::dotenv().ok();
+    Ok(PasswordPolicyEnv {
+        min_length: env::var("PASSWORD_MIN_LENGTH").unwrap_or_else(|_| "10".to_string()).parse()?,
+        max_length: env::var("PASSWORD_MAX_LENGTH").unwrap_or_else(|_| "128".to_string()).parse()?,
+    })
+}
+
+pub fn get_username_policy_env() -> Result<UsernamePolicyEnv> {
+    dotenvy::dotenv().ok();
+    Ok(UsernamePolicyEnv {
+        min_length: env::var("USERNAME_MIN_LENGTH").unwrap_or_else(|_| "3".to_string()).parse()?,
+        max_length: env::var("USERNAME_MAX_LENGTH").unwrap_or_else(|_| "255".to_string()).parse()?,
+        change_cooldown_days: env::var("USERNAME_CHANGE_COOLDOWN_DAYS").unwrap_or_else(|_| "30".to_string()).parse()?,
+        redirect_days: env::var("USERNAME_REDIRECT_DAYS").unwrap_or_else(|_| "90".to_string()).parse()?,
+    })
+}
+
+pub fn get_account_deletion_env() -> Result<AccountDeletionEnv> {
+    dotenvy::dotenv().ok();
+    Ok(AccountDeletionEnv {
+        grace_days: env::var("ACCOUNT_DELETION_GRACE_DAYS").unwrap_or_else(|_| "30".to_string()).parse()?,
+        purge_interval: env::var("ACCOUNT_PURGE_INTERVAL").unwrap_or_else(|_| "3600".to_string()).parse()?,
+    })
+}
+
+pub fn get_magic_link_env() -> Result<MagicLinkEnv> {
+    dotenvy::dotenv().ok();
+    Ok(MagicLinkEnv {
+        ttl: env::var("MAGIC_LINK_TTL").unwrap_or_else(|_| "900".to_string()).parse()?,
+        max_per_address: env::var("MAGIC_LINK_MAX_PER_ADDRESS").unwrap_or_else(|_| "3".to_string()).parse()?,
+    })
+}
+
+pub fn get_two_factor_env() -> Result<TwoFactorEnv> {
+    dotenvy::dotenv().ok();
+    Ok(TwoFactorEnv {
+        encryption_key: env::var("TWO_FACTOR_ENCRYPTION_KEY")?,
+        issuer: env::var("TWO_FACTOR_ISSUER").unwrap_or_else(|_| "Nebula".to_string()),
+        challenge_ttl: env::var("TWO_FACTOR_CHALLENGE_TTL").unwrap_or_else(|_| "300".to_string()).parse()?,
+    })
+}
+
+// The RP ID must be the registrable domain the browser sees, and every origin the client is
+// served from has to be listed, or the browser-signed client data will not verify.
+pub fn get_webauthn_env() -> Result<WebAuthnEnv> {
+    dotenvy::dotenv().ok();
+    let frontend_url = env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:4200".to_string());
+
+    Ok(WebAuthnEnv {
+        rp_id: env::var("WEBAUTHN_RP_ID").unwrap_or_else(|_| "localhost".to_string()),
+        rp_name: env::var("WEBAUTHN_RP_NAME").unwrap_or_else(|_| "Nebula".to_string()),
+        origins: env::var("WEBAUTHN_ORIGINS")
+            .unwrap_or(frontend_url)
+            .split(',')
+            .map(|origin| origin.trim().trim_end_matches('/').to_string())
+            .filter(|origin| !origin.is_empty())
+            .collect(),
+        timeout_ms: env::var("WEBAUTHN_TIMEOUT_MS").unwrap_or_else(|_| "300000".to_string()).parse()?,
+    })
+}
+
+pub fn get_oidc_env() -> Result<Vec<OidcProviderEnv>> {
+    dotenvy::dotenv().ok();
+
+    match env::var("OIDC_PROVIDERS") {
+        Ok(names) => names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(get_oidc_provider_env)
+            .collect(),
+        // Deployments that only ever configured Google keep working without OIDC_PROVIDERS.
+        Err(_) => match env::var("GOOGLE_CLIENT_ID") {
+            Ok(client_id) => Ok(vec![OidcProviderEnv {
+                name: "google".to_string(),
+                issuer_url: Some("https://accounts.google.com".to_string()),
+                authorization_url: None,
+                token_url: None,
+                userinfo_url: None,
+                client_id,
+                client_secret: env::var("GOOGLE_CLIENT_SECRET")?,
+                redirect_url: env::var("GOOGLE_REDIRECT_URL")
+                    .unwrap_or_else(|_| "http://localhost:4200/google-callback".to_string()),
+                scopes: vec!["openid".to_string(), "email".to_string(), "profile".to_string()],
+            }]),
+            Err(_) => Ok(Vec::new()),
+        },
     }
 }
 
+fn get_oidc_provider_env(name: &str) -> Result<OidcProviderEnv> {
+    let prefix = format!("OIDC_{}", name.to_uppercase().replace('-', "_"));
+    let frontend_url = env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:4200".to_string());
+
+    Ok(OidcProviderEnv {
+        name: name.to_string(),
+        issuer_url: env::var(format!("{prefix}_ISSUER")).ok(),
+        authorization_url: env::var(format!("{prefix}_AUTH_URL")).ok(),
+        token_url: env::var(format!("{prefix}_TOKEN_URL")).ok(),
+        userinfo_url: env::var(format!("{prefix}_USERINFO_URL")).ok(),
+        client_id: env::var(format!("{prefix}_CLIENT_ID"))?,
+        client_secret: env::var(format!("{prefix}_CLIENT_SECRET"))?,
+        redirect_url: env::var(format!("{prefix}_REDIRECT_URL"))
+            .unwrap_or_else(|_| format!("{frontend_url}/oidc-callback/{name}")),
+        scopes: env::var(format!("{prefix}_SCOPES"))
+            .unwrap_or_else(|_| "openid email profile".to_string())
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+// Cloudinary stays the default so existing deployments keep uploading where they always have.
+// Local storage writes under statics/, which the server already serves at the site root.
+pub fn get_image_storage_env() -> Result<ImageStorageEnv> {
+    dotenvy::dotenv().ok();
+    let backend = match env::var("IMAGE_STORAGE").unwrap_or_else(|_| "cloudinary".to_string()).to_lowercase().as_str() {
+        "local" => ImageStorageBackend::Local,
+        "cloudinary" => ImageStorageBackend::Cloudinary,
+        other => return Err(anyhow::anyhow!("Unknown IMAGE_STORAGE backend: {}", other)),
+    };
+
+    Ok(ImageStorageEnv {
+        backend,
+        local_dir: env::var("IMAGE_STORAGE_DIR").unwrap_or_else(|_| "statics/uploads".to_string()),
+        public_base_url: env::var("IMAGE_PUBLIC_BASE_URL").unwrap_or_else(|_| "/uploads".to_string()),
+    })
+}
+
+pub fn get_image_upload_env() -> Result<ImageUploadEnv> {
+    dotenvy::dotenv().ok();
+    Ok(ImageUploadEnv {
+        max_bytes: env::var("IMAGE_MAX_BYTES").unwrap_or_else(|_| "5242880".to_string()).parse()?,
+        min_dimension: env::var("IMAGE_MIN_DIMENSION").unwrap_or_else(|_| "64".to_string()).parse()?,
+        max_dimension: env::var("IMAGE_MAX_DIMENSION").unwrap_or_else(|_| "4096".to_string()).parse()?,
+    })
+}
+
 pub fn get_cloudinary_env() -> Result<CloudinaryEnv> {
     dotenvy::dotenv().ok();
     Ok(CloudinaryEnv {
@@ -3,6 +3,7 @@ pub struct Server {
     pub port: u16,
     pub body_limit: u64,
     pub timeout: u64,
+    pub cors_allowed_origins: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -10,10 +11,97 @@ pub struct Database {
     pub url: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct LoginThrottle {
+    pub max_attempts_per_username: i32,
+    pub max_attempts_per_ip: i32,
+    pub lockout_seconds: i64,
+    pub backoff_base_seconds: i64,
+    pub backoff_max_seconds: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Argon2Env {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct PasswordPolicyEnv {
+    pub min_length: usize,
+    pub max_length: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct JwtKeyEnv {
+    pub kid: String,
+    pub algorithm: String,
+    pub secret: Option<String>,
+    pub private_key_path: Option<String>,
+    pub public_key_path: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct JwtEnv {
-    pub secret: String,
     pub ttl : i64,
+    pub refresh_ttl: i64,
+    pub active_kid: String,
+    pub keys: Vec<JwtKeyEnv>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailVerificationEnv {
+    pub required: bool,
+    pub ttl: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MagicLinkEnv {
+    pub ttl: i64,
+    pub max_per_address: i32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TwoFactorEnv {
+    pub encryption_key: String,
+    pub issuer: String,
+    pub challenge_ttl: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountDeletionEnv {
+    pub grace_days: i64,
+    pub purge_interval: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct UsernamePolicyEnv {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub change_cooldown_days: i64,
+    pub redirect_days: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebAuthnEnv {
+    pub rp_id: String,
+    pub rp_name: String,
+    pub origins: Vec<String>,
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct OidcProviderEnv {
+    pub name: String,
+    pub issuer_url: Option<String>,
+    pub authorization_url: Option<String>,
+    pub token_url: Option<String>,
+    pub userinfo_url: Option<String>,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_url: String,
+    pub scopes: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -23,9 +111,31 @@ pub struct CloudinaryEnv {
     pub api_secret: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageStorageBackend {
+    Local,
+    Cloudinary,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageStorageEnv {
+    pub backend: ImageStorageBackend,
+    pub local_dir: String,
+    pub public_base_url: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageUploadEnv {
+    pub max_bytes: usize,
+    pub min_dimension: u32,
+    pub max_dimension: u32,
+}
+
 #[derive(Debug, Clone)]
 pub struct DotEnvyConfig {
     pub server: Server,
     pub database: Database,
     pub secret: String,
+    pub cookie_secret: String,
+    pub login_throttle: LoginThrottle,
 }
@@ -0,0 +1,31 @@
+use crate::infrastructure::database::schema::auth_events;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = auth_events)]
+pub struct AuthEventEntity {
+    pub id: i32,
+    pub brawler_id: Option<i32>,
+    pub username: Option<String>,
+    pub event_type: String,
+    pub outcome: String,
+    pub reason: Option<String>,
+    pub provider: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = auth_events)]
+pub struct NewAuthEventEntity {
+    pub brawler_id: Option<i32>,
+    pub username: Option<String>,
+    pub event_type: String,
+    pub outcome: String,
+    pub reason: Option<String>,
+    pub provider: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
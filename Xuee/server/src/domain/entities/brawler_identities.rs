@@ -0,0 +1,24 @@
+use crate::infrastructure::database::schema::brawler_identities;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = brawler_identities)]
+pub struct BrawlerIdentityEntity {
+    pub id: i32,
+    pub brawler_id: i32,
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = brawler_identities)]
+pub struct NewBrawlerIdentityEntity {
+    pub brawler_id: i32,
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+}
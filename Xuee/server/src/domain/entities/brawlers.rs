@@ -13,6 +13,11 @@ pub struct BrawlerEntity {
     pub display_name: String,
     pub avatar_url: Option<String>,
     pub avatar_public_id: Option<String>,
+    pub email_verified_at: Option<NaiveDateTime>,
+    pub role: String,
+    pub deletion_scheduled_for: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub username_changed_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, Insertable)]
@@ -21,4 +26,27 @@ pub struct RegisterBrawlerEntity {
     pub username: String,
     pub password: String,
     pub display_name: String,
-}
\ No newline at end of file
+}
+#[cfg(test)]
+impl BrawlerEntity {
+    // A plain, verified brawler for use case tests.
+    pub fn fixture(id: i32, username: &str) -> Self {
+        let now = chrono::Utc::now().naive_utc();
+
+        Self {
+            id,
+            username: username.to_string(),
+            password: String::new(),
+            created_at: now,
+            updated_at: now,
+            display_name: username.to_string(),
+            avatar_url: None,
+            avatar_public_id: None,
+            email_verified_at: Some(now),
+            role: "Brawler".to_string(),
+            deletion_scheduled_for: None,
+            deleted_at: None,
+            username_changed_at: None,
+        }
+    }
+}
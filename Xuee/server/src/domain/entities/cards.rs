@@ -1,8 +1,8 @@
 use crate::infrastructure::database::schema::{cards, user_cards};
 use chrono::NaiveDateTime;
-use diesel::{Selectable, Queryable, Identifiable, Insertable, Associations};
+use diesel::{Selectable, Queryable, Identifiable, Insertable, Associations, AsChangeset};
 use crate::domain::entities::brawlers::BrawlerEntity;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Identifiable, Selectable, Queryable, Serialize)]
 #[diesel(table_name = cards)]
@@ -16,6 +16,17 @@ pub struct CardEntity {
     pub image_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Insertable, AsChangeset, Deserialize)]
+#[diesel(table_name = cards)]
+pub struct CardDraftEntity {
+    pub name: String,
+    pub language: String,
+    pub rarity: String,
+    pub attack: i32,
+    pub defense: i32,
+    pub image_url: Option<String>,
+}
+
 #[derive(Debug, Clone, Identifiable, Selectable, Queryable, Associations, Serialize)]
 #[diesel(belongs_to(CardEntity, foreign_key = card_id))]
 #[diesel(belongs_to(BrawlerEntity, foreign_key = user_id))]
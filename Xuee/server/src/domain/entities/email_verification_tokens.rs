@@ -0,0 +1,24 @@
+use crate::infrastructure::database::schema::email_verification_tokens;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = email_verification_tokens)]
+pub struct EmailVerificationTokenEntity {
+    pub id: i32,
+    pub brawler_id: i32,
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = email_verification_tokens)]
+pub struct NewEmailVerificationTokenEntity {
+    pub brawler_id: i32,
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub email: String,
+}
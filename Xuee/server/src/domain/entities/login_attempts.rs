@@ -0,0 +1,25 @@
+use crate::infrastructure::database::schema::login_attempts;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = login_attempts)]
+pub struct LoginAttemptEntity {
+    pub id: i32,
+    pub scope: String,
+    pub identifier: String,
+    pub failed_count: i32,
+    pub last_failed_at: Option<NaiveDateTime>,
+    pub locked_until: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = login_attempts)]
+pub struct NewLoginAttemptEntity {
+    pub scope: String,
+    pub identifier: String,
+    pub failed_count: i32,
+    pub last_failed_at: Option<NaiveDateTime>,
+}
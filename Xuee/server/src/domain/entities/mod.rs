@@ -4,3 +4,15 @@ pub mod brawlers;
 pub mod missions;
 pub mod crew_memberships;
 
+pub mod sessions;
+pub mod password_reset_tokens;
+pub mod email_verification_tokens;
+pub mod two_factor;
+pub mod login_attempts;
+pub mod brawler_identities;
+pub mod api_tokens;
+pub mod auth_events;
+pub mod magic_link_tokens;
+pub mod webauthn_credentials;
+pub mod username_redirects;
+pub mod social;
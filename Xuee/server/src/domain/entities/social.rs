@@ -0,0 +1,28 @@
+use crate::infrastructure::database::schema::{brawler_follows, friend_requests};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = brawler_follows)]
+pub struct NewFollowEntity {
+    pub follower_id: i32,
+    pub followee_id: i32,
+}
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = friend_requests)]
+pub struct FriendRequestEntity {
+    pub id: i32,
+    pub requester_id: i32,
+    pub addressee_id: i32,
+    pub status: String,
+    pub responded_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = friend_requests)]
+pub struct NewFriendRequestEntity {
+    pub requester_id: i32,
+    pub addressee_id: i32,
+}
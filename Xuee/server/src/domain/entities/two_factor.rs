@@ -0,0 +1,29 @@
+use crate::infrastructure::database::schema::{brawler_recovery_codes, brawler_two_factor};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = brawler_two_factor)]
+#[diesel(primary_key(brawler_id))]
+pub struct TwoFactorEntity {
+    pub brawler_id: i32,
+    pub secret_ciphertext: String,
+    pub last_used_step: Option<i64>,
+    pub enabled_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = brawler_two_factor)]
+pub struct NewTwoFactorEntity {
+    pub brawler_id: i32,
+    pub secret_ciphertext: String,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = brawler_recovery_codes)]
+pub struct NewRecoveryCodeEntity {
+    pub brawler_id: i32,
+    pub code_hash: String,
+}
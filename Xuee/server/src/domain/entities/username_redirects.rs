@@ -0,0 +1,21 @@
+use crate::infrastructure::database::schema::username_redirects;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = username_redirects)]
+pub struct UsernameRedirectEntity {
+    pub id: i32,
+    pub old_username: String,
+    pub brawler_id: i32,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = username_redirects)]
+pub struct NewUsernameRedirectEntity {
+    pub old_username: String,
+    pub brawler_id: i32,
+    pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,29 @@
+use crate::infrastructure::database::schema::webauthn_credentials;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+#[derive(Debug, Clone, Identifiable, Selectable, Queryable)]
+#[diesel(table_name = webauthn_credentials)]
+pub struct WebAuthnCredentialEntity {
+    pub id: i32,
+    pub brawler_id: i32,
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub aaguid: String,
+    pub name: String,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = webauthn_credentials)]
+pub struct NewWebAuthnCredentialEntity {
+    pub brawler_id: i32,
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub aaguid: String,
+    pub name: String,
+}
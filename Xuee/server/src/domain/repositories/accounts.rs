@@ -0,0 +1,16 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+use crate::domain::value_objects::account_model::AccountExportModel;
+
+#[async_trait]
+#[automock]
+pub trait AccountRepository {
+    async fn export(&self, brawler_id: i32) -> Result<AccountExportModel>;
+    async fn schedule_deletion(&self, brawler_id: i32, scheduled_for: NaiveDateTime) -> Result<()>;
+    async fn cancel_deletion(&self, brawler_id: i32) -> Result<()>;
+    async fn due_for_deletion(&self) -> Result<Vec<i32>>;
+    async fn erase(&self, brawler_id: i32) -> Result<()>;
+}
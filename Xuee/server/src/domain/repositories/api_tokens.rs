@@ -0,0 +1,15 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::domain::entities::api_tokens::{ApiTokenEntity, NewApiTokenEntity};
+
+#[async_trait]
+#[automock]
+pub trait ApiTokenRepository {
+    async fn create(&self, new_token: NewApiTokenEntity) -> Result<ApiTokenEntity>;
+    async fn list_for_brawler(&self, brawler_id: i32) -> Result<Vec<ApiTokenEntity>>;
+    async fn find_active_by_hash(&self, token_hash: String) -> Result<Option<ApiTokenEntity>>;
+    async fn touch(&self, token_id: i32) -> Result<()>;
+    async fn revoke(&self, brawler_id: i32, token_id: i32) -> Result<()>;
+}
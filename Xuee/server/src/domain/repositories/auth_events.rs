@@ -0,0 +1,14 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::domain::entities::auth_events::{AuthEventEntity, NewAuthEventEntity};
+use crate::domain::value_objects::auth_event_model::AuthEventFilter;
+
+#[async_trait]
+#[automock]
+pub trait AuthEventRepository {
+    async fn record(&self, new_event: NewAuthEventEntity) -> Result<()>;
+    async fn list_for_brawler(&self, brawler_id: i32, limit: i64) -> Result<Vec<AuthEventEntity>>;
+    async fn search(&self, filter: AuthEventFilter) -> Result<Vec<AuthEventEntity>>;
+}
@@ -0,0 +1,14 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::domain::entities::brawler_identities::{BrawlerIdentityEntity, NewBrawlerIdentityEntity};
+
+#[async_trait]
+#[automock]
+pub trait BrawlerIdentityRepository {
+    async fn find_by_provider_subject(&self, provider: String, subject: String) -> Result<Option<BrawlerIdentityEntity>>;
+    async fn list_for_brawler(&self, brawler_id: i32) -> Result<Vec<BrawlerIdentityEntity>>;
+    async fn link(&self, new_identity: NewBrawlerIdentityEntity) -> Result<i32>;
+    async fn unlink(&self, brawler_id: i32, provider: String) -> Result<()>;
+}
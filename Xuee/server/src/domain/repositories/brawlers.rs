@@ -3,6 +3,9 @@ use async_trait::async_trait;
 use mockall::automock;
 
 use crate::domain::entities::brawlers::{BrawlerEntity, RegisterBrawlerEntity};
+use crate::domain::entities::username_redirects::NewUsernameRedirectEntity;
+use crate::domain::value_objects::brawler_model::{BrawlerProfileModel, DirectoryBrawlerModel, DirectorySort};
+use crate::domain::value_objects::mission_filter::{MissionFilter, MyMissionsView};
 use crate::domain::value_objects::mission_model::MissionModel;
 
 #[async_trait]
@@ -10,8 +13,25 @@ use crate::domain::value_objects::mission_model::MissionModel;
 pub trait BrawlerRepository {
     async fn register(&self, register_brawler_entity: RegisterBrawlerEntity) -> Result<i32>;
     async fn find_by_username(&self, username: String) -> Result<BrawlerEntity>;
-    async fn update_avatar(&self, id: i32, avatar_url: String) -> Result<()>;
+    async fn is_username_taken(&self, username: String, except_brawler_id: Option<i32>) -> Result<bool>;
+    async fn find_username_redirect(&self, username: String) -> Result<Option<String>>;
+    async fn find_by_id(&self, id: i32) -> Result<BrawlerEntity>;
+    async fn change_username(&self, id: i32, new_username: String, redirect: NewUsernameRedirectEntity) -> Result<()>;
+    async fn update_avatar(&self, id: i32, avatar_url: String, avatar_public_id: String) -> Result<()>;
     async fn update_display_name(&self, id: i32, display_name: String) -> Result<()>;
-    async fn update_password(&self, id: i32, password_hash: String) -> Result<()>;
-    async fn get_missions(&self, brawler_id: i32) -> Result<Vec<MissionModel>>;
+    async fn update_password(&self, id: i32, password_hash: String, keep_session_id: Option<i32>) -> Result<()>;
+    async fn rehash_password(&self, id: i32, password_hash: String) -> Result<()>;
+    // False when `email` is no longer the brawler's username, e.g. after a rename
+    async fn mark_email_verified(&self, id: i32, email: String) -> Result<bool>;
+    async fn update_role(&self, id: i32, role: String) -> Result<()>;
+    async fn get_missions(&self, brawler_id: i32, view: MyMissionsView, filter: &MissionFilter) -> Result<Vec<MissionModel>>;
+    async fn count_missions(&self, brawler_id: i32, view: MyMissionsView, filter: &MissionFilter) -> Result<i64>;
+    async fn get_profile(&self, brawler_id: i32) -> Result<Option<BrawlerProfileModel>>;
+    async fn search_directory(
+        &self,
+        search: Option<String>,
+        sort: DirectorySort,
+        after: Option<(i64, i32)>,
+        limit: i64,
+    ) -> Result<Vec<DirectoryBrawlerModel>>;
 }
@@ -1,7 +1,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use mockall::automock;
-use crate::domain::entities::cards::{CardEntity, UserCardEntity, NewUserCardEntity, UserCardDetail};
+use crate::domain::entities::cards::{CardDraftEntity, CardEntity, UserCardEntity, NewUserCardEntity, UserCardDetail};
 use crate::domain::entities::battles::{BattleEntity, NewBattleEntity};
 
 #[async_trait]
@@ -13,6 +13,11 @@ pub trait CardRepository {
     async fn update_card_exp(&self, user_card_id: i32, new_exp: i32, new_level: i32) -> Result<()>;
     async fn get_card_by_id(&self, card_id: i32) -> Result<CardEntity>;
     async fn get_user_card_by_id(&self, user_card_id: i32) -> Result<UserCardDetail>;
+
+    // Catalog management (admin only)
+    async fn add_card(&self, card: CardDraftEntity) -> Result<CardEntity>;
+    async fn update_card(&self, card_id: i32, card: CardDraftEntity) -> Result<CardEntity>;
+    async fn delete_card(&self, card_id: i32) -> Result<()>;
     
     // Battle related
     async fn create_battle(&self, battle: NewBattleEntity) -> Result<BattleEntity>;
@@ -0,0 +1,15 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+use crate::domain::entities::login_attempts::LoginAttemptEntity;
+
+#[async_trait]
+#[automock]
+pub trait LoginAttemptRepository {
+    async fn find(&self, scope: String, identifier: String) -> Result<Option<LoginAttemptEntity>>;
+    async fn record_failure(&self, scope: String, identifier: String) -> Result<LoginAttemptEntity>;
+    async fn lock(&self, scope: String, identifier: String, locked_until: NaiveDateTime) -> Result<()>;
+    async fn clear(&self, scope: String, identifier: String) -> Result<()>;
+}
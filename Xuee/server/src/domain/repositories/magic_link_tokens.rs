@@ -0,0 +1,14 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::domain::entities::magic_link_tokens::{NewMagicLinkTokenEntity, MagicLinkTokenEntity};
+
+#[async_trait]
+#[automock]
+pub trait MagicLinkTokenRepository {
+    async fn create(&self, new_token: NewMagicLinkTokenEntity) -> Result<i32>;
+    async fn find_valid_by_hash(&self, token_hash: String) -> Result<MagicLinkTokenEntity>;
+    async fn mark_used(&self, token_id: i32) -> Result<()>;
+    async fn invalidate_all_for_brawler(&self, brawler_id: i32) -> Result<()>;
+}
@@ -9,4 +9,5 @@ pub trait MissionManagementRepository {
     async fn add(&self, add_mission_entity: AddMissionEntity) -> Result<i32>;
     async fn edit(&self, mission_id: i32, edit_mission_entity: EditMissionEntity) -> Result<i32>;
     async fn remove(&self, mission_id: i32, chief_id: i32) -> Result<()>;
+    async fn moderate_remove(&self, mission_id: i32) -> Result<()>;
 }
@@ -13,6 +13,7 @@ use crate::domain::value_objects::{
 pub trait MissionViewingRepository {
     async fn view_detail(&self, mission_id: i32) -> Result<MissionModel>;
     async fn gets(&self, filter: &MissionFilter) -> Result<Vec<MissionModel>>;
+    async fn count(&self, filter: &MissionFilter) -> Result<i64>;
     async fn crew_counting(&self, mission_id: i32) -> Result<u32>;
     async fn get_mission_crew(&self, mission_id: i32) -> Result<Vec<BrawlerModel>>;
 }
@@ -5,4 +5,16 @@ pub mod missions;
 pub mod mission_operation;
 pub mod mission_viewing;
 pub mod crew_operation;
+pub mod sessions;
 
+pub mod password_reset_tokens;
+pub mod email_verification_tokens;
+pub mod two_factor;
+pub mod login_attempts;
+pub mod brawler_identities;
+pub mod api_tokens;
+pub mod auth_events;
+pub mod magic_link_tokens;
+pub mod webauthn_credentials;
+pub mod accounts;
+pub mod social;
@@ -0,0 +1,21 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+use crate::domain::entities::sessions::{NewSessionEntity, SessionEntity};
+
+#[async_trait]
+#[automock]
+pub trait SessionRepository {
+    async fn create(&self, new_session: NewSessionEntity) -> Result<i32>;
+    async fn find_active_by_refresh_token_hash(&self, refresh_token_hash: String) -> Result<SessionEntity>;
+    async fn rotate(&self, session_id: i32, current_hash: String, new_hash: String, expires_at: NaiveDateTime, ip_address: Option<String>) -> Result<()>;
+    async fn list_active_for_brawler(&self, brawler_id: i32) -> Result<Vec<SessionEntity>>;
+    async fn revoke(&self, session_id: i32) -> Result<()>;
+    async fn revoke_for_brawler(&self, brawler_id: i32, session_id: i32) -> Result<()>;
+    async fn revoke_others(&self, brawler_id: i32, keep_session_id: i32) -> Result<usize>;
+    async fn revoke_all_for_brawler(&self, brawler_id: i32) -> Result<()>;
+    async fn is_active(&self, session_id: i32) -> Result<bool>;
+    async fn touch(&self, session_id: i32) -> Result<()>;
+}
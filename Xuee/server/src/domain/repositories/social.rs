@@ -0,0 +1,26 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::domain::entities::social::{FriendRequestEntity, NewFollowEntity, NewFriendRequestEntity};
+use crate::domain::value_objects::social_model::{
+    FriendRequestDirection, FriendRequestModel, FriendRequestStatus, SocialBrawlerModel,
+};
+
+#[async_trait]
+#[automock]
+pub trait SocialRepository {
+    async fn follow(&self, follow: NewFollowEntity) -> Result<bool>;
+    async fn unfollow(&self, follower_id: i32, followee_id: i32) -> Result<bool>;
+    async fn list_followers(&self, brawler_id: i32) -> Result<Vec<SocialBrawlerModel>>;
+    async fn list_following(&self, brawler_id: i32) -> Result<Vec<SocialBrawlerModel>>;
+    async fn list_friends(&self, brawler_id: i32) -> Result<Vec<SocialBrawlerModel>>;
+    async fn are_friends(&self, brawler_id: i32, other_id: i32) -> Result<bool>;
+    async fn remove_friend(&self, brawler_id: i32, other_id: i32) -> Result<bool>;
+    async fn create_friend_request(&self, request: NewFriendRequestEntity) -> Result<i32>;
+    async fn find_friend_request(&self, id: i32) -> Result<FriendRequestEntity>;
+    async fn find_pending_friend_request(&self, requester_id: i32, addressee_id: i32) -> Result<Option<FriendRequestEntity>>;
+    async fn respond_to_friend_request(&self, id: i32, status: FriendRequestStatus) -> Result<()>;
+    async fn delete_friend_request(&self, id: i32) -> Result<()>;
+    async fn list_friend_requests(&self, brawler_id: i32, direction: FriendRequestDirection) -> Result<Vec<FriendRequestModel>>;
+}
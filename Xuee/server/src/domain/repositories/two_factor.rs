@@ -0,0 +1,17 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::domain::entities::two_factor::{NewRecoveryCodeEntity, NewTwoFactorEntity, TwoFactorEntity};
+
+#[async_trait]
+#[automock]
+pub trait TwoFactorRepository {
+    async fn find_by_brawler_id(&self, brawler_id: i32) -> Result<Option<TwoFactorEntity>>;
+    async fn upsert_pending(&self, new_two_factor: NewTwoFactorEntity) -> Result<()>;
+    async fn enable(&self, brawler_id: i32) -> Result<()>;
+    async fn record_used_step(&self, brawler_id: i32, step: i64) -> Result<()>;
+    async fn remove(&self, brawler_id: i32) -> Result<()>;
+    async fn replace_recovery_codes(&self, brawler_id: i32, recovery_codes: Vec<NewRecoveryCodeEntity>) -> Result<()>;
+    async fn consume_recovery_code(&self, brawler_id: i32, code_hash: String) -> Result<bool>;
+}
@@ -0,0 +1,19 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use mockall::automock;
+
+use crate::domain::entities::webauthn_credentials::{NewWebAuthnCredentialEntity, WebAuthnCredentialEntity};
+
+#[async_trait]
+#[automock]
+pub trait WebAuthnCredentialRepository {
+    async fn create(&self, new_credential: NewWebAuthnCredentialEntity) -> Result<WebAuthnCredentialEntity>;
+    async fn list_for_brawler(&self, brawler_id: i32) -> Result<Vec<WebAuthnCredentialEntity>>;
+    async fn find_by_credential_id(&self, credential_id: String) -> Result<Option<WebAuthnCredentialEntity>>;
+    async fn record_use(&self, id: i32, sign_count: i64) -> Result<()>;
+    async fn rename(&self, brawler_id: i32, id: i32, name: String) -> Result<WebAuthnCredentialEntity>;
+    async fn remove(&self, brawler_id: i32, id: i32) -> Result<()>;
+    // Returns false when the challenge has already been answered.
+    async fn consume_challenge(&self, challenge: String, expires_at: NaiveDateTime) -> Result<bool>;
+}
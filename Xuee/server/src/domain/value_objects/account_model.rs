@@ -0,0 +1,97 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{
+    QueryableByName,
+    sql_types::{Int4, Nullable, Text, Timestamp, Varchar, Date},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::{battles::BattleEntity, brawlers::BrawlerEntity};
+use crate::domain::value_objects::mission_model::MissionModel;
+
+// Everything we hold about a brawler, served as a JSON download from GET /account/export.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountExportModel {
+    pub exported_at: NaiveDateTime,
+    pub profile: ExportedProfile,
+    pub missions_led: Vec<MissionModel>,
+    pub crew_memberships: Vec<ExportedCrewMembership>,
+    pub cards: Vec<ExportedUserCard>,
+    pub battles: Vec<BattleEntity>,
+    pub daily_fortunes: Vec<ExportedDailyFortune>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedProfile {
+    pub id: i32,
+    pub username: String,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+    pub role: String,
+    pub email_verified_at: Option<NaiveDateTime>,
+    pub deletion_scheduled_for: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<BrawlerEntity> for ExportedProfile {
+    fn from(entity: BrawlerEntity) -> Self {
+        Self {
+            id: entity.id,
+            username: entity.username,
+            display_name: entity.display_name,
+            avatar_url: entity.avatar_url,
+            role: entity.role,
+            email_verified_at: entity.email_verified_at,
+            deletion_scheduled_for: entity.deletion_scheduled_for,
+            created_at: entity.created_at,
+            updated_at: entity.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct ExportedCrewMembership {
+    #[diesel(sql_type = Int4)]
+    pub mission_id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub mission_name: String,
+    #[diesel(sql_type = Varchar)]
+    pub mission_status: String,
+    #[diesel(sql_type = Timestamp)]
+    pub joined_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct ExportedUserCard {
+    #[diesel(sql_type = Int4)]
+    pub id: i32,
+    #[diesel(sql_type = Int4)]
+    pub card_id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub card_name: String,
+    #[diesel(sql_type = Int4)]
+    pub level: i32,
+    #[diesel(sql_type = Int4)]
+    pub experience: i32,
+    #[diesel(sql_type = Timestamp)]
+    pub obtained_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct ExportedDailyFortune {
+    #[diesel(sql_type = Date)]
+    pub date: NaiveDate,
+    #[diesel(sql_type = Int4)]
+    pub stick_number: i32,
+    #[diesel(sql_type = Text)]
+    pub poem_text: String,
+    #[diesel(sql_type = Text)]
+    pub interpretation: String,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    pub lucky_direction: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountDeletionModel {
+    pub deletion_scheduled_for: Option<NaiveDateTime>,
+}
@@ -0,0 +1,95 @@
+use std::fmt::Display;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::api_tokens::ApiTokenEntity;
+
+// Scopes a personal access token may carry. Routes that declare no scope never accept PATs.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Scope {
+    #[serde(rename = "missions:read")]
+    MissionsRead,
+    #[serde(rename = "missions:write")]
+    MissionsWrite,
+    #[serde(rename = "cards:read")]
+    CardsRead,
+    #[serde(rename = "cards:write")]
+    CardsWrite,
+    #[serde(rename = "social:read")]
+    SocialRead,
+    #[serde(rename = "social:write")]
+    SocialWrite,
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scope::MissionsRead => write!(f, "missions:read"),
+            Scope::MissionsWrite => write!(f, "missions:write"),
+            Scope::CardsRead => write!(f, "cards:read"),
+            Scope::CardsWrite => write!(f, "cards:write"),
+            Scope::SocialRead => write!(f, "social:read"),
+            Scope::SocialWrite => write!(f, "social:write"),
+            Scope::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Scope {
+    type Error = anyhow::Error;
+
+    fn try_from(scope: &str) -> Result<Self, Self::Error> {
+        match scope {
+            "missions:read" => Ok(Self::MissionsRead),
+            "missions:write" => Ok(Self::MissionsWrite),
+            "cards:read" => Ok(Self::CardsRead),
+            "cards:write" => Ok(Self::CardsWrite),
+            "social:read" => Ok(Self::SocialRead),
+            "social:write" => Ok(Self::SocialWrite),
+            "admin" => Ok(Self::Admin),
+            _ => Err(anyhow::anyhow!("Invalid scope")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateApiTokenModel {
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiTokenModel {
+    pub id: i32,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<ApiTokenEntity> for ApiTokenModel {
+    fn from(entity: ApiTokenEntity) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name,
+            token_prefix: entity.token_prefix,
+            scopes: entity.scopes,
+            expires_at: entity.expires_at,
+            last_used_at: entity.last_used_at,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+// The plain token is only ever returned here, at creation time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedApiTokenModel {
+    pub token: String,
+    #[serde(flatten)]
+    pub details: ApiTokenModel,
+}
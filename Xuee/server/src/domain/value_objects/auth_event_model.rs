@@ -0,0 +1,165 @@
+use std::fmt::Display;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::auth_events::AuthEventEntity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthEventKind {
+    Login,
+    TwoFactor,
+    OidcLogin,
+    DevLogin,
+    Logout,
+    SessionRevoked,
+    AccountLocked,
+    PasswordResetRequested,
+    PasswordReset,
+    MagicLinkRequested,
+    MagicLinkLogin,
+    PasskeyLogin,
+    IdentityLinked,
+    IdentityUnlinked,
+}
+
+impl Display for AuthEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthEventKind::Login => write!(f, "login"),
+            AuthEventKind::TwoFactor => write!(f, "two_factor"),
+            AuthEventKind::OidcLogin => write!(f, "oidc_login"),
+            AuthEventKind::DevLogin => write!(f, "dev_login"),
+            AuthEventKind::Logout => write!(f, "logout"),
+            AuthEventKind::SessionRevoked => write!(f, "session_revoked"),
+            AuthEventKind::AccountLocked => write!(f, "account_locked"),
+            AuthEventKind::PasswordResetRequested => write!(f, "password_reset_requested"),
+            AuthEventKind::PasswordReset => write!(f, "password_reset"),
+            AuthEventKind::MagicLinkRequested => write!(f, "magic_link_requested"),
+            AuthEventKind::MagicLinkLogin => write!(f, "magic_link_login"),
+            AuthEventKind::PasskeyLogin => write!(f, "passkey_login"),
+            AuthEventKind::IdentityLinked => write!(f, "identity_linked"),
+            AuthEventKind::IdentityUnlinked => write!(f, "identity_unlinked"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthEventOutcome {
+    Success,
+    Failure,
+}
+
+impl Display for AuthEventOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthEventOutcome::Success => write!(f, "success"),
+            AuthEventOutcome::Failure => write!(f, "failure"),
+        }
+    }
+}
+
+// Where a request came from, as far as the server can tell. Recorded with every auth event.
+#[derive(Debug, Clone, Default)]
+pub struct ClientContext {
+    pub ip_address: String,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthEvent {
+    pub kind: AuthEventKind,
+    pub outcome: AuthEventOutcome,
+    pub brawler_id: Option<i32>,
+    pub username: Option<String>,
+    pub provider: Option<String>,
+    pub reason: Option<String>,
+}
+
+impl AuthEvent {
+    pub fn success(kind: AuthEventKind) -> Self {
+        Self {
+            kind,
+            outcome: AuthEventOutcome::Success,
+            brawler_id: None,
+            username: None,
+            provider: None,
+            reason: None,
+        }
+    }
+
+    pub fn failure(kind: AuthEventKind, reason: impl Into<String>) -> Self {
+        Self {
+            outcome: AuthEventOutcome::Failure,
+            reason: Some(reason.into()),
+            ..Self::success(kind)
+        }
+    }
+
+    pub fn brawler(mut self, brawler_id: i32) -> Self {
+        self.brawler_id = Some(brawler_id);
+        self
+    }
+
+    pub fn username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthEventModel {
+    pub id: i32,
+    pub brawler_id: Option<i32>,
+    pub username: Option<String>,
+    pub event_type: String,
+    pub outcome: String,
+    pub reason: Option<String>,
+    pub provider: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<AuthEventEntity> for AuthEventModel {
+    fn from(entity: AuthEventEntity) -> Self {
+        Self {
+            id: entity.id,
+            brawler_id: entity.brawler_id,
+            username: entity.username,
+            event_type: entity.event_type,
+            outcome: entity.outcome,
+            reason: entity.reason,
+            provider: entity.provider,
+            ip_address: entity.ip_address,
+            user_agent: entity.user_agent,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AuthEventQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct AuthEventFilter {
+    pub brawler_id: Option<i32>,
+    pub username: Option<String>,
+    pub event_type: Option<String>,
+    pub outcome: Option<String>,
+    pub ip_address: Option<String>,
+    pub since: Option<NaiveDateTime>,
+    pub until: Option<NaiveDateTime>,
+    pub limit: Option<i64>,
+}
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use data_encoding::BASE64;
+
+// Decoded bytes of a base64 upload. Accepts a bare payload or a `data:image/...;base64,` URI.
+#[derive(Debug, Clone)]
+pub struct Base64Image(Vec<u8>);
+
+impl Base64Image {
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub fn new(data: &str, max_bytes: usize) -> Result<Self> {
+        let data = data.trim();
+        if data.is_empty() {
+            return Err(anyhow::anyhow!("Image data cannot be empty"));
+        }
+
+        let payload = match data.split_once(',') {
+            Some((header, payload)) if header.starts_with("data:") && header.ends_with(";base64") => payload,
+            Some(_) => return Err(anyhow::anyhow!("Image must be a base64 data URI")),
+            None => data,
+        };
+
+        // Rough pre-check so an oversized payload is rejected before it is decoded
+        if payload.len() / 4 * 3 > max_bytes + 3 {
+            return Err(anyhow::anyhow!("Image must be at most {} KiB", max_bytes / 1024));
+        }
+
+        let bytes = BASE64
+            .decode(payload.as_bytes())
+            .map_err(|_| anyhow::anyhow!("Image data is not valid base64"))?;
+        if bytes.len() > max_bytes {
+            return Err(anyhow::anyhow!("Image must be at most {} KiB", max_bytes / 1024));
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_a_bare_payload() {
+        let image = Base64Image::new(&BASE64.encode(b"png bytes"), 1024).unwrap();
+        assert_eq!(image.into_inner(), b"png bytes");
+    }
+
+    #[test]
+    fn decodes_a_data_uri() {
+        let data = format!("data:image/png;base64,{}", BASE64.encode(b"png bytes"));
+        assert_eq!(Base64Image::new(&data, 1024).unwrap().into_inner(), b"png bytes");
+    }
+
+    #[test]
+    fn rejects_empty_data() {
+        assert!(Base64Image::new("  ", 1024).is_err());
+    }
+
+    #[test]
+    fn rejects_data_uris_that_are_not_base64() {
+        assert!(Base64Image::new("data:image/svg+xml,<svg/>", 1024).is_err());
+        assert!(Base64Image::new("data:image/png;base64,***", 1024).is_err());
+    }
+
+    #[test]
+    fn rejects_payloads_over_the_limit() {
+        // Caught by the pre-check, before decoding
+        assert!(Base64Image::new(&BASE64.encode(&[0u8; 2048]), 1024).is_err());
+        // Within the pre-check slack, caught after decoding
+        assert!(Base64Image::new(&BASE64.encode(&[0u8; 1026]), 1024).is_err());
+        assert!(Base64Image::new(&BASE64.encode(&[0u8; 1024]), 1024).is_ok());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::domain::entities::brawlers::RegisterBrawlerEntity;
 use serde::{Deserialize, Serialize};
-use diesel::{QueryableByName, sql_types::{Int4, Varchar}};
+use chrono::NaiveDateTime;
+use diesel::{QueryableByName, sql_types::{BigInt, Int4, Nullable, Timestamp, Varchar}};
 
 #[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
 pub struct BrawlerModel {
@@ -12,6 +13,93 @@ pub struct BrawlerModel {
     pub display_name: String,
 }
 
+// What anyone can see about a brawler. Every count is computed on read, so nothing can drift.
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct BrawlerProfileModel {
+    #[diesel(sql_type = Int4)]
+    pub id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub display_name: String,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    pub avatar_url: Option<String>,
+    #[diesel(sql_type = Timestamp)]
+    pub joined_at: NaiveDateTime,
+    #[diesel(sql_type = BigInt)]
+    pub missions_led: i64,
+    #[diesel(sql_type = BigInt)]
+    pub missions_joined: i64,
+    #[diesel(sql_type = BigInt)]
+    pub mission_success_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub mission_failure_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub battles_won: i64,
+    #[diesel(sql_type = BigInt)]
+    pub battles_lost: i64,
+    #[diesel(sql_type = BigInt)]
+    pub battles_drawn: i64,
+    #[diesel(sql_type = BigInt)]
+    pub cards_owned: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectorySort {
+    #[default]
+    Activity,
+    CardPower,
+    MissionsCompleted,
+}
+
+impl DirectorySort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DirectorySort::Activity => "activity",
+            DirectorySort::CardPower => "card_power",
+            DirectorySort::MissionsCompleted => "missions_completed",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DirectoryQuery {
+    pub q: Option<String>,
+    #[serde(default)]
+    pub sort: DirectorySort,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+// The directory's public card for a brawler. Usernames are sign-in emails, so they are never returned.
+// `activity` counts missions led or joined and battles fought over the last 30 days.
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct DirectoryBrawlerModel {
+    #[diesel(sql_type = Int4)]
+    pub id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub display_name: String,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    pub avatar_url: Option<String>,
+    #[diesel(sql_type = Timestamp)]
+    pub joined_at: NaiveDateTime,
+    #[diesel(sql_type = BigInt)]
+    pub activity: i64,
+    #[diesel(sql_type = BigInt)]
+    pub card_power: i64,
+    #[diesel(sql_type = BigInt)]
+    pub missions_completed: i64,
+}
+
+impl DirectoryBrawlerModel {
+    pub fn sort_value(&self, sort: DirectorySort) -> i64 {
+        match sort {
+            DirectorySort::Activity => self.activity,
+            DirectorySort::CardPower => self.card_power,
+            DirectorySort::MissionsCompleted => self.missions_completed,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegisterBrawlerModel {
     pub username: String,
@@ -47,9 +135,47 @@ pub struct AvatarUploadRequest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AvatarUploadResponse {
     pub url: String,
+    pub variants: Vec<AvatarVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvatarVariant {
+    pub size: u32,
+    pub url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateDisplayNameRequest {
     pub display_name: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeUsernameRequest {
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeUsernameResponse {
+    pub username: String,
+    pub previous_username: String,
+    pub redirect_expires_at: NaiveDateTime,
+    pub next_change_available_at: NaiveDateTime,
+}
+
+// What a username lookup found: the brawler itself, or a former name that now points elsewhere.
+#[derive(Debug, Clone)]
+pub enum UsernameLookup {
+    Profile(BrawlerProfileModel),
+    Redirect(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
@@ -0,0 +1,37 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::brawler_identities::BrawlerIdentityEntity;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrawlerIdentityModel {
+    pub provider: String,
+    pub email: Option<String>,
+    pub linked_at: NaiveDateTime,
+}
+
+impl From<BrawlerIdentityEntity> for BrawlerIdentityModel {
+    fn from(entity: BrawlerIdentityEntity) -> Self {
+        Self {
+            provider: entity.provider,
+            email: entity.email,
+            linked_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcCallbackModel {
+    pub code: String,
+    pub state: String,
+}
+
+// What the browser carries between the authorization redirect and the callback, in a signed cookie.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthFlow {
+    pub provider: String,
+    pub state: String,
+    pub pkce_verifier: String,
+    #[serde(default)]
+    pub nonce: Option<String>,
+}
@@ -1,9 +1,289 @@
+use anyhow::Result;
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::value_objects::mission_statuses::MissionStatuses;
+use crate::domain::value_objects::{
+    mission_model::MissionModel,
+    mission_statuses::MissionStatuses,
+    pagination::{self, Cursor, InvalidCursor, Page},
+};
+
+// Timestamps in cursors use the same text form Postgres accepts on the way back in.
+const CURSOR_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+// How /missions lists when the caller does not choose: newest first.
+pub const DEFAULT_MISSION_ORDERING: (MissionSort, SortOrder) = (MissionSort::CreatedAt, SortOrder::Desc);
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct MissionFilter {
     pub name: Option<String>,
     pub status: Option<MissionStatuses>,
+    pub sort: Option<MissionSort>,
+    pub order: Option<SortOrder>,
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    // Set by the /missions/friends handler from the caller's session, never from the query string
+    #[serde(skip)]
+    pub friends_of: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MissionSort {
+    CreatedAt,
+    MissionDate,
+    CrewCount,
+    Name,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl MissionSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MissionSort::CreatedAt => "created_at",
+            MissionSort::MissionDate => "mission_date",
+            MissionSort::CrewCount => "crew_count",
+            MissionSort::Name => "name",
+        }
+    }
+
+    // Newest and busiest first; dates and names read naturally from the start
+    pub fn default_order(&self) -> SortOrder {
+        match self {
+            MissionSort::CreatedAt | MissionSort::CrewCount => SortOrder::Desc,
+            MissionSort::MissionDate | MissionSort::Name => SortOrder::Asc,
+        }
+    }
+
+    // Undated missions always sort last, so they stand in as +/- infinity depending on direction.
+    pub fn cursor_value(&self, mission: &MissionModel, order: SortOrder) -> String {
+        match self {
+            MissionSort::CreatedAt => mission.created_at.format(CURSOR_TIMESTAMP_FORMAT).to_string(),
+            MissionSort::MissionDate => match (mission.mission_date, order) {
+                (Some(date), _) => date.format(CURSOR_TIMESTAMP_FORMAT).to_string(),
+                (None, SortOrder::Asc) => "infinity".to_string(),
+                (None, SortOrder::Desc) => "-infinity".to_string(),
+            },
+            MissionSort::CrewCount => mission.crew_count.to_string(),
+            MissionSort::Name => mission.name.clone(),
+        }
+    }
+
+    fn is_valid_cursor_value(&self, value: &str) -> bool {
+        let is_timestamp = || NaiveDateTime::parse_from_str(value, CURSOR_TIMESTAMP_FORMAT).is_ok();
+        match self {
+            MissionSort::CreatedAt => is_timestamp(),
+            MissionSort::MissionDate => value == "infinity" || value == "-infinity" || is_timestamp(),
+            MissionSort::CrewCount => value.parse::<i64>().is_ok(),
+            MissionSort::Name => true,
+        }
+    }
+}
+
+impl SortOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+impl MissionFilter {
+    // An explicit `sort` brings its own natural order unless `order` overrides it.
+    pub fn ordering(&self, default: (MissionSort, SortOrder)) -> (MissionSort, SortOrder) {
+        match (self.sort, self.order) {
+            (Some(sort), Some(order)) => (sort, order),
+            (Some(sort), None) => (sort, sort.default_order()),
+            (None, Some(order)) => (default.0, order),
+            (None, None) => default,
+        }
+    }
+
+    pub fn page_size(&self) -> i64 {
+        pagination::page_size(self.limit)
+    }
+
+    // Rejects cursors that are malformed or were issued for a different sort or direction.
+    pub fn after(&self, ordering: (MissionSort, SortOrder)) -> Result<Option<Cursor>> {
+        let Some(raw) = self.cursor.as_deref() else {
+            return Ok(None);
+        };
+
+        let cursor = Cursor::decode(raw, &cursor_tag(ordering))?;
+        if !ordering.0.is_valid_cursor_value(&cursor.value) {
+            return Err(InvalidCursor.into());
+        }
+
+        Ok(Some(cursor))
+    }
+
+    // `rows` holds up to page_size() + 1 missions in the given ordering.
+    pub fn into_page(&self, rows: Vec<MissionModel>, total_count: i64, ordering: (MissionSort, SortOrder)) -> Page<MissionModel> {
+        let (sort, order) = ordering;
+        let tag = cursor_tag(ordering);
+
+        pagination::into_page(rows, self.page_size(), |mission| {
+            Cursor::new(tag.as_str(), sort.cursor_value(mission, order), mission.id)
+        })
+        .with_total(total_count)
+    }
+}
+
+fn cursor_tag((sort, order): (MissionSort, SortOrder)) -> String {
+    format!("{}.{}", sort.as_str(), order.as_str())
+}
+
+// Sub-views of /brawlers/me/missions. Undated missions count as upcoming until they finish.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MyMissionsView {
+    #[default]
+    All,
+    Leading,
+    Joined,
+    Upcoming,
+    Past,
+}
+
+impl MyMissionsView {
+    // Upcoming reads soonest first and past reads most recent first, unless the caller sorts.
+    pub fn default_ordering(&self) -> (MissionSort, SortOrder) {
+        match self {
+            MyMissionsView::Upcoming => (MissionSort::MissionDate, SortOrder::Asc),
+            MyMissionsView::Past => (MissionSort::MissionDate, SortOrder::Desc),
+            _ => (MissionSort::CreatedAt, SortOrder::Desc),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_micro_opt(hour, 30, 15, 250).unwrap()
+    }
+
+    fn mission(id: i32, crew_count: i64, mission_date: Option<NaiveDateTime>) -> MissionModel {
+        MissionModel {
+            id,
+            name: format!("Mission {}", id),
+            description: None,
+            status: MissionStatuses::Open.to_string(),
+            chief_id: 1,
+            chief_display_name: "Robin".to_string(),
+            crew_count,
+            mission_date,
+            time: None,
+            email: None,
+            phone: None,
+            location: None,
+            rewards: None,
+            created_at: at(id as u32),
+            updated_at: at(id as u32),
+        }
+    }
+
+    fn filter(cursor: Option<String>, limit: i64) -> MissionFilter {
+        MissionFilter {
+            cursor,
+            limit: Some(limit),
+            ..MissionFilter::default()
+        }
+    }
+
+    // Pages through `rows` with the given ordering and returns the cursor for the next page.
+    fn next_cursor(rows: Vec<MissionModel>, limit: i64, ordering: (MissionSort, SortOrder)) -> String {
+        let page = filter(None, limit).into_page(rows, 0, ordering);
+        page.next_cursor.expect("another page")
+    }
+
+    fn is_invalid_cursor(result: Result<Option<Cursor>>) -> bool {
+        result.is_err_and(|e| e.is::<InvalidCursor>())
+    }
+
+    #[test]
+    fn an_explicit_sort_brings_its_natural_order() {
+        let mut filter = MissionFilter::default();
+        assert_eq!(filter.ordering(DEFAULT_MISSION_ORDERING), (MissionSort::CreatedAt, SortOrder::Desc));
+
+        filter.sort = Some(MissionSort::Name);
+        assert_eq!(filter.ordering(DEFAULT_MISSION_ORDERING), (MissionSort::Name, SortOrder::Asc));
+
+        filter.order = Some(SortOrder::Desc);
+        assert_eq!(filter.ordering(DEFAULT_MISSION_ORDERING), (MissionSort::Name, SortOrder::Desc));
+    }
+
+    #[test]
+    fn timestamp_cursors_round_trip() {
+        let ordering = (MissionSort::CreatedAt, SortOrder::Desc);
+        let cursor = next_cursor(vec![mission(5, 0, None), mission(4, 0, None)], 1, ordering);
+
+        let after = filter(Some(cursor), 1).after(ordering).unwrap().unwrap();
+        assert_eq!(after.id, 5);
+        assert_eq!(after.value, "2026-10-18T05:30:15.000250");
+    }
+
+    #[test]
+    fn undated_missions_resume_at_the_matching_end() {
+        let rows = || vec![mission(1, 0, None), mission(2, 0, None)];
+
+        for (order, value) in [(SortOrder::Asc, "infinity"), (SortOrder::Desc, "-infinity")] {
+            let ordering = (MissionSort::MissionDate, order);
+            let cursor = next_cursor(rows(), 1, ordering);
+
+            assert_eq!(filter(Some(cursor), 1).after(ordering).unwrap().unwrap().value, value);
+        }
+    }
+
+    #[test]
+    fn ties_on_the_sort_key_resume_after_the_last_id() {
+        let ordering = (MissionSort::CrewCount, SortOrder::Desc);
+        let cursor = next_cursor(vec![mission(9, 3, None), mission(4, 3, None), mission(2, 3, None)], 2, ordering);
+
+        let after = filter(Some(cursor), 2).after(ordering).unwrap().unwrap();
+        assert_eq!((after.value.as_str(), after.id), ("3", 4));
+    }
+
+    #[test]
+    fn rejects_a_cursor_from_another_sort_or_direction() {
+        let cursor = next_cursor(vec![mission(2, 1, None), mission(1, 1, None)], 1, (MissionSort::CrewCount, SortOrder::Desc));
+
+        assert!(is_invalid_cursor(filter(Some(cursor.clone()), 1).after((MissionSort::CrewCount, SortOrder::Asc))));
+        assert!(is_invalid_cursor(filter(Some(cursor), 1).after((MissionSort::Name, SortOrder::Desc))));
+    }
+
+    #[test]
+    fn rejects_malformed_or_tampered_cursors() {
+        let ordering = (MissionSort::CrewCount, SortOrder::Desc);
+
+        assert!(is_invalid_cursor(filter(Some("%%%".to_string()), 1).after(ordering)));
+        // Well-formed, but the value could not have come from this sort
+        let tampered = Cursor::new("crew_count.desc", "1; DROP TABLE missions", 3).encode();
+        assert!(is_invalid_cursor(filter(Some(tampered), 1).after(ordering)));
+        let tampered = Cursor::new("created_at.desc", "yesterday", 3).encode();
+        assert!(is_invalid_cursor(filter(Some(tampered), 1).after(DEFAULT_MISSION_ORDERING)));
+    }
+
+    #[test]
+    fn no_cursor_starts_at_the_beginning() {
+        assert_eq!(filter(None, 1).after(DEFAULT_MISSION_ORDERING).unwrap(), None);
+    }
+
+    #[test]
+    fn pages_report_the_total_and_stop_at_the_end() {
+        let page = filter(None, 2).into_page(vec![mission(2, 0, None), mission(1, 0, None)], 2, DEFAULT_MISSION_ORDERING);
+
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.next_cursor, None);
+        assert_eq!(page.total_count, Some(2));
+    }
 }
@@ -1,4 +1,15 @@
 pub mod brawler_model;
+pub mod base64_image;
 pub mod mission_filter;
 pub mod mission_model;
 pub mod mission_statuses;
+pub mod roles;
+pub mod api_token_model;
+pub mod auth_event_model;
+pub mod session_model;
+pub mod account_model;
+pub mod webauthn_model;
+pub mod two_factor_model;
+pub mod identity_model;
+pub mod social_model;
+pub mod pagination;
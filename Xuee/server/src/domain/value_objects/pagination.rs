@@ -0,0 +1,166 @@
+use std::fmt;
+use anyhow::Result;
+use data_encoding::BASE64URL_NOPAD;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+pub const MAX_PAGE_SIZE: i64 = 50;
+
+// Returned when a cursor is malformed or was issued for a different ordering; routers map it to 400.
+#[derive(Debug)]
+pub struct InvalidCursor;
+
+impl fmt::Display for InvalidCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid or stale pagination cursor")
+    }
+}
+
+impl std::error::Error for InvalidCursor {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    // Matches across all pages; only listings that can afford the extra COUNT fill it in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i64>,
+}
+
+impl<T> Page<T> {
+    pub fn with_total(mut self, total_count: i64) -> Self {
+        self.total_count = Some(total_count);
+        self
+    }
+}
+
+// Keyset position: the last row's sort value plus its id as a tie-breaker. Rows added between
+// requests never shift a page the way OFFSET does. Clients treat the encoded form as opaque.
+// `sort` names the ordering the cursor came from, so it cannot be replayed against another one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub sort: String,
+    pub value: String,
+    pub id: i32,
+}
+
+impl Cursor {
+    pub fn new(sort: impl Into<String>, value: impl Into<String>, id: i32) -> Self {
+        Self { sort: sort.into(), value: value.into(), id }
+    }
+
+    pub fn encode(&self) -> String {
+        BASE64URL_NOPAD.encode(format!("{}:{}:{}", self.sort, self.value, self.id).as_bytes())
+    }
+
+    // The value sits in the middle and may itself contain ':' (timestamps do)
+    pub fn decode(raw: &str, sort: &str) -> Result<Self> {
+        let bytes = BASE64URL_NOPAD.decode(raw.as_bytes()).map_err(|_| InvalidCursor)?;
+        let text = String::from_utf8(bytes).map_err(|_| InvalidCursor)?;
+
+        let (tag, rest) = text.split_once(':').ok_or(InvalidCursor)?;
+        let (value, id) = rest.rsplit_once(':').ok_or(InvalidCursor)?;
+        if tag != sort {
+            return Err(InvalidCursor.into());
+        }
+        let id = id.parse().map_err(|_| InvalidCursor)?;
+
+        Ok(Self::new(sort, value, id))
+    }
+}
+
+pub fn page_size(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
+
+// Callers fetch one row past the page size; its presence is what proves there is a next page.
+pub fn into_page<T>(mut rows: Vec<T>, size: i64, cursor_for: impl Fn(&T) -> Cursor) -> Page<T> {
+    let has_more = rows.len() as i64 > size;
+    rows.truncate(size as usize);
+
+    let next_cursor = if has_more { rows.last().map(|row| cursor_for(row).encode()) } else { None };
+
+    Page { items: rows, next_cursor, total_count: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_invalid_cursor(result: Result<Cursor>) -> bool {
+        result.is_err_and(|e| e.is::<InvalidCursor>())
+    }
+
+    #[test]
+    fn cursors_round_trip() {
+        let cursor = Cursor::new("activity", "42", 7);
+
+        assert_eq!(Cursor::decode(&cursor.encode(), "activity").unwrap(), cursor);
+    }
+
+    #[test]
+    fn values_may_contain_the_separator() {
+        let cursor = Cursor::new("created_at.desc", "2026-10-18T12:30:45.000000", 3);
+
+        assert_eq!(Cursor::decode(&cursor.encode(), "created_at.desc").unwrap(), cursor);
+    }
+
+    #[test]
+    fn rejects_a_cursor_from_another_sort() {
+        let cursor = Cursor::new("card_power", "42", 7).encode();
+
+        assert!(is_invalid_cursor(Cursor::decode(&cursor, "activity")));
+    }
+
+    #[test]
+    fn rejects_malformed_cursors() {
+        let encode = |text: &str| BASE64URL_NOPAD.encode(text.as_bytes());
+
+        for raw in [
+            "not base64!".to_string(),
+            BASE64URL_NOPAD.encode(&[0xff, 0xfe, 0x3a]),
+            encode("activity"),
+            encode("activity:42"),
+            encode("activity:42:seven"),
+            String::new(),
+        ] {
+            assert!(is_invalid_cursor(Cursor::decode(&raw, "activity")), "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn rows_tied_on_the_sort_value_get_distinct_cursors() {
+        let first = Cursor::new("activity", "42", 7).encode();
+        let second = Cursor::new("activity", "42", 8).encode();
+
+        assert_ne!(first, second);
+        assert_eq!(Cursor::decode(&second, "activity").unwrap().id, 8);
+    }
+
+    #[test]
+    fn the_extra_row_proves_a_next_page_and_is_dropped() {
+        let page = into_page(vec![(42, 1), (42, 2), (41, 3)], 2, |&(value, id)| Cursor::new("activity", value.to_string(), id));
+
+        assert_eq!(page.items, vec![(42, 1), (42, 2)]);
+        // The next page starts after the last row shown, not the one fetched to look ahead
+        let next = Cursor::decode(page.next_cursor.as_deref().unwrap(), "activity").unwrap();
+        assert_eq!(next, Cursor::new("activity", "42", 2));
+    }
+
+    #[test]
+    fn a_short_or_exact_page_is_the_last_one() {
+        let cursor_for = |&id: &i32| Cursor::new("activity", "0", id);
+
+        assert_eq!(into_page(vec![1, 2], 2, cursor_for).next_cursor, None);
+        assert_eq!(into_page(vec![1], 2, cursor_for).next_cursor, None);
+        assert_eq!(into_page(Vec::new(), 2, cursor_for).next_cursor, None);
+    }
+
+    #[test]
+    fn page_size_is_clamped() {
+        assert_eq!(page_size(None), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size(Some(0)), 1);
+        assert_eq!(page_size(Some(-5)), 1);
+        assert_eq!(page_size(Some(1000)), MAX_PAGE_SIZE);
+    }
+}
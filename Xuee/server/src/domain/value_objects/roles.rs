@@ -0,0 +1,40 @@
+use std::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+// Ordered from least to most privileged; each role holds every permission of the roles below it,
+// so `role >= Role::Moderator` reads as "may moderate".
+#[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Role {
+    #[default]
+    Brawler,
+    Moderator,
+    Admin,
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Role::Brawler => write!(f, "Brawler"),
+            Role::Moderator => write!(f, "Moderator"),
+            Role::Admin => write!(f, "Admin"),
+        }
+    }
+}
+
+impl TryFrom<&str> for Role {
+    type Error = anyhow::Error;
+
+    fn try_from(role: &str) -> Result<Self, Self::Error> {
+        match role {
+            "Brawler" => Ok(Self::Brawler),
+            "Moderator" => Ok(Self::Moderator),
+            "Admin" => Ok(Self::Admin),
+            _ => Err(anyhow::anyhow!("Invalid role")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRoleRequest {
+    pub role: Role,
+}
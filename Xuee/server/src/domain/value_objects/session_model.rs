@@ -0,0 +1,75 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::sessions::SessionEntity;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionModel {
+    pub id: i32,
+    pub device: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub last_seen_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub current: bool,
+}
+
+impl SessionModel {
+    pub fn from_entity(entity: SessionEntity, current_session_id: i32) -> Self {
+        Self {
+            id: entity.id,
+            device: describe_device(entity.user_agent.as_deref()),
+            current: entity.id == current_session_id,
+            user_agent: entity.user_agent,
+            ip_address: entity.ip_address,
+            created_at: entity.created_at,
+            last_seen_at: entity.last_seen_at,
+            expires_at: entity.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokedSessionsModel {
+    pub revoked: usize,
+}
+
+// A rough "Firefox on Windows" label for the session list; the raw user agent is returned too.
+pub fn describe_device(user_agent: Option<&str>) -> String {
+    let Some(user_agent) = user_agent.filter(|user_agent| !user_agent.trim().is_empty()) else {
+        return "Unknown device".to_string();
+    };
+
+    // Order matters: Edge and Opera also claim Chrome, and Chrome also claims Safari.
+    let browser = [
+        ("Edg/", "Edge"),
+        ("OPR/", "Opera"),
+        ("Firefox/", "Firefox"),
+        ("Chrome/", "Chrome"),
+        ("Safari/", "Safari"),
+        ("curl/", "curl"),
+    ]
+    .iter()
+    .find(|(marker, _)| user_agent.contains(marker))
+    .map(|(_, name)| *name);
+
+    let platform = [
+        ("iPhone", "iOS"),
+        ("iPad", "iPadOS"),
+        ("Android", "Android"),
+        ("Windows", "Windows"),
+        ("Mac OS X", "macOS"),
+        ("CrOS", "ChromeOS"),
+        ("Linux", "Linux"),
+    ]
+    .iter()
+    .find(|(marker, _)| user_agent.contains(marker))
+    .map(|(_, name)| *name);
+
+    match (browser, platform) {
+        (Some(browser), Some(platform)) => format!("{} on {}", browser, platform),
+        (Some(name), None) | (None, Some(name)) => name.to_string(),
+        (None, None) => "Unknown device".to_string(),
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Display;
+use chrono::NaiveDateTime;
+use diesel::{QueryableByName, sql_types::{Int4, Nullable, Timestamp, Varchar}};
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum FriendRequestStatus {
+    #[default]
+    Pending,
+    Accepted,
+    Declined,
+}
+
+impl Display for FriendRequestStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FriendRequestStatus::Pending => write!(f, "Pending"),
+            FriendRequestStatus::Accepted => write!(f, "Accepted"),
+            FriendRequestStatus::Declined => write!(f, "Declined"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FriendRequestDirection {
+    #[default]
+    Incoming,
+    Outgoing,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct FriendRequestQuery {
+    #[serde(default)]
+    pub direction: FriendRequestDirection,
+}
+
+// A brawler as seen from someone's follower, following or friend list. `since` is when the edge was made.
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct SocialBrawlerModel {
+    #[diesel(sql_type = Int4)]
+    pub id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub display_name: String,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    pub avatar_url: Option<String>,
+    #[diesel(sql_type = Timestamp)]
+    pub since: NaiveDateTime,
+}
+
+// A pending request. `brawler_*` is the other party: the sender if incoming, the recipient if outgoing.
+#[derive(Debug, Clone, Serialize, Deserialize, QueryableByName)]
+pub struct FriendRequestModel {
+    #[diesel(sql_type = Int4)]
+    pub id: i32,
+    #[diesel(sql_type = Int4)]
+    pub brawler_id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub display_name: String,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    pub avatar_url: Option<String>,
+    #[diesel(sql_type = Timestamp)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendFriendRequestModel {
+    pub brawler_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendFriendRequestResponse {
+    pub id: i32,
+    pub status: FriendRequestStatus,
+}
@@ -0,0 +1,176 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::entities::webauthn_credentials::WebAuthnCredentialEntity;
+
+// Binary fields are base64url without padding, as produced by the browser helpers
+// (`PublicKeyCredential.parseCreationOptionsFromJSON` / `toJSON()`).
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserEntity {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialParameter {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub alg: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    pub resident_key: String,
+    pub user_verification: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKeyCredentialCreationOptions {
+    pub challenge: String,
+    pub rp: RelyingParty,
+    pub user: UserEntity,
+    pub pub_key_cred_params: Vec<CredentialParameter>,
+    pub timeout: u64,
+    pub attestation: String,
+    pub authenticator_selection: AuthenticatorSelection,
+    pub exclude_credentials: Vec<CredentialDescriptor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKeyCredentialRequestOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    pub timeout: u64,
+    pub user_verification: String,
+    pub allow_credentials: Vec<CredentialDescriptor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub attestation_object: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationCredential {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub response: AttestationResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    pub user_handle: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthenticationCredential {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub response: AssertionResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisterPasskeyRequest {
+    pub name: Option<String>,
+    pub credential: RegistrationCredential,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PasskeyLoginOptionsRequest {
+    pub username: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasskeyLoginRequest {
+    pub credential: AuthenticationCredential,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenamePasskeyRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasskeyModel {
+    pub id: i32,
+    pub name: String,
+    pub aaguid: String,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<WebAuthnCredentialEntity> for PasskeyModel {
+    fn from(entity: WebAuthnCredentialEntity) -> Self {
+        Self {
+            id: entity.id,
+            name: entity.name,
+            aaguid: entity.aaguid,
+            last_used_at: entity.last_used_at,
+            created_at: entity.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CeremonyKind {
+    Registration,
+    Authentication,
+}
+
+// Carried between the options and finish calls in a signed cookie, like `OAuthFlow`.
+// The cookie alone cannot stop a replay, so finishing also records the challenge server-side.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebAuthnCeremony {
+    pub kind: CeremonyKind,
+    pub challenge: String,
+    pub brawler_id: Option<i32>,
+    pub issued_at: NaiveDateTime,
+}
+
+impl WebAuthnCeremony {
+    pub fn new(kind: CeremonyKind, challenge: String, brawler_id: Option<i32>) -> Self {
+        Self {
+            kind,
+            challenge,
+            brawler_id,
+            issued_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn expires_at(&self, timeout_ms: u64) -> NaiveDateTime {
+        self.issued_at + Duration::milliseconds(timeout_ms as i64)
+    }
+
+    pub fn is_expired(&self, timeout_ms: u64) -> bool {
+        self.expires_at(timeout_ms) <= Utc::now().naive_utc()
+    }
+}
@@ -1,12 +1,21 @@
 use anyhow::Result;
 use argon2::{
-    Argon2, PasswordHash, PasswordVerifier, PasswordHasher,
+    Algorithm, Argon2, Params, PasswordHash, PasswordVerifier, PasswordHasher, Version,
     password_hash::{SaltString, rand_core::OsRng}
 };
 
+use crate::config::config_loader::get_argon2_env;
+
+fn configured_params() -> Result<Params> {
+    let argon2_env = get_argon2_env()?;
+
+    Params::new(argon2_env.memory_kib, argon2_env.iterations, argon2_env.parallelism, None)
+        .map_err(|e| anyhow::anyhow!(e.to_string()))
+}
+
 pub fn hash(password: String) -> Result<String> {
     let salt = SaltString::generate(&mut OsRng);
-    let argon2 = Argon2::default();
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, configured_params()?);
     
     let password_hash = argon2
         .hash_password(password.as_bytes(), &salt)
@@ -15,6 +24,7 @@ pub fn hash(password: String) -> Result<String> {
     Ok(password_hash.to_string())
 }
 
+// Verification reads the algorithm and parameters from the stored hash, so old hashes keep working.
 pub fn verify(password: String, hash: String) -> Result<bool> {
     let parsed_hash = PasswordHash::new(&hash)
         .map_err(|e| anyhow::anyhow!(e.to_string()))?;
@@ -26,3 +36,23 @@ pub fn verify(password: String, hash: String) -> Result<bool> {
         Err(_) => Ok(false),
     }
 }
+
+// True when the stored hash was made with another algorithm or weaker/different parameters
+// than the ones currently configured. Only meaningful after a successful `verify`.
+pub fn needs_rehash(hash: &str) -> Result<bool> {
+    let Ok(parsed_hash) = PasswordHash::new(hash) else {
+        return Ok(true);
+    };
+    if parsed_hash.algorithm != Algorithm::Argon2id.ident() || parsed_hash.version != Some(Version::V0x13.into()) {
+        return Ok(true);
+    }
+
+    let Ok(current) = Params::try_from(&parsed_hash) else {
+        return Ok(true);
+    };
+    let configured = configured_params()?;
+
+    Ok(current.m_cost() != configured.m_cost()
+        || current.t_cost() != configured.t_cost()
+        || current.p_cost() != configured.p_cost())
+}
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+CREATE TABLE sessions (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL,
+    refresh_token_hash VARCHAR(255) NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    sessions
+ADD
+    CONSTRAINT fk_session_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id);
+
+CREATE UNIQUE INDEX idx_sessions_refresh_token_hash ON sessions (refresh_token_hash);
+
+SELECT diesel_manage_updated_at('sessions');
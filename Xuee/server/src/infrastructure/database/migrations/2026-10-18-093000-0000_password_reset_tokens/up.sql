@@ -0,0 +1,16 @@
+-- Your SQL goes here
+CREATE TABLE password_reset_tokens (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL,
+    token_hash VARCHAR(255) NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    password_reset_tokens
+ADD
+    CONSTRAINT fk_password_reset_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id);
+
+CREATE UNIQUE INDEX idx_password_reset_tokens_token_hash ON password_reset_tokens (token_hash);
@@ -0,0 +1,21 @@
+-- Your SQL goes here
+ALTER TABLE brawlers ADD COLUMN email_verified_at TIMESTAMP;
+
+-- Accounts from before verification existed never got a link, so they are grandfathered in
+UPDATE brawlers SET email_verified_at = created_at;
+
+CREATE TABLE email_verification_tokens (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL,
+    token_hash VARCHAR(255) NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    email_verification_tokens
+ADD
+    CONSTRAINT fk_email_verification_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id);
+
+CREATE UNIQUE INDEX idx_email_verification_tokens_token_hash ON email_verification_tokens (token_hash);
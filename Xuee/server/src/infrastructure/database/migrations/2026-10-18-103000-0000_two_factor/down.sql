@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS brawler_recovery_codes;
+
+DROP TABLE IF EXISTS brawler_two_factor;
@@ -0,0 +1,31 @@
+-- Your SQL goes here
+CREATE TABLE brawler_two_factor (
+    brawler_id INTEGER PRIMARY KEY,
+    secret_ciphertext VARCHAR(255) NOT NULL,
+    last_used_step BIGINT,
+    enabled_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE TABLE brawler_recovery_codes (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL,
+    code_hash VARCHAR(255) NOT NULL,
+    used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    brawler_two_factor
+ADD
+    CONSTRAINT fk_two_factor_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id);
+
+ALTER TABLE
+    brawler_recovery_codes
+ADD
+    CONSTRAINT fk_recovery_code_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id);
+
+CREATE INDEX idx_brawler_recovery_codes_brawler_id ON brawler_recovery_codes (brawler_id);
+
+SELECT diesel_manage_updated_at('brawler_two_factor');
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE login_attempts (
+    id SERIAL PRIMARY KEY,
+    scope VARCHAR(16) NOT NULL,
+    identifier VARCHAR(255) NOT NULL,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    last_failed_at TIMESTAMP,
+    locked_until TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX idx_login_attempts_scope_identifier ON login_attempts (scope, identifier);
+
+SELECT diesel_manage_updated_at('login_attempts');
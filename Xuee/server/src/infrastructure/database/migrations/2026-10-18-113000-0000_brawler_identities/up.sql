@@ -0,0 +1,32 @@
+-- Your SQL goes here
+CREATE TABLE brawler_identities (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL,
+    provider VARCHAR(64) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    email VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    brawler_identities
+ADD
+    CONSTRAINT fk_identity_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id);
+
+CREATE UNIQUE INDEX idx_brawler_identities_provider_subject ON brawler_identities (provider, subject);
+
+CREATE UNIQUE INDEX idx_brawler_identities_brawler_provider ON brawler_identities (brawler_id, provider);
+
+SELECT diesel_manage_updated_at('brawler_identities');
+
+-- The old Google login created accounts named after the Google-verified address without an
+-- identity row or a verification link. Trusting that address lets their next sign-in attach;
+-- password registrations always have a verification token and are left alone.
+UPDATE brawlers
+SET email_verified_at = created_at
+WHERE email_verified_at IS NULL
+    AND username LIKE '%@%'
+    AND NOT EXISTS (
+        SELECT 1 FROM email_verification_tokens t WHERE t.brawler_id = brawlers.id
+    );
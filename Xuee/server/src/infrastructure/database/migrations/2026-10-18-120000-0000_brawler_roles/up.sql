@@ -0,0 +1,5 @@
+-- Your SQL goes here
+ALTER TABLE
+    brawlers
+ADD
+    COLUMN role VARCHAR(32) NOT NULL DEFAULT 'Brawler';
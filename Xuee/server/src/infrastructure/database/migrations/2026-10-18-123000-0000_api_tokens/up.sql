@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TABLE api_tokens (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    token_prefix VARCHAR(16) NOT NULL,
+    token_hash VARCHAR(255) NOT NULL,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    expires_at TIMESTAMP,
+    last_used_at TIMESTAMP,
+    revoked_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    api_tokens
+ADD
+    CONSTRAINT fk_api_token_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id);
+
+CREATE UNIQUE INDEX idx_api_tokens_token_hash ON api_tokens (token_hash);
+
+SELECT diesel_manage_updated_at('api_tokens');
@@ -0,0 +1,21 @@
+-- Your SQL goes here
+CREATE TABLE auth_events (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER,
+    username VARCHAR(255),
+    event_type VARCHAR(64) NOT NULL,
+    outcome VARCHAR(16) NOT NULL,
+    reason TEXT,
+    provider VARCHAR(64),
+    ip_address VARCHAR(64),
+    user_agent TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    auth_events
+ADD
+    CONSTRAINT fk_auth_event_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_auth_events_brawler_created ON auth_events (brawler_id, created_at DESC);
+CREATE INDEX idx_auth_events_created ON auth_events (created_at DESC);
@@ -0,0 +1,23 @@
+-- Your SQL goes here
+CREATE TABLE webauthn_credentials (
+    id SERIAL PRIMARY KEY,
+    brawler_id INTEGER NOT NULL,
+    credential_id VARCHAR(1024) NOT NULL,
+    public_key BYTEA NOT NULL,
+    sign_count BIGINT NOT NULL DEFAULT 0,
+    aaguid VARCHAR(36) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    last_used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    webauthn_credentials
+ADD
+    CONSTRAINT fk_webauthn_credential_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id);
+
+CREATE UNIQUE INDEX idx_webauthn_credentials_credential_id ON webauthn_credentials (credential_id);
+CREATE INDEX idx_webauthn_credentials_brawler_id ON webauthn_credentials (brawler_id);
+
+SELECT diesel_manage_updated_at('webauthn_credentials');
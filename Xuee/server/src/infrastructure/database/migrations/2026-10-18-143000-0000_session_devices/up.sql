@@ -0,0 +1,11 @@
+-- Your SQL goes here
+ALTER TABLE
+    sessions
+ADD
+    COLUMN user_agent TEXT,
+ADD
+    COLUMN ip_address VARCHAR(45),
+ADD
+    COLUMN last_seen_at TIMESTAMP NOT NULL DEFAULT now();
+
+CREATE INDEX idx_sessions_brawler_id ON sessions (brawler_id);
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+ALTER TABLE
+    brawlers
+ADD
+    COLUMN deletion_scheduled_for TIMESTAMP,
+ADD
+    COLUMN deleted_at TIMESTAMP;
+
+CREATE INDEX idx_brawlers_deletion_scheduled_for ON brawlers (deletion_scheduled_for)
+WHERE
+    deletion_scheduled_for IS NOT NULL;
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS username_redirects;
+
+ALTER TABLE
+    brawlers DROP COLUMN IF EXISTS username_changed_at;
+
+DROP INDEX IF EXISTS idx_brawlers_username_lower;
@@ -0,0 +1,40 @@
+-- Your SQL goes here
+-- Older rows may already collide; keep the first registration's name and suffix the rest with their id,
+-- trimming the name so the result still fits varchar(255)
+UPDATE
+    brawlers b
+SET
+    username = left(b.username, 255 - length('-' || b.id)) || '-' || b.id
+WHERE
+    EXISTS (
+        SELECT
+            1
+        FROM
+            brawlers k
+        WHERE
+            lower(k.username) = lower(b.username)
+            AND k.id < b.id
+    );
+
+CREATE UNIQUE INDEX idx_brawlers_username_lower ON brawlers (lower(username));
+
+ALTER TABLE
+    brawlers
+ADD
+    COLUMN username_changed_at TIMESTAMP;
+
+CREATE TABLE username_redirects (
+    id SERIAL PRIMARY KEY,
+    old_username VARCHAR(255) NOT NULL,
+    brawler_id INTEGER NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+ALTER TABLE
+    username_redirects
+ADD
+    CONSTRAINT fk_username_redirect_brawler FOREIGN KEY (brawler_id) REFERENCES brawlers(id) ON DELETE CASCADE;
+
+CREATE UNIQUE INDEX idx_username_redirects_old_username_lower ON username_redirects (lower(old_username));
+CREATE INDEX idx_username_redirects_brawler_id ON username_redirects (brawler_id);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS friend_requests;
+DROP TABLE IF EXISTS brawler_follows;
@@ -0,0 +1,40 @@
+-- Your SQL goes here
+CREATE TABLE brawler_follows (
+    follower_id INTEGER NOT NULL,
+    followee_id INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (follower_id, followee_id),
+    CONSTRAINT chk_brawler_follows_not_self CHECK (follower_id <> followee_id)
+);
+
+ALTER TABLE
+    brawler_follows
+ADD
+    CONSTRAINT fk_brawler_follows_follower FOREIGN KEY (follower_id) REFERENCES brawlers(id) ON DELETE CASCADE,
+ADD
+    CONSTRAINT fk_brawler_follows_followee FOREIGN KEY (followee_id) REFERENCES brawlers(id) ON DELETE CASCADE;
+
+CREATE INDEX idx_brawler_follows_followee_id ON brawler_follows (followee_id);
+
+CREATE TABLE friend_requests (
+    id SERIAL PRIMARY KEY,
+    requester_id INTEGER NOT NULL,
+    addressee_id INTEGER NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'Pending',
+    responded_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    CONSTRAINT chk_friend_requests_not_self CHECK (requester_id <> addressee_id)
+);
+
+ALTER TABLE
+    friend_requests
+ADD
+    CONSTRAINT fk_friend_requests_requester FOREIGN KEY (requester_id) REFERENCES brawlers(id) ON DELETE CASCADE,
+ADD
+    CONSTRAINT fk_friend_requests_addressee FOREIGN KEY (addressee_id) REFERENCES brawlers(id) ON DELETE CASCADE;
+
+-- Only one open request per direction; answered ones stay as history
+CREATE UNIQUE INDEX idx_friend_requests_pending ON friend_requests (requester_id, addressee_id)
+WHERE
+    status = 'Pending';
+CREATE INDEX idx_friend_requests_addressee_id ON friend_requests (addressee_id);
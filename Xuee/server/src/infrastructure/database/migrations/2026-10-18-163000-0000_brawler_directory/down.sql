@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_brawlers_username_local_trgm;
+DROP INDEX IF EXISTS idx_brawlers_display_name_trgm;
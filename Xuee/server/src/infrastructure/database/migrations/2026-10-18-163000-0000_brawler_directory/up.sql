@@ -0,0 +1,6 @@
+-- Your SQL goes here
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Serve the directory's substring search (LIKE '%term%') on both searchable names
+CREATE INDEX idx_brawlers_display_name_trgm ON brawlers USING gin (lower(display_name) gin_trgm_ops);
+CREATE INDEX idx_brawlers_username_local_trgm ON brawlers USING gin (lower(split_part(username, '@', 1)) gin_trgm_ops);
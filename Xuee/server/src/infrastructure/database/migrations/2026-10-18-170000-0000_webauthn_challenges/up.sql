@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE webauthn_challenges (
+    challenge VARCHAR(64) PRIMARY KEY,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX idx_webauthn_challenges_expires_at ON webauthn_challenges (expires_at);
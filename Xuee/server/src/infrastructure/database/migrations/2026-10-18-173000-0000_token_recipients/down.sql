@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE password_reset_tokens DROP COLUMN IF EXISTS email;
+ALTER TABLE magic_link_tokens DROP COLUMN IF EXISTS email;
+ALTER TABLE email_verification_tokens DROP COLUMN IF EXISTS email;
@@ -0,0 +1,27 @@
+-- Your SQL goes here
+-- Each emailed token remembers the address it went to, so it stops working once the brawler renames
+ALTER TABLE email_verification_tokens ADD COLUMN email VARCHAR(255);
+ALTER TABLE magic_link_tokens ADD COLUMN email VARCHAR(255);
+ALTER TABLE password_reset_tokens ADD COLUMN email VARCHAR(255);
+
+-- Older tokens went to whatever the username was when they were issued; any issued before a rename
+-- can no longer be attributed to an address, so they are burned
+UPDATE email_verification_tokens t SET used_at = now()
+FROM brawlers b
+WHERE b.id = t.brawler_id AND t.used_at IS NULL AND b.username_changed_at >= t.created_at;
+
+UPDATE magic_link_tokens t SET used_at = now()
+FROM brawlers b
+WHERE b.id = t.brawler_id AND t.used_at IS NULL AND b.username_changed_at >= t.created_at;
+
+UPDATE password_reset_tokens t SET used_at = now()
+FROM brawlers b
+WHERE b.id = t.brawler_id AND t.used_at IS NULL AND b.username_changed_at >= t.created_at;
+
+UPDATE email_verification_tokens t SET email = b.username FROM brawlers b WHERE b.id = t.brawler_id;
+UPDATE magic_link_tokens t SET email = b.username FROM brawlers b WHERE b.id = t.brawler_id;
+UPDATE password_reset_tokens t SET email = b.username FROM brawlers b WHERE b.id = t.brawler_id;
+
+ALTER TABLE email_verification_tokens ALTER COLUMN email SET NOT NULL;
+ALTER TABLE magic_link_tokens ALTER COLUMN email SET NOT NULL;
+ALTER TABLE password_reset_tokens ALTER COLUMN email SET NOT NULL;
@@ -0,0 +1,51 @@
+use diesel::{QueryableByName, sql_types::BigInt};
+
+use crate::domain::value_objects::mission_filter::{MissionSort, SortOrder};
+
+// Shared by /missions and the /brawlers/me/missions dashboards so both page the same way.
+pub const MISSION_COLUMNS: &str = r#"
+    m.id, m.name, m.description, m.status, m.chief_id,
+    b.display_name as chief_display_name,
+    (SELECT COUNT(*) FROM crew_memberships cm WHERE cm.mission_id = m.id) as crew_count,
+    m.mission_date, m.time, m.email, m.phone, m.location, m.rewards,
+    m.created_at, m.updated_at
+"#;
+
+#[derive(Debug, QueryableByName)]
+pub struct TotalCount {
+    #[diesel(sql_type = BigInt)]
+    pub total: i64,
+}
+
+// Wraps a filtered mission SELECT in a keyset page. The base query's output columns are the sort
+// keys, so crew_count is not recomputed. `first_param` is the cursor value (as text), followed by
+// the cursor id and the row limit.
+pub fn page_sql(base: &str, (sort, order): (MissionSort, SortOrder), first_param: usize) -> String {
+    let (key, cast) = match (sort, order) {
+        (MissionSort::CreatedAt, _) => ("created_at", "TIMESTAMP"),
+        (MissionSort::MissionDate, SortOrder::Asc) => ("COALESCE(mission_date, 'infinity'::timestamp)", "TIMESTAMP"),
+        (MissionSort::MissionDate, SortOrder::Desc) => ("COALESCE(mission_date, '-infinity'::timestamp)", "TIMESTAMP"),
+        (MissionSort::CrewCount, _) => ("crew_count", "BIGINT"),
+        (MissionSort::Name, _) => ("name", "VARCHAR"),
+    };
+    let (comparison, direction) = match order {
+        SortOrder::Asc => (">", "ASC"),
+        SortOrder::Desc => ("<", "DESC"),
+    };
+
+    format!(
+        r#"
+        SELECT * FROM ({base}) listing
+        WHERE (${value} IS NULL OR ({key}, id) {comparison} (CAST(${value} AS {cast}), ${id}))
+        ORDER BY {key} {direction}, id {direction}
+        LIMIT ${limit}
+        "#,
+        value = first_param,
+        id = first_param + 1,
+        limit = first_param + 2,
+    )
+}
+
+pub fn count_sql(base: &str) -> String {
+    format!("SELECT COUNT(*) AS total FROM ({}) listing", base)
+}
@@ -1,3 +1,4 @@
 pub mod repositories;
 pub mod postgresql_connection;
 pub mod schema;
+pub mod mission_listing;
@@ -0,0 +1,328 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::sql_types::Int4;
+use diesel::{delete, update};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+
+use crate::domain::{
+    entities::{battles::BattleEntity, brawlers::BrawlerEntity},
+    repositories::accounts::AccountRepository,
+    value_objects::{
+        account_model::{AccountExportModel, ExportedCrewMembership, ExportedDailyFortune, ExportedUserCard},
+        mission_model::MissionModel,
+        mission_statuses::MissionStatuses,
+    },
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::{
+        api_tokens, battles, brawler_follows, brawler_identities, brawler_recovery_codes, brawler_two_factor,
+        brawlers, crew_memberships, email_verification_tokens, friend_requests, magic_link_tokens, missions,
+        password_reset_tokens, sessions, user_cards, username_redirects, webauthn_credentials,
+    },
+};
+
+// Missions that still change; erasing an account never rewrites finished ones.
+fn live_statuses() -> [String; 2] {
+    [MissionStatuses::Open.to_string(), MissionStatuses::InProgress.to_string()]
+}
+
+pub struct AccountPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl AccountPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl AccountRepository for AccountPostgres {
+    async fn export(&self, brawler_id: i32) -> Result<AccountExportModel> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let profile = brawlers::table
+            .filter(brawlers::id.eq(brawler_id))
+            .filter(brawlers::deleted_at.is_null())
+            .select(BrawlerEntity::as_select())
+            .first::<BrawlerEntity>(&mut conn)
+            .await?;
+
+        let missions_led = diesel::sql_query(
+            r#"
+            SELECT
+                m.id, m.name, m.description, m.status, m.chief_id,
+                b.display_name as chief_display_name,
+                (SELECT COUNT(*) FROM crew_memberships cm WHERE cm.mission_id = m.id) as crew_count,
+                m.mission_date, m.time, m.email, m.phone, m.location, m.rewards,
+                m.created_at, m.updated_at
+            FROM missions m
+            JOIN brawlers b ON m.chief_id = b.id
+            WHERE m.chief_id = $1
+            ORDER BY m.created_at
+        "#,
+        )
+        .bind::<Int4, _>(brawler_id)
+        .load::<MissionModel>(&mut conn)
+        .await?;
+
+        let crew_memberships = diesel::sql_query(
+            r#"
+            SELECT cm.mission_id, m.name AS mission_name, m.status AS mission_status, cm.joined_at
+            FROM crew_memberships cm
+            JOIN missions m ON m.id = cm.mission_id
+            WHERE cm.brawler_id = $1
+            ORDER BY cm.joined_at
+        "#,
+        )
+        .bind::<Int4, _>(brawler_id)
+        .load::<ExportedCrewMembership>(&mut conn)
+        .await?;
+
+        let cards = diesel::sql_query(
+            r#"
+            SELECT uc.id, uc.card_id, c.name AS card_name, uc.level, uc.experience, uc.obtained_at
+            FROM user_cards uc
+            JOIN cards c ON c.id = uc.card_id
+            WHERE uc.user_id = $1
+            ORDER BY uc.obtained_at
+        "#,
+        )
+        .bind::<Int4, _>(brawler_id)
+        .load::<ExportedUserCard>(&mut conn)
+        .await?;
+
+        let battles = battles::table
+            .filter(battles::attacker_id.eq(brawler_id).or(battles::defender_id.eq(brawler_id)))
+            .order(battles::created_at.asc())
+            .select(BattleEntity::as_select())
+            .load::<BattleEntity>(&mut conn)
+            .await?;
+
+        // daily_fortunes comes from migration_fortune.sql and is not in the Diesel schema
+        let daily_fortunes = diesel::sql_query(
+            r#"
+            SELECT df.date, fs.number AS stick_number, fs.poem_text, fs.interpretation, fs.lucky_direction
+            FROM daily_fortunes df
+            JOIN fortune_sticks fs ON fs.id = df.stick_id
+            WHERE df.user_id = $1
+            ORDER BY df.date
+        "#,
+        )
+        .bind::<Int4, _>(brawler_id)
+        .load::<ExportedDailyFortune>(&mut conn)
+        .await?;
+
+        Ok(AccountExportModel {
+            exported_at: Utc::now().naive_utc(),
+            profile: profile.into(),
+            missions_led,
+            crew_memberships,
+            cards,
+            battles,
+            daily_fortunes,
+        })
+    }
+
+    async fn schedule_deletion(&self, brawler_id: i32, scheduled_for: NaiveDateTime) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(brawlers::table.filter(brawlers::id.eq(brawler_id)))
+            .filter(brawlers::deleted_at.is_null())
+            .set(brawlers::deletion_scheduled_for.eq(scheduled_for))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn cancel_deletion(&self, brawler_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(brawlers::table.filter(brawlers::id.eq(brawler_id)))
+            .filter(brawlers::deleted_at.is_null())
+            .set(brawlers::deletion_scheduled_for.eq(None::<NaiveDateTime>))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn due_for_deletion(&self) -> Result<Vec<i32>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let ids = brawlers::table
+            .filter(brawlers::deletion_scheduled_for.le(diesel::dsl::now))
+            .filter(brawlers::deleted_at.is_null())
+            .select(brawlers::id)
+            .load::<i32>(&mut connection)
+            .await?;
+
+        Ok(ids)
+    }
+
+    // Battles and some missions must keep pointing at the brawler, so the row survives as an
+    // anonymous tombstone; everything personal is removed or detached.
+    async fn erase(&self, brawler_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        connection
+            .transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    // Live missions pass to their longest-serving crew member, who leaves the crew to lead it.
+                    // Completed and failed missions are history and keep the tombstone as chief and crew.
+                    diesel::sql_query(
+                        r#"
+                        WITH successor AS (
+                            SELECT DISTINCT ON (cm.mission_id) cm.mission_id, cm.brawler_id
+                            FROM crew_memberships cm
+                            JOIN missions m ON m.id = cm.mission_id
+                            WHERE m.chief_id = $1
+                                AND m.deleted_at IS NULL
+                                AND m.status IN ('Open', 'InProgress')
+                                AND cm.brawler_id <> $1
+                            ORDER BY cm.mission_id, cm.joined_at, cm.brawler_id
+                        ), handed_over AS (
+                            UPDATE missions m
+                            SET chief_id = s.brawler_id
+                            FROM successor s
+                            WHERE m.id = s.mission_id
+                            RETURNING m.id, m.chief_id
+                        )
+                        DELETE FROM crew_memberships cm
+                        USING handed_over h
+                        WHERE cm.mission_id = h.id AND cm.brawler_id = h.chief_id
+                    "#,
+                    )
+                    .bind::<Int4, _>(brawler_id)
+                    .execute(conn)
+                    .await?;
+
+                    // Live missions nobody can take over are closed, keeping the tombstone as their chief
+                    update(missions::table.filter(missions::chief_id.eq(brawler_id)))
+                        .filter(missions::deleted_at.is_null())
+                        .filter(missions::status.eq_any(live_statuses()))
+                        .set(missions::deleted_at.eq(diesel::dsl::now))
+                        .execute(conn)
+                        .await?;
+
+                    let live_missions = missions::table
+                        .filter(missions::status.eq_any(live_statuses()))
+                        .select(missions::id);
+                    delete(crew_memberships::table.filter(crew_memberships::brawler_id.eq(brawler_id)))
+                        .filter(crew_memberships::mission_id.eq_any(live_missions))
+                        .execute(conn)
+                        .await?;
+                    delete(user_cards::table.filter(user_cards::user_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    diesel::sql_query("DELETE FROM daily_fortunes WHERE user_id = $1")
+                        .bind::<Int4, _>(brawler_id)
+                        .execute(conn)
+                        .await?;
+
+                    delete(sessions::table.filter(sessions::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(api_tokens::table.filter(api_tokens::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(webauthn_credentials::table.filter(webauthn_credentials::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(brawler_identities::table.filter(brawler_identities::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(brawler_recovery_codes::table.filter(brawler_recovery_codes::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(brawler_two_factor::table.filter(brawler_two_factor::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(password_reset_tokens::table.filter(password_reset_tokens::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(email_verification_tokens::table.filter(email_verification_tokens::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(magic_link_tokens::table.filter(magic_link_tokens::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(
+                        brawler_follows::table.filter(
+                            brawler_follows::follower_id
+                                .eq(brawler_id)
+                                .or(brawler_follows::followee_id.eq(brawler_id)),
+                        ),
+                    )
+                    .execute(conn)
+                    .await?;
+                    delete(
+                        friend_requests::table.filter(
+                            friend_requests::requester_id
+                                .eq(brawler_id)
+                                .or(friend_requests::addressee_id.eq(brawler_id)),
+                        ),
+                    )
+                    .execute(conn)
+                    .await?;
+                    // The audit trail stays, but no longer says who or from where. Failed sign-ins record
+                    // only the typed username, so rows are also matched on the current and former names.
+                    diesel::sql_query(
+                        r#"
+                        UPDATE auth_events
+                        SET brawler_id = NULL, username = NULL, ip_address = NULL, user_agent = NULL
+                        WHERE brawler_id = $1
+                            OR lower(username) IN (
+                                SELECT lower(username) FROM brawlers WHERE id = $1
+                                UNION
+                                SELECT lower(old_username) FROM username_redirects WHERE brawler_id = $1
+                            )
+                    "#,
+                    )
+                    .bind::<Int4, _>(brawler_id)
+                    .execute(conn)
+                    .await?;
+
+                    // Former names are released straight away instead of pointing at a tombstone
+                    delete(username_redirects::table.filter(username_redirects::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+
+                    // Throttle rows are keyed by the address itself
+                    diesel::sql_query(
+                        "DELETE FROM login_attempts WHERE scope IN ('username', 'magic_link') \
+                         AND identifier = (SELECT lower(username) FROM brawlers WHERE id = $1)",
+                    )
+                    .bind::<Int4, _>(brawler_id)
+                    .execute(conn)
+                    .await?;
+
+                    // A password of "!" is not a PHC string, so verification always fails
+                    update(brawlers::table.filter(brawlers::id.eq(brawler_id)))
+                        .set((
+                            brawlers::username.eq(format!("deleted-{}", brawler_id)),
+                            brawlers::password.eq("!"),
+                            brawlers::display_name.eq("Deleted brawler"),
+                            brawlers::avatar_url.eq(None::<String>),
+                            brawlers::avatar_public_id.eq(None::<String>),
+                            brawlers::email_verified_at.eq(None::<NaiveDateTime>),
+                            brawlers::role.eq("Brawler"),
+                            brawlers::deletion_scheduled_for.eq(None::<NaiveDateTime>),
+                            brawlers::deleted_at.eq(diesel::dsl::now),
+                        ))
+                        .execute(conn)
+                        .await?;
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+}
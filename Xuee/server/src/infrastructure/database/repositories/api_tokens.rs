@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{insert_into, update};
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::api_tokens::{ApiTokenEntity, NewApiTokenEntity},
+    repositories::api_tokens::ApiTokenRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::api_tokens,
+};
+
+pub struct ApiTokenPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl ApiTokenPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for ApiTokenPostgres {
+    async fn create(&self, new_token: NewApiTokenEntity) -> Result<ApiTokenEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = insert_into(api_tokens::table)
+            .values(&new_token)
+            .returning(ApiTokenEntity::as_returning())
+            .get_result::<ApiTokenEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_for_brawler(&self, brawler_id: i32) -> Result<Vec<ApiTokenEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = api_tokens::table
+            .filter(api_tokens::brawler_id.eq(brawler_id))
+            .filter(api_tokens::revoked_at.is_null())
+            .order(api_tokens::created_at.desc())
+            .select(ApiTokenEntity::as_select())
+            .load::<ApiTokenEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_active_by_hash(&self, token_hash: String) -> Result<Option<ApiTokenEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = api_tokens::table
+            .filter(api_tokens::token_hash.eq(token_hash))
+            .filter(api_tokens::revoked_at.is_null())
+            .filter(
+                api_tokens::expires_at
+                    .is_null()
+                    .or(api_tokens::expires_at.gt(diesel::dsl::now)),
+            )
+            .select(ApiTokenEntity::as_select())
+            .first::<ApiTokenEntity>(&mut connection)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn touch(&self, token_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(api_tokens::table.filter(api_tokens::id.eq(token_id)))
+            .set(api_tokens::last_used_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, brawler_id: i32, token_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(api_tokens::table.filter(api_tokens::id.eq(token_id)))
+            .filter(api_tokens::brawler_id.eq(brawler_id))
+            .filter(api_tokens::revoked_at.is_null())
+            .set(api_tokens::revoked_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("API token not found"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::insert_into;
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::auth_events::{AuthEventEntity, NewAuthEventEntity},
+    repositories::auth_events::AuthEventRepository,
+    value_objects::auth_event_model::AuthEventFilter,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::auth_events,
+};
+
+pub struct AuthEventPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl AuthEventPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl AuthEventRepository for AuthEventPostgres {
+    async fn record(&self, new_event: NewAuthEventEntity) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        insert_into(auth_events::table)
+            .values(&new_event)
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_for_brawler(&self, brawler_id: i32, limit: i64) -> Result<Vec<AuthEventEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = auth_events::table
+            .filter(auth_events::brawler_id.eq(brawler_id))
+            .order(auth_events::created_at.desc())
+            .limit(limit)
+            .select(AuthEventEntity::as_select())
+            .load::<AuthEventEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn search(&self, filter: AuthEventFilter) -> Result<Vec<AuthEventEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let mut query = auth_events::table.into_boxed();
+        if let Some(brawler_id) = filter.brawler_id {
+            query = query.filter(auth_events::brawler_id.eq(brawler_id));
+        }
+        if let Some(username) = filter.username {
+            query = query.filter(auth_events::username.ilike(username));
+        }
+        if let Some(event_type) = filter.event_type {
+            query = query.filter(auth_events::event_type.eq(event_type));
+        }
+        if let Some(outcome) = filter.outcome {
+            query = query.filter(auth_events::outcome.eq(outcome));
+        }
+        if let Some(ip_address) = filter.ip_address {
+            query = query.filter(auth_events::ip_address.eq(ip_address));
+        }
+        if let Some(since) = filter.since {
+            query = query.filter(auth_events::created_at.ge(since));
+        }
+        if let Some(until) = filter.until {
+            query = query.filter(auth_events::created_at.lt(until));
+        }
+
+        let result = query
+            .order(auth_events::created_at.desc())
+            .limit(filter.limit.unwrap_or(100))
+            .select(AuthEventEntity::as_select())
+            .load::<AuthEventEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{delete, insert_into};
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::brawler_identities::{BrawlerIdentityEntity, NewBrawlerIdentityEntity},
+    repositories::brawler_identities::BrawlerIdentityRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::brawler_identities,
+};
+
+pub struct BrawlerIdentityPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl BrawlerIdentityPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl BrawlerIdentityRepository for BrawlerIdentityPostgres {
+    async fn find_by_provider_subject(&self, provider: String, subject: String) -> Result<Option<BrawlerIdentityEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = brawler_identities::table
+            .filter(brawler_identities::provider.eq(provider))
+            .filter(brawler_identities::subject.eq(subject))
+            .select(BrawlerIdentityEntity::as_select())
+            .first::<BrawlerIdentityEntity>(&mut connection)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn list_for_brawler(&self, brawler_id: i32) -> Result<Vec<BrawlerIdentityEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = brawler_identities::table
+            .filter(brawler_identities::brawler_id.eq(brawler_id))
+            .order(brawler_identities::created_at.asc())
+            .select(BrawlerIdentityEntity::as_select())
+            .load::<BrawlerIdentityEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn link(&self, new_identity: NewBrawlerIdentityEntity) -> Result<i32> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = insert_into(brawler_identities::table)
+            .values(&new_identity)
+            .returning(brawler_identities::id)
+            .get_result::<i32>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn unlink(&self, brawler_id: i32, provider: String) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = delete(brawler_identities::table)
+            .filter(brawler_identities::brawler_id.eq(brawler_id))
+            .filter(brawler_identities::provider.eq(provider))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Identity is not linked"));
+        }
+
+        Ok(())
+    }
+}
@@ -2,20 +2,32 @@ use std::sync::Arc;
 use anyhow::Result;
 use async_trait::async_trait;
 use diesel::prelude::*;
-use diesel::{insert_into, update, QueryDsl};
-use diesel::sql_types::Int4;
-use diesel_async::RunQueryDsl;
+use diesel::{delete, insert_into, update, QueryDsl};
+use diesel::sql_types::{BigInt, Int4, Nullable, Timestamp, Varchar};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
 
 use crate::domain::{
-    entities::brawlers::{BrawlerEntity, RegisterBrawlerEntity},
+    entities::{
+        brawlers::{BrawlerEntity, RegisterBrawlerEntity},
+        username_redirects::NewUsernameRedirectEntity,
+    },
     repositories::brawlers::BrawlerRepository,
-    value_objects::mission_model::MissionModel,
+    value_objects::{
+        brawler_model::{BrawlerProfileModel, DirectoryBrawlerModel, DirectorySort},
+        mission_filter::{MissionFilter, MyMissionsView},
+        mission_model::MissionModel,
+    },
 };
 use crate::infrastructure::database::{
+    mission_listing::{self, TotalCount},
     postgresql_connection::PgPoolSquad,
-    schema::brawlers,
+    schema::{brawlers, email_verification_tokens, magic_link_tokens, password_reset_tokens, sessions, username_redirects},
 };
 
+diesel::sql_function!(fn lower(x: Varchar) -> Varchar);
+diesel::sql_function!(fn coalesce(x: Nullable<Timestamp>, y: Timestamp) -> Nullable<Timestamp>);
+
 pub struct BrawlerPostgres {
     db_pool: Arc<PgPoolSquad>,
 }
@@ -44,7 +56,64 @@ impl BrawlerRepository for BrawlerPostgres {
         let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
         let result = brawlers::table
-            .filter(brawlers::username.eq(username))
+            .filter(lower(brawlers::username).eq(username.trim().to_lowercase()))
+            .select(BrawlerEntity::as_select())
+            .first::<BrawlerEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    // A name parked as someone's redirect counts as taken until the grace period runs out.
+    async fn is_username_taken(&self, username: String, except_brawler_id: Option<i32>) -> Result<bool> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        let username = username.trim().to_lowercase();
+        let except_brawler_id = except_brawler_id.unwrap_or_default();
+
+        let owned = diesel::select(diesel::dsl::exists(
+            brawlers::table
+                .filter(lower(brawlers::username).eq(&username))
+                .filter(brawlers::id.ne(except_brawler_id)),
+        ))
+        .get_result::<bool>(&mut connection)
+        .await?;
+        if owned {
+            return Ok(true);
+        }
+
+        let parked = diesel::select(diesel::dsl::exists(
+            username_redirects::table
+                .filter(lower(username_redirects::old_username).eq(&username))
+                .filter(username_redirects::brawler_id.ne(except_brawler_id))
+                .filter(username_redirects::expires_at.gt(diesel::dsl::now)),
+        ))
+        .get_result::<bool>(&mut connection)
+        .await?;
+
+        Ok(parked)
+    }
+
+    async fn find_username_redirect(&self, username: String) -> Result<Option<String>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = username_redirects::table
+            .inner_join(brawlers::table)
+            .filter(lower(username_redirects::old_username).eq(username.trim().to_lowercase()))
+            .filter(username_redirects::expires_at.gt(diesel::dsl::now))
+            .filter(brawlers::deleted_at.is_null())
+            .select(brawlers::username)
+            .first::<String>(&mut connection)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<BrawlerEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = brawlers::table
+            .filter(brawlers::id.eq(id))
             .select(BrawlerEntity::as_select())
             .first::<BrawlerEntity>(&mut connection)
             .await?;
@@ -52,17 +121,86 @@ impl BrawlerRepository for BrawlerPostgres {
         Ok(result)
     }
 
-    async fn update_avatar(&self, id: i32, avatar_url: String) -> Result<()> {
+    async fn update_avatar(&self, id: i32, avatar_url: String, avatar_public_id: String) -> Result<()> {
         let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
         update(brawlers::table.filter(brawlers::id.eq(id)))
-            .set(brawlers::avatar_url.eq(avatar_url))
+            .set((
+                brawlers::avatar_url.eq(avatar_url),
+                brawlers::avatar_public_id.eq(avatar_public_id),
+            ))
             .execute(&mut connection)
             .await?;
 
         Ok(())
     }
 
+    // The old name is parked as a redirect, links mailed to the old address are burned and the new
+    // address has to be verified again.
+    async fn change_username(&self, id: i32, new_username: String, redirect: NewUsernameRedirectEntity) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        connection
+            .transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    // Clears the brawler's own redirect when they take a former name back, and any
+                    // expired redirect still sitting on either name
+                    let names = vec![new_username.to_lowercase(), redirect.old_username.to_lowercase()];
+                    delete(username_redirects::table)
+                        .filter(lower(username_redirects::old_username).eq_any(names))
+                        .filter(
+                            username_redirects::brawler_id
+                                .eq(id)
+                                .or(username_redirects::expires_at.le(diesel::dsl::now)),
+                        )
+                        .execute(conn)
+                        .await?;
+
+                    // A change of letter case keeps the same address, so it needs neither
+                    let renamed = redirect.old_username.to_lowercase() != new_username.to_lowercase();
+                    if renamed {
+                        insert_into(username_redirects::table)
+                            .values(&redirect)
+                            .execute(conn)
+                            .await?;
+                        update(brawlers::table.filter(brawlers::id.eq(id)))
+                            .set(brawlers::email_verified_at.eq(None::<chrono::NaiveDateTime>))
+                            .execute(conn)
+                            .await?;
+
+                        // Links mailed to the old address must not vouch for the new one
+                        update(email_verification_tokens::table.filter(email_verification_tokens::brawler_id.eq(id)))
+                            .filter(email_verification_tokens::used_at.is_null())
+                            .set(email_verification_tokens::used_at.eq(diesel::dsl::now))
+                            .execute(conn)
+                            .await?;
+                        update(magic_link_tokens::table.filter(magic_link_tokens::brawler_id.eq(id)))
+                            .filter(magic_link_tokens::used_at.is_null())
+                            .set(magic_link_tokens::used_at.eq(diesel::dsl::now))
+                            .execute(conn)
+                            .await?;
+                        update(password_reset_tokens::table.filter(password_reset_tokens::brawler_id.eq(id)))
+                            .filter(password_reset_tokens::used_at.is_null())
+                            .set(password_reset_tokens::used_at.eq(diesel::dsl::now))
+                            .execute(conn)
+                            .await?;
+                    }
+
+                    update(brawlers::table.filter(brawlers::id.eq(id)))
+                        .set((
+                            brawlers::username.eq(new_username),
+                            brawlers::username_changed_at.eq(diesel::dsl::now),
+                        ))
+                        .execute(conn)
+                        .await?;
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
     async fn update_display_name(&self, id: i32, display_name: String) -> Result<()> {
         let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
@@ -74,7 +212,40 @@ impl BrawlerRepository for BrawlerPostgres {
         Ok(())
     }
 
-    async fn update_password(&self, id: i32, password_hash: String) -> Result<()> {
+    // A new password ends every other login in the same transaction, so a stolen session
+    // cannot outlive the password change that was meant to lock it out.
+    async fn update_password(&self, id: i32, password_hash: String, keep_session_id: Option<i32>) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        connection
+            .transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    update(brawlers::table.filter(brawlers::id.eq(id)))
+                        .set(brawlers::password.eq(password_hash))
+                        .execute(conn)
+                        .await?;
+
+                    let mut revoke_sessions = update(sessions::table)
+                        .filter(sessions::brawler_id.eq(id))
+                        .filter(sessions::revoked_at.is_null())
+                        .into_boxed();
+                    if let Some(keep_session_id) = keep_session_id {
+                        revoke_sessions = revoke_sessions.filter(sessions::id.ne(keep_session_id));
+                    }
+                    revoke_sessions
+                        .set(sessions::revoked_at.eq(diesel::dsl::now))
+                        .execute(conn)
+                        .await?;
+
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
+    // Same password, stronger parameters: sessions are left alone.
+    async fn rehash_password(&self, id: i32, password_hash: String) -> Result<()> {
         let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
         update(brawlers::table.filter(brawlers::id.eq(id)))
@@ -85,26 +256,240 @@ impl BrawlerRepository for BrawlerPostgres {
         Ok(())
     }
 
-    async fn get_missions(&self, brawler_id: i32) -> Result<Vec<MissionModel>> {
+    // Matching the address in the same statement means a rename racing the link cannot slip in
+    // between the check and the update.
+    async fn mark_email_verified(&self, id: i32, email: String) -> Result<bool> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(brawlers::table.filter(brawlers::id.eq(id)))
+            .filter(lower(brawlers::username).eq(email.trim().to_lowercase()))
+            .set(brawlers::email_verified_at.eq(coalesce(brawlers::email_verified_at, diesel::dsl::now)))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(affected > 0)
+    }
+
+    async fn update_role(&self, id: i32, role: String) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(brawlers::table.filter(brawlers::id.eq(id)))
+            .set(brawlers::role.eq(role))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Brawler not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_missions(&self, brawler_id: i32, view: MyMissionsView, filter: &MissionFilter) -> Result<Vec<MissionModel>> {
         let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-        let sql = r#"
-            SELECT 
-                m.id, m.name, m.description, m.status, m.chief_id, 
-                b.display_name as chief_display_name,
-                (SELECT COUNT(*) FROM crew_memberships cm WHERE cm.mission_id = m.id) as crew_count,
-                m.created_at, m.updated_at
-            FROM missions m
-            JOIN brawlers b ON m.chief_id = b.id
-            JOIN crew_memberships cm ON cm.mission_id = m.id
-            WHERE cm.brawler_id = $1
-        "#;
+        let ordering = filter.ordering(view.default_ordering());
+        let after = filter.after(ordering)?;
+        let sql = mission_listing::page_sql(&my_missions_sql(view), ordering, 4);
+
+        let status_bind = filter.status.as_ref().map(|s| s.to_string());
+        let name_bind = filter.name.as_ref().map(|n| format!("%{}%", n));
 
         let results = diesel::sql_query(sql)
             .bind::<Int4, _>(brawler_id)
+            .bind::<Nullable<Varchar>, _>(status_bind)
+            .bind::<Nullable<Varchar>, _>(name_bind)
+            .bind::<Nullable<Varchar>, _>(after.as_ref().map(|cursor| cursor.value.clone()))
+            .bind::<Nullable<Int4>, _>(after.as_ref().map(|cursor| cursor.id))
+            .bind::<BigInt, _>(filter.page_size() + 1)
             .load::<MissionModel>(&mut conn)
             .await?;
 
         Ok(results)
     }
+
+    async fn count_missions(&self, brawler_id: i32, view: MyMissionsView, filter: &MissionFilter) -> Result<i64> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let status_bind = filter.status.as_ref().map(|s| s.to_string());
+        let name_bind = filter.name.as_ref().map(|n| format!("%{}%", n));
+
+        let result = diesel::sql_query(mission_listing::count_sql(&my_missions_sql(view)))
+            .bind::<Int4, _>(brawler_id)
+            .bind::<Nullable<Varchar>, _>(status_bind)
+            .bind::<Nullable<Varchar>, _>(name_bind)
+            .get_result::<TotalCount>(&mut conn)
+            .await?;
+
+        Ok(result.total)
+    }
+
+    async fn get_profile(&self, brawler_id: i32) -> Result<Option<BrawlerProfileModel>> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        // Missions count once whether the brawler led them or crewed them; deleted missions are ignored.
+        let sql = r#"
+            WITH involved AS (
+                SELECT m.id, m.status
+                FROM missions m
+                WHERE m.deleted_at IS NULL
+                  AND (m.chief_id = $1
+                       OR EXISTS (SELECT 1 FROM crew_memberships cm WHERE cm.mission_id = m.id AND cm.brawler_id = $1))
+            )
+            SELECT
+                b.id,
+                b.display_name,
+                b.avatar_url,
+                b.created_at AS joined_at,
+                (SELECT COUNT(*) FROM missions m WHERE m.chief_id = b.id AND m.deleted_at IS NULL) AS missions_led,
+                (SELECT COUNT(*) FROM crew_memberships cm
+                    JOIN missions m ON m.id = cm.mission_id
+                    WHERE cm.brawler_id = b.id AND m.deleted_at IS NULL) AS missions_joined,
+                (SELECT COUNT(*) FROM involved WHERE status = 'Completed') AS mission_success_count,
+                (SELECT COUNT(*) FROM involved WHERE status = 'Failed') AS mission_failure_count,
+                (SELECT COUNT(*) FROM battles bt WHERE bt.winner_id = b.id) AS battles_won,
+                (SELECT COUNT(*) FROM battles bt
+                    WHERE (bt.attacker_id = b.id OR bt.defender_id = b.id)
+                      AND bt.winner_id IS NOT NULL AND bt.winner_id <> b.id) AS battles_lost,
+                (SELECT COUNT(*) FROM battles bt
+                    WHERE (bt.attacker_id = b.id OR bt.defender_id = b.id) AND bt.winner_id IS NULL) AS battles_drawn,
+                (SELECT COUNT(*) FROM user_cards uc WHERE uc.user_id = b.id) AS cards_owned
+            FROM brawlers b
+            WHERE b.id = $1 AND b.deleted_at IS NULL
+        "#;
+
+        let result = diesel::sql_query(sql)
+            .bind::<Int4, _>(brawler_id)
+            .get_result::<BrawlerProfileModel>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn search_directory(
+        &self,
+        search: Option<String>,
+        sort: DirectorySort,
+        after: Option<(i64, i32)>,
+        limit: i64,
+    ) -> Result<Vec<DirectoryBrawlerModel>> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        // Only the part of the username before '@' is searchable, so the directory cannot be used
+        // to confirm whether a full email address has an account. Both sides have trigram indexes.
+        // Sort names double as the directory's column aliases.
+
+        let sql = format!(
+            r#"
+            WITH directory AS (
+                SELECT
+                    b.id,
+                    b.display_name,
+                    b.avatar_url,
+                    b.created_at AS joined_at,
+                    (SELECT COUNT(*) FROM missions m
+                        WHERE m.chief_id = b.id AND m.deleted_at IS NULL
+                          AND m.created_at > now() - interval '30 days')
+                    + (SELECT COUNT(*) FROM crew_memberships cm
+                        JOIN missions m ON m.id = cm.mission_id
+                        WHERE cm.brawler_id = b.id AND m.deleted_at IS NULL
+                          AND cm.joined_at > now() - interval '30 days')
+                    + (SELECT COUNT(*) FROM battles bt
+                        WHERE (bt.attacker_id = b.id OR bt.defender_id = b.id)
+                          AND bt.created_at > now() - interval '30 days') AS activity,
+                    COALESCE((SELECT SUM((c.attack + c.defense) * uc.level) FROM user_cards uc
+                        JOIN cards c ON c.id = uc.card_id
+                        WHERE uc.user_id = b.id), 0)::BIGINT AS card_power,
+                    (SELECT COUNT(*) FROM missions m
+                        WHERE m.deleted_at IS NULL AND m.status = 'Completed'
+                          AND (m.chief_id = b.id
+                               OR EXISTS (SELECT 1 FROM crew_memberships cm
+                                          WHERE cm.mission_id = m.id AND cm.brawler_id = b.id))) AS missions_completed
+                FROM brawlers b
+                WHERE b.deleted_at IS NULL
+                  AND ($1 IS NULL
+                       OR lower(b.display_name) LIKE $1
+                       OR lower(split_part(b.username, '@', 1)) LIKE $1)
+            )
+            SELECT id, display_name, avatar_url, joined_at, activity, card_power, missions_completed
+            FROM directory
+            WHERE ($2 IS NULL OR ({sort}, id) < ($2, $3))
+            ORDER BY {sort} DESC, id DESC
+            LIMIT $4
+            "#,
+            sort = sort.as_str(),
+        );
+
+        let pattern = search
+            .map(|search| search.trim().to_lowercase())
+            .filter(|search| !search.is_empty())
+            .map(|search| format!("%{}%", escape_like(&search)));
+
+        let rows = diesel::sql_query(sql)
+            .bind::<Nullable<Varchar>, _>(pattern)
+            .bind::<Nullable<BigInt>, _>(after.map(|(value, _)| value))
+            .bind::<Nullable<Int4>, _>(after.map(|(_, id)| id))
+            .bind::<BigInt, _>(limit)
+            .load::<DirectoryBrawlerModel>(&mut conn)
+            .await?;
+
+        Ok(rows)
+    }
+}
+
+// Undated missions are upcoming while they are still live. COALESCE keeps the predicate from
+// being NULL, which would drop the mission from both Upcoming and Past.
+const UPCOMING: &str = "COALESCE(m.mission_date >= now(), m.status IN ('Open', 'InProgress'))";
+
+// $1 brawler_id, $2 status, $3 name pattern
+fn my_missions_sql(view: MyMissionsView) -> String {
+    let led = "m.chief_id = $1";
+    let joined = "EXISTS (SELECT 1 FROM crew_memberships cm WHERE cm.mission_id = m.id AND cm.brawler_id = $1)";
+    let scope = match view {
+        MyMissionsView::All => format!("({} OR {})", led, joined),
+        MyMissionsView::Leading => led.to_string(),
+        MyMissionsView::Joined => joined.to_string(),
+        MyMissionsView::Upcoming => format!("({} OR {}) AND {}", led, joined, UPCOMING),
+        MyMissionsView::Past => format!("({} OR {}) AND NOT {}", led, joined, UPCOMING),
+    };
+
+    format!(
+        r#"
+        SELECT {}
+        FROM missions m
+        JOIN brawlers b ON m.chief_id = b.id
+        WHERE m.deleted_at IS NULL
+          AND {}
+          AND ($2 IS NULL OR m.status = $2)
+          AND ($3 IS NULL OR m.name ILIKE $3)
+        "#,
+        mission_listing::MISSION_COLUMNS,
+        scope
+    )
+}
+
+// User input is matched literally; `%` and `_` would otherwise act as wildcards.
+fn escape_like(input: &str) -> String {
+    input.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn past_is_exactly_the_missions_that_are_not_upcoming() {
+        assert!(my_missions_sql(MyMissionsView::Upcoming).contains(&format!(") AND {}", UPCOMING)));
+        assert!(my_missions_sql(MyMissionsView::Past).contains(&format!(") AND NOT {}", UPCOMING)));
+    }
+
+    #[test]
+    fn undated_finished_missions_are_classified_by_status() {
+        // `mission_date >= now()` is NULL without a date, and NOT NULL would hide a Completed or
+        // Failed mission from both views; the status has to decide instead.
+        assert!(UPCOMING.starts_with("COALESCE(m.mission_date >= now(), "));
+        assert!(UPCOMING.ends_with(", m.status IN ('Open', 'InProgress'))"));
+        assert!(!UPCOMING.contains("IS NULL AND"));
+    }
 }
@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use diesel::prelude::*;
 use diesel_async::RunQueryDsl;
 
-use crate::domain::entities::cards::{CardEntity, UserCardEntity, NewUserCardEntity, UserCardDetail};
+use crate::domain::entities::cards::{CardDraftEntity, CardEntity, UserCardEntity, NewUserCardEntity, UserCardDetail};
 use crate::domain::entities::battles::{BattleEntity, NewBattleEntity};
 use crate::domain::repositories::cards::CardRepository;
 use crate::infrastructure::database::postgresql_connection::PgPoolSquad;
@@ -90,6 +90,43 @@ impl CardRepository for CardPostgres {
         Ok(UserCardDetail { user_card: uc, card: c })
     }
 
+    async fn add_card(&self, card: CardDraftEntity) -> Result<CardEntity> {
+        let mut conn = self.pool.get().await?;
+        let result = diesel::insert_into(cards::table)
+            .values(&card)
+            .get_result::<CardEntity>(&mut conn)
+            .await?;
+        Ok(result)
+    }
+
+    async fn update_card(&self, card_id_val: i32, card: CardDraftEntity) -> Result<CardEntity> {
+        let mut conn = self.pool.get().await?;
+        let result = diesel::update(cards::table.find(card_id_val))
+            .set(&card)
+            .get_result::<CardEntity>(&mut conn)
+            .await?;
+        Ok(result)
+    }
+
+    async fn delete_card(&self, card_id_val: i32) -> Result<()> {
+        let mut conn = self.pool.get().await?;
+
+        // Cards already drawn by brawlers stay in the catalog so inventories remain valid
+        let owned = user_cards::table
+            .filter(user_cards::card_id.eq(card_id_val))
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+        if owned > 0 {
+            return Err(anyhow!("Card is owned by {} brawlers and cannot be deleted", owned));
+        }
+
+        diesel::delete(cards::table.find(card_id_val))
+            .execute(&mut conn)
+            .await?;
+        Ok(())
+    }
+
     async fn create_battle(&self, battle: NewBattleEntity) -> Result<BattleEntity> {
         let mut conn = self.pool.get().await?;
         let result = diesel::insert_into(battles::table)
@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{insert_into, update};
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::email_verification_tokens::{NewEmailVerificationTokenEntity, EmailVerificationTokenEntity},
+    repositories::email_verification_tokens::EmailVerificationTokenRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::email_verification_tokens,
+};
+
+pub struct EmailVerificationTokenPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl EmailVerificationTokenPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl EmailVerificationTokenRepository for EmailVerificationTokenPostgres {
+    async fn create(&self, new_token: NewEmailVerificationTokenEntity) -> Result<i32> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = insert_into(email_verification_tokens::table)
+            .values(&new_token)
+            .returning(email_verification_tokens::id)
+            .get_result::<i32>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_valid_by_hash(&self, token_hash: String) -> Result<EmailVerificationTokenEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = email_verification_tokens::table
+            .filter(email_verification_tokens::token_hash.eq(token_hash))
+            .filter(email_verification_tokens::used_at.is_null())
+            .filter(email_verification_tokens::expires_at.gt(diesel::dsl::now))
+            .select(EmailVerificationTokenEntity::as_select())
+            .first::<EmailVerificationTokenEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn mark_used(&self, token_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(email_verification_tokens::table.filter(email_verification_tokens::id.eq(token_id)))
+            .filter(email_verification_tokens::used_at.is_null())
+            .set(email_verification_tokens::used_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Verification token has already been used"));
+        }
+
+        Ok(())
+    }
+
+    async fn invalidate_all_for_brawler(&self, brawler_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(email_verification_tokens::table.filter(email_verification_tokens::brawler_id.eq(brawler_id)))
+            .filter(email_verification_tokens::used_at.is_null())
+            .set(email_verification_tokens::used_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+}
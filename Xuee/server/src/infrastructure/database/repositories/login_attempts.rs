@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::{delete, insert_into, update};
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::login_attempts::{LoginAttemptEntity, NewLoginAttemptEntity},
+    repositories::login_attempts::LoginAttemptRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::login_attempts,
+};
+
+pub struct LoginAttemptPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl LoginAttemptPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl LoginAttemptRepository for LoginAttemptPostgres {
+    async fn find(&self, scope: String, identifier: String) -> Result<Option<LoginAttemptEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = login_attempts::table
+            .filter(login_attempts::scope.eq(scope))
+            .filter(login_attempts::identifier.eq(identifier))
+            .select(LoginAttemptEntity::as_select())
+            .first::<LoginAttemptEntity>(&mut connection)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn record_failure(&self, scope: String, identifier: String) -> Result<LoginAttemptEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        // A single upsert keeps concurrent guesses from under-counting.
+        let result = insert_into(login_attempts::table)
+            .values(&NewLoginAttemptEntity {
+                scope,
+                identifier,
+                failed_count: 1,
+                last_failed_at: Some(chrono::Utc::now().naive_utc()),
+            })
+            .on_conflict((login_attempts::scope, login_attempts::identifier))
+            .do_update()
+            .set((
+                login_attempts::failed_count.eq(login_attempts::failed_count + 1),
+                login_attempts::last_failed_at.eq(diesel::dsl::now.nullable()),
+            ))
+            .returning(LoginAttemptEntity::as_returning())
+            .get_result::<LoginAttemptEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn lock(&self, scope: String, identifier: String, locked_until: NaiveDateTime) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        // The counter restarts so the backoff begins afresh once the lockout ends.
+        update(login_attempts::table)
+            .filter(login_attempts::scope.eq(scope))
+            .filter(login_attempts::identifier.eq(identifier))
+            .set((
+                login_attempts::failed_count.eq(0),
+                login_attempts::locked_until.eq(locked_until),
+            ))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn clear(&self, scope: String, identifier: String) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        delete(login_attempts::table)
+            .filter(login_attempts::scope.eq(scope))
+            .filter(login_attempts::identifier.eq(identifier))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+}
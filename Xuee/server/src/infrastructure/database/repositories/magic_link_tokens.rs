@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{insert_into, update};
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::magic_link_tokens::{NewMagicLinkTokenEntity, MagicLinkTokenEntity},
+    repositories::magic_link_tokens::MagicLinkTokenRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::magic_link_tokens,
+};
+
+pub struct MagicLinkTokenPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl MagicLinkTokenPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl MagicLinkTokenRepository for MagicLinkTokenPostgres {
+    async fn create(&self, new_token: NewMagicLinkTokenEntity) -> Result<i32> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = insert_into(magic_link_tokens::table)
+            .values(&new_token)
+            .returning(magic_link_tokens::id)
+            .get_result::<i32>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_valid_by_hash(&self, token_hash: String) -> Result<MagicLinkTokenEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = magic_link_tokens::table
+            .filter(magic_link_tokens::token_hash.eq(token_hash))
+            .filter(magic_link_tokens::used_at.is_null())
+            .filter(magic_link_tokens::expires_at.gt(diesel::dsl::now))
+            .select(MagicLinkTokenEntity::as_select())
+            .first::<MagicLinkTokenEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn mark_used(&self, token_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(magic_link_tokens::table.filter(magic_link_tokens::id.eq(token_id)))
+            .filter(magic_link_tokens::used_at.is_null())
+            .set(magic_link_tokens::used_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Magic link has already been used"));
+        }
+
+        Ok(())
+    }
+
+    async fn invalidate_all_for_brawler(&self, brawler_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(magic_link_tokens::table.filter(magic_link_tokens::brawler_id.eq(brawler_id)))
+            .filter(magic_link_tokens::used_at.is_null())
+            .set(magic_link_tokens::used_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+}
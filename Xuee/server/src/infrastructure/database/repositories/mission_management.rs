@@ -72,4 +72,22 @@ impl MissionManagementRepository for MisssionManagementPostgres {
         Ok(())
 
     }
+
+    async fn moderate_remove(&self, mission_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await?;
+
+        // Moderators may take down a mission in any status, not just open ones
+        let affected = diesel::update(missions::table)
+            .filter(missions::id.eq(mission_id))
+            .filter(missions::deleted_at.is_null())
+            .set(missions::deleted_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Mission not found"));
+        }
+
+        Ok(())
+    }
 }
@@ -1,16 +1,17 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use diesel::prelude::*;
-use diesel::sql_types::{Int4, Nullable, Varchar};
+use diesel::sql_types::{BigInt, Int4, Nullable, Varchar};
 use diesel_async::RunQueryDsl;
 use std::sync::Arc;
 
 use crate::domain::value_objects::{
     mission_model::MissionModel,
-    mission_filter::MissionFilter,
+    mission_filter::{MissionFilter, DEFAULT_MISSION_ORDERING},
     brawler_model::BrawlerModel,
 };
 use crate::domain::repositories::mission_viewing::MissionViewingRepository;
+use crate::infrastructure::database::mission_listing::{self, TotalCount};
 use crate::infrastructure::database::postgresql_connection::PgPoolSquad;
 use crate::infrastructure::database::schema::crew_memberships;
 
@@ -52,18 +53,9 @@ impl MissionViewingRepository for MissionViewingPostgres {
     async fn gets(&self, filter: &MissionFilter) -> Result<Vec<MissionModel>> {
         let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
 
-        let sql = r#"
-            SELECT 
-                m.id, m.name, m.description, m.status, m.chief_id, 
-                b.display_name as chief_display_name,
-                (SELECT COUNT(*) FROM crew_memberships cm WHERE cm.mission_id = m.id) as crew_count,
-                m.mission_date, m.time, m.email, m.phone, m.location, m.rewards,
-                m.created_at, m.updated_at
-            FROM missions m
-            JOIN brawlers b ON m.chief_id = b.id
-            WHERE ($1 IS NULL OR m.status = $1)
-              AND ($2 IS NULL OR m.name ILIKE $2)
-        "#;
+        let ordering = filter.ordering(DEFAULT_MISSION_ORDERING);
+        let after = filter.after(ordering)?;
+        let sql = mission_listing::page_sql(&filtered_missions_sql(), ordering, 4);
 
         let status_bind = filter.status.as_ref().map(|s| s.to_string());
         let name_bind = filter.name.as_ref().map(|n| format!("%{}%", n));
@@ -71,12 +63,32 @@ impl MissionViewingRepository for MissionViewingPostgres {
         let rows = diesel::sql_query(sql)
             .bind::<Nullable<Varchar>, _>(status_bind)
             .bind::<Nullable<Varchar>, _>(name_bind)
+            .bind::<Nullable<Int4>, _>(filter.friends_of)
+            .bind::<Nullable<Varchar>, _>(after.as_ref().map(|cursor| cursor.value.clone()))
+            .bind::<Nullable<Int4>, _>(after.as_ref().map(|cursor| cursor.id))
+            .bind::<BigInt, _>(filter.page_size() + 1)
             .load::<MissionModel>(&mut conn)
             .await?;
 
         Ok(rows)
     }
 
+    async fn count(&self, filter: &MissionFilter) -> Result<i64> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let status_bind = filter.status.as_ref().map(|s| s.to_string());
+        let name_bind = filter.name.as_ref().map(|n| format!("%{}%", n));
+
+        let result = diesel::sql_query(mission_listing::count_sql(&filtered_missions_sql()))
+            .bind::<Nullable<Varchar>, _>(status_bind)
+            .bind::<Nullable<Varchar>, _>(name_bind)
+            .bind::<Nullable<Int4>, _>(filter.friends_of)
+            .get_result::<TotalCount>(&mut conn)
+            .await?;
+
+        Ok(result.total)
+    }
+
     async fn crew_counting(&self, mission_id: i32) -> Result<u32> {
         let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
         
@@ -107,3 +119,28 @@ impl MissionViewingRepository for MissionViewingPostgres {
         Ok(crew)
     }
 }
+
+// $1 status, $2 name pattern, $3 friends_of
+fn filtered_missions_sql() -> String {
+    format!(
+        r#"
+        SELECT {}
+        FROM missions m
+        JOIN brawlers b ON m.chief_id = b.id
+        WHERE m.deleted_at IS NULL
+          AND ($1 IS NULL OR m.status = $1)
+          AND ($2 IS NULL OR m.name ILIKE $2)
+          AND ($3 IS NULL OR EXISTS (
+              SELECT 1
+              FROM brawler_follows f1
+              JOIN brawler_follows f2 ON f2.follower_id = f1.followee_id AND f2.followee_id = f1.follower_id
+              WHERE f1.follower_id = $3
+                AND (f1.followee_id = m.chief_id OR EXISTS (
+                    SELECT 1 FROM crew_memberships cm
+                    WHERE cm.mission_id = m.id AND cm.brawler_id = f1.followee_id
+                ))
+          ))
+        "#,
+        mission_listing::MISSION_COLUMNS
+    )
+}
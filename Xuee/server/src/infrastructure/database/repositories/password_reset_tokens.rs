@@ -0,0 +1,82 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{insert_into, update};
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::password_reset_tokens::{NewPasswordResetTokenEntity, PasswordResetTokenEntity},
+    repositories::password_reset_tokens::PasswordResetTokenRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::password_reset_tokens,
+};
+
+pub struct PasswordResetTokenPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl PasswordResetTokenPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl PasswordResetTokenRepository for PasswordResetTokenPostgres {
+    async fn create(&self, new_token: NewPasswordResetTokenEntity) -> Result<i32> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = insert_into(password_reset_tokens::table)
+            .values(&new_token)
+            .returning(password_reset_tokens::id)
+            .get_result::<i32>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_valid_by_hash(&self, token_hash: String) -> Result<PasswordResetTokenEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = password_reset_tokens::table
+            .filter(password_reset_tokens::token_hash.eq(token_hash))
+            .filter(password_reset_tokens::used_at.is_null())
+            .filter(password_reset_tokens::expires_at.gt(diesel::dsl::now))
+            .select(PasswordResetTokenEntity::as_select())
+            .first::<PasswordResetTokenEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn mark_used(&self, token_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(password_reset_tokens::table.filter(password_reset_tokens::id.eq(token_id)))
+            .filter(password_reset_tokens::used_at.is_null())
+            .set(password_reset_tokens::used_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Reset token has already been used"));
+        }
+
+        Ok(())
+    }
+
+    async fn invalidate_all_for_brawler(&self, brawler_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(password_reset_tokens::table.filter(password_reset_tokens::brawler_id.eq(brawler_id)))
+            .filter(password_reset_tokens::used_at.is_null())
+            .set(password_reset_tokens::used_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::dsl::IntervalDsl;
+use diesel::prelude::*;
+use diesel::{insert_into, update};
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::sessions::{NewSessionEntity, SessionEntity},
+    repositories::sessions::SessionRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::sessions,
+};
+
+pub struct SessionPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl SessionPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl SessionRepository for SessionPostgres {
+    async fn create(&self, new_session: NewSessionEntity) -> Result<i32> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = insert_into(sessions::table)
+            .values(&new_session)
+            .returning(sessions::id)
+            .get_result::<i32>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_active_by_refresh_token_hash(&self, refresh_token_hash: String) -> Result<SessionEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = sessions::table
+            .filter(sessions::refresh_token_hash.eq(refresh_token_hash))
+            .filter(sessions::revoked_at.is_null())
+            .filter(sessions::expires_at.gt(diesel::dsl::now))
+            .select(SessionEntity::as_select())
+            .first::<SessionEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn rotate(&self, session_id: i32, current_hash: String, new_hash: String, expires_at: NaiveDateTime, ip_address: Option<String>) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        // Matching on the current hash makes a concurrent refresh with the same token lose the race.
+        let affected = update(sessions::table.filter(sessions::id.eq(session_id)))
+            .filter(sessions::refresh_token_hash.eq(current_hash))
+            .filter(sessions::revoked_at.is_null())
+            .set((
+                sessions::refresh_token_hash.eq(new_hash),
+                sessions::expires_at.eq(expires_at),
+                sessions::last_seen_at.eq(diesel::dsl::now),
+                sessions::ip_address.eq(ip_address),
+            ))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Refresh token has already been used"));
+        }
+
+        Ok(())
+    }
+
+    async fn list_active_for_brawler(&self, brawler_id: i32) -> Result<Vec<SessionEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let results = sessions::table
+            .filter(sessions::brawler_id.eq(brawler_id))
+            .filter(sessions::revoked_at.is_null())
+            .filter(sessions::expires_at.gt(diesel::dsl::now))
+            .order(sessions::last_seen_at.desc())
+            .select(SessionEntity::as_select())
+            .load::<SessionEntity>(&mut connection)
+            .await?;
+
+        Ok(results)
+    }
+
+    async fn revoke(&self, session_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(sessions::table.filter(sessions::id.eq(session_id)))
+            .filter(sessions::revoked_at.is_null())
+            .set(sessions::revoked_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn revoke_for_brawler(&self, brawler_id: i32, session_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(sessions::table.filter(sessions::id.eq(session_id)))
+            .filter(sessions::brawler_id.eq(brawler_id))
+            .filter(sessions::revoked_at.is_null())
+            .set(sessions::revoked_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Session not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn revoke_others(&self, brawler_id: i32, keep_session_id: i32) -> Result<usize> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(sessions::table.filter(sessions::brawler_id.eq(brawler_id)))
+            .filter(sessions::id.ne(keep_session_id))
+            .filter(sessions::revoked_at.is_null())
+            .set(sessions::revoked_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(affected)
+    }
+
+    async fn revoke_all_for_brawler(&self, brawler_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(sessions::table.filter(sessions::brawler_id.eq(brawler_id)))
+            .filter(sessions::revoked_at.is_null())
+            .set(sessions::revoked_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn is_active(&self, session_id: i32) -> Result<bool> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let count = sessions::table
+            .filter(sessions::id.eq(session_id))
+            .filter(sessions::revoked_at.is_null())
+            .filter(sessions::expires_at.gt(diesel::dsl::now))
+            .count()
+            .get_result::<i64>(&mut connection)
+            .await?;
+
+        Ok(count > 0)
+    }
+
+    // Runs on every authenticated request, so the write is skipped while last_seen_at is fresh.
+    async fn touch(&self, session_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(sessions::table.filter(sessions::id.eq(session_id)))
+            .filter(sessions::last_seen_at.lt(diesel::dsl::now - 1.minute()))
+            .set(sessions::last_seen_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::sql_types::Int4;
+use diesel::{delete, insert_into, update};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+
+use crate::domain::{
+    entities::social::{FriendRequestEntity, NewFollowEntity, NewFriendRequestEntity},
+    repositories::social::SocialRepository,
+    value_objects::social_model::{
+        FriendRequestDirection, FriendRequestModel, FriendRequestStatus, SocialBrawlerModel,
+    },
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::{brawler_follows, friend_requests},
+};
+
+// Friends are brawlers who follow each other; the pair of edges is the friendship.
+const FRIENDS_SQL: &str = r#"
+    SELECT b.id, b.display_name, b.avatar_url, GREATEST(f1.created_at, f2.created_at) AS since
+    FROM brawler_follows f1
+    JOIN brawler_follows f2 ON f2.follower_id = f1.followee_id AND f2.followee_id = f1.follower_id
+    JOIN brawlers b ON b.id = f1.followee_id
+    WHERE f1.follower_id = $1
+      AND b.deleted_at IS NULL
+    ORDER BY since DESC
+"#;
+
+pub struct SocialPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl SocialPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl SocialRepository for SocialPostgres {
+    async fn follow(&self, follow: NewFollowEntity) -> Result<bool> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let inserted = insert_into(brawler_follows::table)
+            .values(&follow)
+            .on_conflict_do_nothing()
+            .execute(&mut conn)
+            .await?;
+
+        Ok(inserted > 0)
+    }
+
+    async fn unfollow(&self, follower_id: i32, followee_id: i32) -> Result<bool> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let deleted = delete(brawler_follows::table)
+            .filter(brawler_follows::follower_id.eq(follower_id))
+            .filter(brawler_follows::followee_id.eq(followee_id))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn list_followers(&self, brawler_id: i32) -> Result<Vec<SocialBrawlerModel>> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let sql = r#"
+            SELECT b.id, b.display_name, b.avatar_url, f.created_at AS since
+            FROM brawler_follows f
+            JOIN brawlers b ON b.id = f.follower_id
+            WHERE f.followee_id = $1
+              AND b.deleted_at IS NULL
+            ORDER BY f.created_at DESC
+        "#;
+
+        let rows = diesel::sql_query(sql)
+            .bind::<Int4, _>(brawler_id)
+            .load::<SocialBrawlerModel>(&mut conn)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_following(&self, brawler_id: i32) -> Result<Vec<SocialBrawlerModel>> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let sql = r#"
+            SELECT b.id, b.display_name, b.avatar_url, f.created_at AS since
+            FROM brawler_follows f
+            JOIN brawlers b ON b.id = f.followee_id
+            WHERE f.follower_id = $1
+              AND b.deleted_at IS NULL
+            ORDER BY f.created_at DESC
+        "#;
+
+        let rows = diesel::sql_query(sql)
+            .bind::<Int4, _>(brawler_id)
+            .load::<SocialBrawlerModel>(&mut conn)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn list_friends(&self, brawler_id: i32) -> Result<Vec<SocialBrawlerModel>> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let rows = diesel::sql_query(FRIENDS_SQL)
+            .bind::<Int4, _>(brawler_id)
+            .load::<SocialBrawlerModel>(&mut conn)
+            .await?;
+
+        Ok(rows)
+    }
+
+    async fn are_friends(&self, brawler_id: i32, other_id: i32) -> Result<bool> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let edges = brawler_follows::table
+            .filter(
+                brawler_follows::follower_id
+                    .eq(brawler_id)
+                    .and(brawler_follows::followee_id.eq(other_id))
+                    .or(brawler_follows::follower_id.eq(other_id).and(brawler_follows::followee_id.eq(brawler_id))),
+            )
+            .count()
+            .get_result::<i64>(&mut conn)
+            .await?;
+
+        Ok(edges == 2)
+    }
+
+    async fn remove_friend(&self, brawler_id: i32, other_id: i32) -> Result<bool> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let deleted = delete(brawler_follows::table)
+            .filter(
+                brawler_follows::follower_id
+                    .eq(brawler_id)
+                    .and(brawler_follows::followee_id.eq(other_id))
+                    .or(brawler_follows::follower_id.eq(other_id).and(brawler_follows::followee_id.eq(brawler_id))),
+            )
+            .execute(&mut conn)
+            .await?;
+
+        Ok(deleted > 0)
+    }
+
+    async fn create_friend_request(&self, request: NewFriendRequestEntity) -> Result<i32> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let id = insert_into(friend_requests::table)
+            .values(&request)
+            .returning(friend_requests::id)
+            .get_result::<i32>(&mut conn)
+            .await?;
+
+        Ok(id)
+    }
+
+    async fn find_friend_request(&self, id: i32) -> Result<FriendRequestEntity> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = friend_requests::table
+            .filter(friend_requests::id.eq(id))
+            .select(FriendRequestEntity::as_select())
+            .first::<FriendRequestEntity>(&mut conn)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_pending_friend_request(&self, requester_id: i32, addressee_id: i32) -> Result<Option<FriendRequestEntity>> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = friend_requests::table
+            .filter(friend_requests::requester_id.eq(requester_id))
+            .filter(friend_requests::addressee_id.eq(addressee_id))
+            .filter(friend_requests::status.eq(FriendRequestStatus::Pending.to_string()))
+            .select(FriendRequestEntity::as_select())
+            .first::<FriendRequestEntity>(&mut conn)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    // Accepting writes both follow edges in the same transaction as the answer.
+    async fn respond_to_friend_request(&self, id: i32, status: FriendRequestStatus) -> Result<()> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            async move {
+                let request = update(friend_requests::table)
+                    .filter(friend_requests::id.eq(id))
+                    .filter(friend_requests::status.eq(FriendRequestStatus::Pending.to_string()))
+                    .set((
+                        friend_requests::status.eq(status.to_string()),
+                        friend_requests::responded_at.eq(diesel::dsl::now),
+                    ))
+                    .returning(FriendRequestEntity::as_returning())
+                    .get_result::<FriendRequestEntity>(conn)
+                    .await
+                    .optional()?
+                    .ok_or_else(|| anyhow::anyhow!("Friend request is no longer pending"))?;
+
+                if status == FriendRequestStatus::Accepted {
+                    insert_into(brawler_follows::table)
+                        .values(&vec![
+                            NewFollowEntity { follower_id: request.requester_id, followee_id: request.addressee_id },
+                            NewFollowEntity { follower_id: request.addressee_id, followee_id: request.requester_id },
+                        ])
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .await?;
+                }
+
+                Ok(())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
+    async fn delete_friend_request(&self, id: i32) -> Result<()> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        delete(friend_requests::table.filter(friend_requests::id.eq(id)))
+            .execute(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_friend_requests(&self, brawler_id: i32, direction: FriendRequestDirection) -> Result<Vec<FriendRequestModel>> {
+        let mut conn = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let (own_column, other_column) = match direction {
+            FriendRequestDirection::Incoming => ("addressee_id", "requester_id"),
+            FriendRequestDirection::Outgoing => ("requester_id", "addressee_id"),
+        };
+
+        let sql = format!(
+            r#"
+            SELECT fr.id, b.id AS brawler_id, b.display_name, b.avatar_url, fr.created_at
+            FROM friend_requests fr
+            JOIN brawlers b ON b.id = fr.{other}
+            WHERE fr.{own} = $1
+              AND fr.status = 'Pending'
+              AND b.deleted_at IS NULL
+            ORDER BY fr.created_at DESC
+            "#,
+            own = own_column,
+            other = other_column,
+        );
+
+        let rows = diesel::sql_query(sql)
+            .bind::<Int4, _>(brawler_id)
+            .load::<FriendRequestModel>(&mut conn)
+            .await?;
+
+        Ok(rows)
+    }
+}
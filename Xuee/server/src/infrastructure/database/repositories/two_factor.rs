@@ -0,0 +1,169 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::{delete, insert_into, update};
+use diesel_async::{AsyncConnection, RunQueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+
+use crate::domain::{
+    entities::two_factor::{NewRecoveryCodeEntity, NewTwoFactorEntity, TwoFactorEntity},
+    repositories::two_factor::TwoFactorRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::{brawler_recovery_codes, brawler_two_factor},
+};
+
+pub struct TwoFactorPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl TwoFactorPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for TwoFactorPostgres {
+    async fn find_by_brawler_id(&self, brawler_id: i32) -> Result<Option<TwoFactorEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = brawler_two_factor::table
+            .filter(brawler_two_factor::brawler_id.eq(brawler_id))
+            .select(TwoFactorEntity::as_select())
+            .first::<TwoFactorEntity>(&mut connection)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn upsert_pending(&self, new_two_factor: NewTwoFactorEntity) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        connection
+            .transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    // Re-enrolling replaces any unconfirmed secret, but never an enabled one.
+                    let enabled_at = brawler_two_factor::table
+                        .filter(brawler_two_factor::brawler_id.eq(new_two_factor.brawler_id))
+                        .select(brawler_two_factor::enabled_at)
+                        .for_update()
+                        .first::<Option<chrono::NaiveDateTime>>(conn)
+                        .await
+                        .optional()?;
+                    if let Some(Some(_)) = enabled_at {
+                        return Err(anyhow::anyhow!("Two-factor authentication is already enabled"));
+                    }
+
+                    insert_into(brawler_two_factor::table)
+                        .values(&new_two_factor)
+                        .on_conflict(brawler_two_factor::brawler_id)
+                        .do_update()
+                        .set((
+                            brawler_two_factor::secret_ciphertext.eq(&new_two_factor.secret_ciphertext),
+                            brawler_two_factor::last_used_step.eq(None::<i64>),
+                        ))
+                        .execute(conn)
+                        .await?;
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn enable(&self, brawler_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(brawler_two_factor::table.filter(brawler_two_factor::brawler_id.eq(brawler_id)))
+            .filter(brawler_two_factor::enabled_at.is_null())
+            .set(brawler_two_factor::enabled_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn record_used_step(&self, brawler_id: i32, step: i64) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        // Only move forward, so a code cannot be replayed inside its validity window.
+        let affected = update(brawler_two_factor::table.filter(brawler_two_factor::brawler_id.eq(brawler_id)))
+            .filter(
+                brawler_two_factor::last_used_step
+                    .is_null()
+                    .or(brawler_two_factor::last_used_step.lt(step)),
+            )
+            .set(brawler_two_factor::last_used_step.eq(step))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Two-factor code has already been used"));
+        }
+
+        Ok(())
+    }
+
+    async fn remove(&self, brawler_id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        connection
+            .transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    delete(brawler_recovery_codes::table.filter(brawler_recovery_codes::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    delete(brawler_two_factor::table.filter(brawler_two_factor::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn replace_recovery_codes(&self, brawler_id: i32, recovery_codes: Vec<NewRecoveryCodeEntity>) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        connection
+            .transaction::<_, anyhow::Error, _>(|conn| {
+                async move {
+                    delete(brawler_recovery_codes::table.filter(brawler_recovery_codes::brawler_id.eq(brawler_id)))
+                        .execute(conn)
+                        .await?;
+                    insert_into(brawler_recovery_codes::table)
+                        .values(&recovery_codes)
+                        .execute(conn)
+                        .await?;
+                    Ok(())
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    async fn consume_recovery_code(&self, brawler_id: i32, code_hash: String) -> Result<bool> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = update(brawler_recovery_codes::table)
+            .filter(brawler_recovery_codes::brawler_id.eq(brawler_id))
+            .filter(brawler_recovery_codes::code_hash.eq(code_hash))
+            .filter(brawler_recovery_codes::used_at.is_null())
+            .set(brawler_recovery_codes::used_at.eq(diesel::dsl::now))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(affected > 0)
+    }
+}
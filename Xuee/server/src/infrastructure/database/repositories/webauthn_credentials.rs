@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::{delete, insert_into, update};
+use diesel_async::RunQueryDsl;
+
+use crate::domain::{
+    entities::webauthn_credentials::{NewWebAuthnCredentialEntity, WebAuthnCredentialEntity},
+    repositories::webauthn_credentials::WebAuthnCredentialRepository,
+};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    schema::{webauthn_challenges, webauthn_credentials},
+};
+
+pub struct WebAuthnCredentialPostgres {
+    db_pool: Arc<PgPoolSquad>,
+}
+
+impl WebAuthnCredentialPostgres {
+    pub fn new(db_pool: Arc<PgPoolSquad>) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl WebAuthnCredentialRepository for WebAuthnCredentialPostgres {
+    async fn create(&self, new_credential: NewWebAuthnCredentialEntity) -> Result<WebAuthnCredentialEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = insert_into(webauthn_credentials::table)
+            .values(&new_credential)
+            .returning(WebAuthnCredentialEntity::as_returning())
+            .get_result::<WebAuthnCredentialEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn list_for_brawler(&self, brawler_id: i32) -> Result<Vec<WebAuthnCredentialEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = webauthn_credentials::table
+            .filter(webauthn_credentials::brawler_id.eq(brawler_id))
+            .order(webauthn_credentials::created_at.asc())
+            .select(WebAuthnCredentialEntity::as_select())
+            .load::<WebAuthnCredentialEntity>(&mut connection)
+            .await?;
+
+        Ok(result)
+    }
+
+    async fn find_by_credential_id(&self, credential_id: String) -> Result<Option<WebAuthnCredentialEntity>> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = webauthn_credentials::table
+            .filter(webauthn_credentials::credential_id.eq(credential_id))
+            .select(WebAuthnCredentialEntity::as_select())
+            .first::<WebAuthnCredentialEntity>(&mut connection)
+            .await
+            .optional()?;
+
+        Ok(result)
+    }
+
+    async fn record_use(&self, id: i32, sign_count: i64) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        update(webauthn_credentials::table.filter(webauthn_credentials::id.eq(id)))
+            .set((
+                webauthn_credentials::sign_count.eq(sign_count),
+                webauthn_credentials::last_used_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut connection)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn rename(&self, brawler_id: i32, id: i32, name: String) -> Result<WebAuthnCredentialEntity> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let result = update(webauthn_credentials::table.filter(webauthn_credentials::id.eq(id)))
+            .filter(webauthn_credentials::brawler_id.eq(brawler_id))
+            .set(webauthn_credentials::name.eq(name))
+            .returning(WebAuthnCredentialEntity::as_returning())
+            .get_result::<WebAuthnCredentialEntity>(&mut connection)
+            .await
+            .optional()?;
+
+        result.ok_or_else(|| anyhow::anyhow!("Passkey not found"))
+    }
+
+    async fn remove(&self, brawler_id: i32, id: i32) -> Result<()> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        let affected = delete(webauthn_credentials::table.filter(webauthn_credentials::id.eq(id)))
+            .filter(webauthn_credentials::brawler_id.eq(brawler_id))
+            .execute(&mut connection)
+            .await?;
+
+        if affected == 0 {
+            return Err(anyhow::anyhow!("Passkey not found"));
+        }
+
+        Ok(())
+    }
+
+    async fn consume_challenge(&self, challenge: String, expires_at: NaiveDateTime) -> Result<bool> {
+        let mut connection = self.db_pool.get().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+        // Answered challenges only need remembering until they would have expired anyway
+        delete(webauthn_challenges::table.filter(webauthn_challenges::expires_at.le(diesel::dsl::now)))
+            .execute(&mut connection)
+            .await?;
+
+        let inserted = insert_into(webauthn_challenges::table)
+            .values((
+                webauthn_challenges::challenge.eq(challenge),
+                webauthn_challenges::expires_at.eq(expires_at),
+            ))
+            .on_conflict_do_nothing()
+            .execute(&mut connection)
+            .await?;
+
+        Ok(inserted == 1)
+    }
+}
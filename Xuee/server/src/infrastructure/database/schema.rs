@@ -15,6 +15,12 @@ diesel::table! {
         avatar_url -> Nullable<Varchar>,
         #[max_length = 255]
         avatar_public_id -> Nullable<Varchar>,
+        email_verified_at -> Nullable<Timestamp>,
+        #[max_length = 32]
+        role -> Varchar,
+        deletion_scheduled_for -> Nullable<Timestamp>,
+        deleted_at -> Nullable<Timestamp>,
+        username_changed_at -> Nullable<Timestamp>,
     }
 }
 
@@ -88,9 +94,218 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    magic_link_tokens (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 255]
+        token_hash -> Varchar,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        #[max_length = 255]
+        email -> Varchar,
+    }
+}
+
+diesel::table! {
+    password_reset_tokens (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 255]
+        token_hash -> Varchar,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        #[max_length = 255]
+        email -> Varchar,
+    }
+}
+
+diesel::table! {
+    email_verification_tokens (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 255]
+        token_hash -> Varchar,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        #[max_length = 255]
+        email -> Varchar,
+    }
+}
+
+diesel::table! {
+    brawler_two_factor (brawler_id) {
+        brawler_id -> Int4,
+        #[max_length = 255]
+        secret_ciphertext -> Varchar,
+        last_used_step -> Nullable<Int8>,
+        enabled_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    brawler_recovery_codes (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 255]
+        code_hash -> Varchar,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    api_tokens (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 16]
+        token_prefix -> Varchar,
+        #[max_length = 255]
+        token_hash -> Varchar,
+        scopes -> Array<Text>,
+        expires_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    auth_events (id) {
+        id -> Int4,
+        brawler_id -> Nullable<Int4>,
+        #[max_length = 255]
+        username -> Nullable<Varchar>,
+        #[max_length = 64]
+        event_type -> Varchar,
+        #[max_length = 16]
+        outcome -> Varchar,
+        reason -> Nullable<Text>,
+        #[max_length = 64]
+        provider -> Nullable<Varchar>,
+        #[max_length = 64]
+        ip_address -> Nullable<Varchar>,
+        user_agent -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    brawler_identities (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 64]
+        provider -> Varchar,
+        #[max_length = 255]
+        subject -> Varchar,
+        #[max_length = 255]
+        email -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    login_attempts (id) {
+        id -> Int4,
+        #[max_length = 16]
+        scope -> Varchar,
+        #[max_length = 255]
+        identifier -> Varchar,
+        failed_count -> Int4,
+        last_failed_at -> Nullable<Timestamp>,
+        locked_until -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    sessions (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 255]
+        refresh_token_hash -> Varchar,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        user_agent -> Nullable<Text>,
+        #[max_length = 45]
+        ip_address -> Nullable<Varchar>,
+        last_seen_at -> Timestamp,
+    }
+}
+
+
+
+
+
+diesel::table! {
+    webauthn_credentials (id) {
+        id -> Int4,
+        brawler_id -> Int4,
+        #[max_length = 1024]
+        credential_id -> Varchar,
+        public_key -> Bytea,
+        sign_count -> Int8,
+        #[max_length = 36]
+        aaguid -> Varchar,
+        #[max_length = 100]
+        name -> Varchar,
+        last_used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    username_redirects (id) {
+        id -> Int4,
+        #[max_length = 255]
+        old_username -> Varchar,
+        brawler_id -> Int4,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
 
+diesel::table! {
+    brawler_follows (follower_id, followee_id) {
+        follower_id -> Int4,
+        followee_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
 
+diesel::table! {
+    friend_requests (id) {
+        id -> Int4,
+        requester_id -> Int4,
+        addressee_id -> Int4,
+        #[max_length = 16]
+        status -> Varchar,
+        responded_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
 
+diesel::table! {
+    webauthn_challenges (challenge) {
+        #[max_length = 64]
+        challenge -> Varchar,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
 
 diesel::joinable!(crew_memberships -> brawlers (brawler_id));
 diesel::joinable!(crew_memberships -> missions (mission_id));
@@ -98,6 +313,17 @@ diesel::joinable!(missions -> brawlers (chief_id));
 diesel::joinable!(user_cards -> brawlers (user_id));
 diesel::joinable!(user_cards -> cards (card_id));
 diesel::joinable!(battles -> brawlers (attacker_id));
+diesel::joinable!(sessions -> brawlers (brawler_id));
+diesel::joinable!(password_reset_tokens -> brawlers (brawler_id));
+diesel::joinable!(email_verification_tokens -> brawlers (brawler_id));
+diesel::joinable!(brawler_two_factor -> brawlers (brawler_id));
+diesel::joinable!(brawler_recovery_codes -> brawlers (brawler_id));
+diesel::joinable!(brawler_identities -> brawlers (brawler_id));
+diesel::joinable!(api_tokens -> brawlers (brawler_id));
+diesel::joinable!(auth_events -> brawlers (brawler_id));
+diesel::joinable!(magic_link_tokens -> brawlers (brawler_id));
+diesel::joinable!(webauthn_credentials -> brawlers (brawler_id));
+diesel::joinable!(username_redirects -> brawlers (brawler_id));
 
 
 diesel::allow_tables_to_appear_in_same_query!(
@@ -107,5 +333,19 @@ diesel::allow_tables_to_appear_in_same_query!(
     battles,
     crew_memberships,
     missions,
-
+    sessions,
+    password_reset_tokens,
+    email_verification_tokens,
+    brawler_two_factor,
+    brawler_recovery_codes,
+    login_attempts,
+    brawler_identities,
+    api_tokens,
+    auth_events,
+    magic_link_tokens,
+    webauthn_credentials,
+    username_redirects,
+    brawler_follows,
+    friend_requests,
+    webauthn_challenges,
 );
@@ -0,0 +1,77 @@
+use axum::http::HeaderMap;
+use axum_extra::extract::cookie::{Cookie, CookieJar, Key, SameSite};
+use sha2::{Digest, Sha512};
+
+use crate::config::{config_loader::get_stage, stage::Stage};
+use crate::infrastructure::opaque_token;
+
+pub const SESSION_COOKIE: &str = "nebula_session";
+pub const REFRESH_COOKIE: &str = "nebula_refresh";
+// Angular's HttpClient XSRF support reads this cookie and echoes it in the header below.
+pub const CSRF_COOKIE: &str = "XSRF-TOKEN";
+pub const CSRF_HEADER: &str = "x-xsrf-token";
+
+const SESSION_COOKIE_PATH: &str = "/api";
+const REFRESH_COOKIE_PATH: &str = "/api/v1/authentication";
+
+// Key::from needs 64 bytes, so any configured secret is stretched through SHA-512.
+pub fn signing_key(secret: &str) -> Key {
+    Key::from(&Sha512::digest(secret.as_bytes()))
+}
+
+// Plain-http local development would otherwise never get the cookie back.
+pub fn secure_cookies() -> bool {
+    get_stage() != Stage::Local
+}
+
+// Sets the session, refresh and CSRF cookies for a freshly issued passport. Returns the CSRF
+// token as well, for clients on another origin that cannot read the cookie.
+pub fn add_session_cookies(
+    jar: CookieJar,
+    access_token: String,
+    access_ttl: i64,
+    refresh_token: String,
+    refresh_ttl: i64,
+) -> (CookieJar, String) {
+    let csrf_token = opaque_token::generate();
+
+    let session = Cookie::build((SESSION_COOKIE, access_token))
+        .path(SESSION_COOKIE_PATH)
+        .http_only(true)
+        .secure(secure_cookies())
+        .same_site(SameSite::Lax)
+        .max_age(cookie::time::Duration::seconds(access_ttl));
+    let refresh = Cookie::build((REFRESH_COOKIE, refresh_token))
+        .path(REFRESH_COOKIE_PATH)
+        .http_only(true)
+        .secure(secure_cookies())
+        .same_site(SameSite::Strict)
+        .max_age(cookie::time::Duration::seconds(refresh_ttl));
+    let csrf = Cookie::build((CSRF_COOKIE, csrf_token.clone()))
+        .path("/")
+        .secure(secure_cookies())
+        .same_site(SameSite::Lax)
+        .max_age(cookie::time::Duration::seconds(refresh_ttl));
+
+    (jar.add(session).add(refresh).add(csrf), csrf_token)
+}
+
+pub fn clear_session_cookies(jar: CookieJar) -> CookieJar {
+    jar.remove(Cookie::build(SESSION_COOKIE).path(SESSION_COOKIE_PATH))
+        .remove(Cookie::build(REFRESH_COOKIE).path(REFRESH_COOKIE_PATH))
+        .remove(Cookie::build(CSRF_COOKIE).path("/"))
+}
+
+// Double-submit check: a cross-site page can make the browser send the cookie but cannot read
+// it, so it cannot also put the same value in the header.
+pub fn csrf_token_matches(headers: &HeaderMap, jar: &CookieJar) -> bool {
+    let Some(cookie) = jar.get(CSRF_COOKIE) else {
+        return false;
+    };
+    let Some(header) = headers.get(CSRF_HEADER).and_then(|value| value.to_str().ok()) else {
+        return false;
+    };
+
+    // Compare digests so the check does not leak how much of the token matched
+    !cookie.value().is_empty() && opaque_token::hash(cookie.value()) == opaque_token::hash(header)
+}
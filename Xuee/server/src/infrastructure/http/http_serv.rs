@@ -2,11 +2,11 @@ use std::{net::SocketAddr, sync::Arc};
 use anyhow::Result;
 use axum::{
     Router,
-    http::{Method, StatusCode},
+    http::{HeaderName, HeaderValue, Method, StatusCode},
 };
 use tokio::net::TcpListener;
 use tower_http::{
-    cors::{Any, CorsLayer},
+    cors::{AllowHeaders, AllowOrigin, CorsLayer},
     limit::RequestBodyLimitLayer,
     services::{ServeDir, ServeFile},
     trace::TraceLayer,
@@ -17,7 +17,8 @@ use crate::{
     config::config_model::DotEnvyConfig,
     infrastructure::{
         database::postgresql_connection::PgPoolSquad,
-        http::routers,
+        http::{cookies::CSRF_HEADER, routers},
+        services::image_storage::ConfiguredImageStorage,
     },
 };
 
@@ -27,24 +28,48 @@ fn static_serve() -> Router {
     Router::new().fallback_service(service)
 }
 
-fn api_serve(db_pool: Arc<PgPoolSquad>) -> Router {
-    Router::new()
-        .nest("/v1", Router::new()
-            .nest("/authentication", routers::authentication::router(db_pool.clone()))
-            .nest("/brawlers", routers::brawlers::router(db_pool.clone()))
-            .nest("/missions", routers::missions::router(db_pool.clone()))
-            .nest("/mission-management", routers::mission_management::router(db_pool.clone()))
-            .nest("/debug", routers::debug::router(db_pool.clone()))
-            .nest("/cards", routers::cards::router(db_pool.clone()))
+fn api_serve(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>, image_storage: Arc<ConfiguredImageStorage>) -> Router {
+    let v1 = Router::new()
+        .nest("/authentication", routers::authentication::router(db_pool.clone(), config.clone()))
+        .nest("/authentication/2fa", routers::two_factor::router(db_pool.clone()))
+        .nest("/authentication/passkeys", routers::passkeys::router(db_pool.clone(), config.clone()))
+        .nest("/brawlers", routers::brawlers::router(db_pool.clone(), image_storage.clone()))
+        .nest("/missions", routers::missions::router(db_pool.clone()))
+        .nest("/mission-management", routers::mission_management::router(db_pool.clone()))
+        .nest("/debug", routers::debug::router(db_pool.clone()))
+        .nest("/cards", routers::cards::router(db_pool.clone()))
+        .nest("/admin", routers::admin::router(db_pool.clone()))
+        .nest("/api-tokens", routers::api_tokens::router(db_pool.clone()))
+        .nest("/account", routers::accounts::router(db_pool.clone(), image_storage))
+        .nest("/social", routers::social::router(db_pool.clone()));
 
-        )
+    // Only exists in builds with the `dev-identity` feature, and only mounts under Stage::Local
+    #[cfg(feature = "dev-identity")]
+    let v1 = match routers::dev_identity::router(db_pool.clone(), config.clone()) {
+        Some(dev_router) => v1.nest("/authentication/dev", dev_router),
+        None => v1,
+    };
+
+    Router::new()
+        .nest("/v1", v1)
         .fallback(|| async { (StatusCode::NOT_FOUND, "API route not found") })
 }
 
-pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Result<()> {
+pub async fn start(
+    config: Arc<DotEnvyConfig>,
+    db_pool: Arc<PgPoolSquad>,
+    image_storage: Arc<ConfiguredImageStorage>,
+) -> Result<()> {
+    let allowed_origins = config
+        .server
+        .cors_allowed_origins
+        .iter()
+        .map(|origin| origin.parse::<HeaderValue>())
+        .collect::<Result<Vec<_>, _>>()?;
+
     let app = Router::new()
         .merge(static_serve())
-        .nest("/api", api_serve(db_pool))
+        .nest("/api", api_serve(config.clone(), db_pool, image_storage))
         .layer(RequestBodyLimitLayer::new(
             (config.server.body_limit * 1024 * 1024).try_into()?,
         ))
@@ -58,8 +83,12 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
                     Method::DELETE,
                     Method::OPTIONS,
                 ])
-                .allow_origin(Any)
-                .allow_headers(Any),
+                // Cookies (OAuth flow, sessions) are only sent to explicitly allowed origins
+                .allow_origin(AllowOrigin::list(allowed_origins))
+                .allow_headers(AllowHeaders::mirror_request())
+                // Cross-origin clients cannot read the CSRF cookie, so it is also sent as a header
+                .expose_headers([HeaderName::from_static(CSRF_HEADER)])
+                .allow_credentials(true),
         )
         .layer(TraceLayer::new_for_http());
 
@@ -67,7 +96,8 @@ pub async fn start(config: Arc<DotEnvyConfig>, db_pool: Arc<PgPoolSquad>) -> Res
     let listener = TcpListener::bind(addr).await?;
 
     info!("Server start on port {}", config.server.port);
-    axum::serve(listener, app)
+    // Peer addresses feed the per-IP login throttle
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(shutdown_signal())
         .await?;
 
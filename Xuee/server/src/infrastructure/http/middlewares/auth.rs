@@ -1,31 +1,198 @@
+use std::sync::Arc;
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     middleware::Next,
     response::Response,
     http::{StatusCode, header},
 };
-use crate::config::config_loader::get_jwt_env;
-use crate::infrastructure::jwt::{verify_token, jwt_model::Claims};
+use axum_extra::extract::cookie::CookieJar;
+use crate::application::use_cases::api_tokens::TOKEN_PREFIX;
+use crate::config::config_loader::get_auth_mode;
+use crate::domain::repositories::{
+    api_tokens::ApiTokenRepository,
+    brawlers::BrawlerRepository,
+    sessions::SessionRepository,
+};
+use crate::domain::value_objects::{api_token_model::Scope, roles::Role};
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    repositories::{api_tokens::ApiTokenPostgres, brawlers::BrawlerPostgres, sessions::SessionPostgres},
+};
+use crate::infrastructure::http::cookies::{csrf_token_matches, SESSION_COOKIE};
+use crate::infrastructure::http::middlewares::require_scope::RequiredScope;
+use crate::infrastructure::opaque_token;
+use crate::infrastructure::jwt::jwt_model::Claims;
+use crate::infrastructure::services::token_service::TokenService;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SessionId(pub i32);
 
-pub async fn auth(mut req: Request, next: Next) -> Result<Response, StatusCode> {
-    let auth_header = req
+pub async fn auth(
+    State(db_pool): State<Arc<PgPoolSquad>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let auth_mode = get_auth_mode();
+
+    let bearer = req
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|header_value| header_value.to_str().ok())
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+        .filter(|_| auth_mode.accepts_bearer())
+        .map(|auth_header| auth_header.strip_prefix("Bearer ").unwrap_or(auth_header).to_string());
 
-    let token = if auth_header.starts_with("Bearer ") {
-        &auth_header[7..]
-    } else {
-        auth_header
+    let token = match bearer {
+        Some(token) if token.starts_with(TOKEN_PREFIX) => {
+            return personal_access_token(db_pool, token, req, next).await;
+        }
+        Some(token) => token,
+        None if auth_mode.accepts_cookie() => {
+            let jar = CookieJar::from_headers(req.headers());
+            let token = jar
+                .get(SESSION_COOKIE)
+                .map(|cookie| cookie.value().to_string())
+                .ok_or(StatusCode::UNAUTHORIZED)?;
+
+            // Browsers attach the cookie to cross-site requests too, so anything that changes
+            // state must prove it can read the CSRF cookie.
+            if !req.method().is_safe() && !csrf_token_matches(req.headers(), &jar) {
+                return Err(StatusCode::FORBIDDEN);
+            }
+            token
+        }
+        None => return Err(StatusCode::UNAUTHORIZED),
     };
 
-    let jwt_env = get_jwt_env();
-    let claims: Claims = verify_token(token, &jwt_env.secret).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let token_service = TokenService::shared().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let claims: Claims = token_service.verify(&token).map_err(|_| StatusCode::UNAUTHORIZED)?;
 
     let user_id = claims.sub.parse::<i32>().map_err(|_| StatusCode::UNAUTHORIZED)?;
 
+    // Tokens without a session (e.g. password reset links) are never valid for API access.
+    let session_id = claims.sid.ok_or(StatusCode::UNAUTHORIZED)?;
+    if claims.purpose.is_some() {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    let session_repository = SessionPostgres::new(db_pool);
+    let is_active = session_repository
+        .is_active(session_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !is_active {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    // Last-seen is informational; failing to record it must not fail the request
+    let _ = session_repository.touch(session_id).await;
+
     req.extensions_mut().insert(user_id);
+    req.extensions_mut().insert(SessionId(session_id));
+    // Tokens minted before roles existed carry none and get the least privileged role.
+    req.extensions_mut().insert(
+        claims
+            .role
+            .as_deref()
+            .and_then(|role| Role::try_from(role).ok())
+            .unwrap_or_default(),
+    );
+
+    Ok(next.run(req).await)
+}
+
+// PATs are deny-by-default: only routes wrapped in `require_scope` accept them, and only when
+// the token carries that scope. They never get a `SessionId`.
+async fn personal_access_token(
+    db_pool: Arc<PgPoolSquad>,
+    token: String,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let api_token_repository = ApiTokenPostgres::new(db_pool.clone());
+    let api_token = api_token_repository
+        .find_active_by_hash(opaque_token::hash(&token))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    ensure_scope_granted(req.extensions().get::<RequiredScope>().copied(), &api_token.scopes)?;
+
+    api_token_repository
+        .touch(api_token.id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // The role is read fresh rather than frozen at creation, so a demotion applies to existing tokens.
+    let brawler = BrawlerPostgres::new(db_pool)
+        .find_by_id(api_token.brawler_id)
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    req.extensions_mut().insert(api_token.brawler_id);
+    req.extensions_mut().insert(Role::try_from(brawler.role.as_str()).unwrap_or_default());
 
     Ok(next.run(req).await)
 }
+
+
+// Scope strings this build does not recognise grant nothing, and neither does `admin` on its
+// own: every route names the exact scope it needs.
+fn ensure_scope_granted(required: Option<RequiredScope>, granted: &[String]) -> Result<(), StatusCode> {
+    let RequiredScope(required) = required.ok_or(StatusCode::FORBIDDEN)?;
+    let granted = granted
+        .iter()
+        .any(|scope| Scope::try_from(scope.as_str()).is_ok_and(|scope| scope == required));
+    if !granted {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scopes(scopes: &[&str]) -> Vec<String> {
+        scopes.iter().map(|scope| scope.to_string()).collect()
+    }
+
+    #[test]
+    fn refuses_tokens_on_routes_that_declare_no_scope() {
+        let granted = scopes(&["missions:read", "missions:write", "admin"]);
+
+        assert_eq!(ensure_scope_granted(None, &granted), Err(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn refuses_tokens_without_the_required_scope() {
+        let required = Some(RequiredScope(Scope::MissionsWrite));
+
+        assert_eq!(ensure_scope_granted(required, &scopes(&["missions:read"])), Err(StatusCode::FORBIDDEN));
+        assert_eq!(ensure_scope_granted(required, &[]), Err(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn admin_scope_does_not_stand_in_for_other_scopes() {
+        let required = Some(RequiredScope(Scope::CardsRead));
+
+        assert_eq!(ensure_scope_granted(required, &scopes(&["admin"])), Err(StatusCode::FORBIDDEN));
+    }
+
+    #[test]
+    fn ignores_unknown_scope_strings() {
+        let required = Some(RequiredScope(Scope::SocialRead));
+
+        assert_eq!(
+            ensure_scope_granted(required, &scopes(&["social:*", "SOCIAL:READ", ""])),
+            Err(StatusCode::FORBIDDEN)
+        );
+        assert_eq!(ensure_scope_granted(required, &scopes(&["social:*", "social:read"])), Ok(()));
+    }
+
+    #[test]
+    fn accepts_tokens_carrying_the_required_scope() {
+        let required = Some(RequiredScope(Scope::Admin));
+
+        assert_eq!(ensure_scope_granted(required, &scopes(&["missions:read", "admin"])), Ok(()));
+    }
+}
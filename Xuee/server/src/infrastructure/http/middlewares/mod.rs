@@ -1 +1,4 @@
 pub mod auth;
+pub mod verified_email;
+pub mod require_role;
+pub mod require_scope;
@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request},
+    http::{request::Parts, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::domain::value_objects::roles::Role;
+
+pub trait RoleRequirement: Send + Sync + 'static {
+    const ROLE: Role;
+}
+
+pub struct Admin;
+
+impl RoleRequirement for Admin {
+    const ROLE: Role = Role::Admin;
+}
+
+pub struct Moderator;
+
+impl RoleRequirement for Moderator {
+    const ROLE: Role = Role::Moderator;
+}
+
+// Rejects with 403 unless the caller holds at least `R::ROLE`. Relies on `auth` having run first.
+pub struct RequireRole<R: RoleRequirement>(pub Role, PhantomData<R>);
+
+#[async_trait]
+impl<S, R> FromRequestParts<S> for RequireRole<R>
+where
+    S: Send + Sync,
+    R: RoleRequirement,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let role = *parts.extensions.get::<Role>().ok_or(StatusCode::UNAUTHORIZED)?;
+        if role < R::ROLE {
+            return Err(StatusCode::FORBIDDEN);
+        }
+
+        Ok(Self(role, PhantomData))
+    }
+}
+
+// Layer form for whole routers: `.layer(middleware::from_fn(require_role::<Admin>))` inside `auth`.
+pub async fn require_role<R: RoleRequirement>(
+    _role: RequireRole<R>,
+    req: Request,
+    next: Next,
+) -> Response {
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn extract<R: RoleRequirement>(role: Option<Role>) -> Result<Role, StatusCode> {
+        let (mut parts, _) = axum::http::Request::new(()).into_parts();
+        if let Some(role) = role {
+            parts.extensions.insert(role);
+        }
+
+        RequireRole::<R>::from_request_parts(&mut parts, &()).await.map(|RequireRole(role, _)| role)
+    }
+
+    #[tokio::test]
+    async fn refuses_requests_that_skipped_auth() {
+        assert_eq!(extract::<Moderator>(None).await, Err(StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn refuses_roles_below_the_requirement() {
+        assert_eq!(extract::<Moderator>(Some(Role::Brawler)).await, Err(StatusCode::FORBIDDEN));
+        assert_eq!(extract::<Admin>(Some(Role::Moderator)).await, Err(StatusCode::FORBIDDEN));
+    }
+
+    #[tokio::test]
+    async fn accepts_the_required_role_and_above() {
+        assert_eq!(extract::<Moderator>(Some(Role::Moderator)).await, Ok(Role::Moderator));
+        assert_eq!(extract::<Moderator>(Some(Role::Admin)).await, Ok(Role::Admin));
+    }
+}
@@ -0,0 +1,21 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::domain::value_objects::api_token_model::Scope;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RequiredScope(pub Scope);
+
+// Declares the scope a personal access token needs for the wrapped routes. Must be layered
+// outside `auth` (i.e. added after it) so the requirement is known when the token is checked.
+pub async fn require_scope(
+    State(scope): State<Scope>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    req.extensions_mut().insert(RequiredScope(scope));
+    next.run(req).await
+}
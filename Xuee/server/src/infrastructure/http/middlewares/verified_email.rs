@@ -0,0 +1,39 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+    http::StatusCode,
+};
+use crate::config::config_loader::get_email_verification_env;
+use crate::domain::repositories::brawlers::BrawlerRepository;
+use crate::infrastructure::database::{
+    postgresql_connection::PgPoolSquad,
+    repositories::brawlers::BrawlerPostgres,
+};
+
+// Must be layered inside `auth`, which provides the brawler id.
+pub async fn verified_email(
+    State(db_pool): State<Arc<PgPoolSquad>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let env = get_email_verification_env().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !env.required {
+        return Ok(next.run(req).await);
+    }
+
+    let user_id = *req.extensions().get::<i32>().ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let brawler_repository = BrawlerPostgres::new(db_pool);
+    let user = brawler_repository
+        .find_by_id(user_id)
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    if user.email_verified_at.is_none() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(next.run(req).await)
+}
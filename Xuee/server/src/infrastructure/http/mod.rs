@@ -1,3 +1,5 @@
 pub mod http_serv;
 pub mod routers;
-pub mod middlewares;
\ No newline at end of file
+pub mod middlewares;
+pub mod client_context;
+pub mod cookies;
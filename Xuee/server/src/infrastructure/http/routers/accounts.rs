@@ -0,0 +1,96 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Extension, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json},
+    routing::get,
+    Router,
+    middleware,
+};
+
+use crate::{
+    application::use_cases::accounts::AccountUseCase,
+    infrastructure::{
+        database::{
+            repositories::{
+                accounts::AccountPostgres,
+                brawlers::BrawlerPostgres,
+                sessions::SessionPostgres,
+            },
+            postgresql_connection::PgPoolSquad,
+        },
+        http::middlewares::auth::{auth, SessionId},
+        services::{email_service::EmailService, image_storage::ConfiguredImageStorage},
+    },
+};
+
+type AccountUseCaseImpl = AccountUseCase<BrawlerPostgres, AccountPostgres, SessionPostgres, ConfiguredImageStorage>;
+
+pub fn build_use_case(db_pool: Arc<PgPoolSquad>, image_storage: Arc<ConfiguredImageStorage>) -> Arc<AccountUseCaseImpl> {
+    Arc::new(AccountUseCase::new(
+        Arc::new(BrawlerPostgres::new(db_pool.clone())),
+        Arc::new(AccountPostgres::new(db_pool.clone())),
+        Arc::new(SessionPostgres::new(db_pool)),
+        image_storage,
+        Arc::new(EmailService::new()),
+    ))
+}
+
+// Session-only like /api-tokens: no scope is declared, so a personal access token cannot
+// export or delete the account it belongs to.
+pub fn router(db_pool: Arc<PgPoolSquad>, image_storage: Arc<ConfiguredImageStorage>) -> Router {
+    Router::new()
+        .route("/export", get(export))
+        .route("/deletion", get(deletion_status).post(request_deletion).delete(cancel_deletion))
+        .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+        .with_state(build_use_case(db_pool, image_storage))
+}
+
+pub async fn export(
+    State(use_case): State<Arc<AccountUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.export(user_id).await {
+        Ok(archive) => (
+            StatusCode::OK,
+            [(
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"nebula-account-{}.json\"", user_id),
+            )],
+            Json(archive),
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn deletion_status(
+    State(use_case): State<Arc<AccountUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.deletion_status(user_id).await {
+        Ok(status) => (StatusCode::OK, Json(status)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn request_deletion(
+    State(use_case): State<Arc<AccountUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Extension(SessionId(session_id)): Extension<SessionId>,
+) -> impl IntoResponse {
+    match use_case.request_deletion(user_id, session_id).await {
+        Ok(status) => (StatusCode::ACCEPTED, Json(status)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn cancel_deletion(
+    State(use_case): State<Arc<AccountUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.cancel_deletion(user_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
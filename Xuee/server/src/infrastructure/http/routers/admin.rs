@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+    routing::{delete, get, post, put},
+    Router,
+    middleware,
+};
+
+use crate::{
+    application::use_cases::admin::AdminUseCase,
+    domain::{
+        entities::cards::CardDraftEntity,
+        value_objects::{api_token_model::Scope, auth_event_model::AuthEventFilter, roles::UpdateRoleRequest},
+    },
+    infrastructure::{
+        database::{
+            repositories::{
+                auth_events::AuthEventPostgres,
+                brawlers::BrawlerPostgres,
+                cards::CardPostgres,
+                mission_management::MisssionManagementPostgres,
+                sessions::SessionPostgres,
+            },
+            postgresql_connection::PgPoolSquad,
+        },
+        http::middlewares::{
+            auth::auth,
+            require_role::{require_role, Admin, Moderator},
+            require_scope::require_scope,
+        },
+    },
+};
+
+type AdminUseCaseImpl = AdminUseCase<BrawlerPostgres, SessionPostgres, CardPostgres, MisssionManagementPostgres, AuthEventPostgres>;
+
+pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
+    let admin_use_case = Arc::new(AdminUseCase::new(
+        Arc::new(BrawlerPostgres::new(db_pool.clone())),
+        Arc::new(SessionPostgres::new(db_pool.clone())),
+        Arc::new(CardPostgres::new(db_pool.clone())),
+        Arc::new(MisssionManagementPostgres::new(db_pool.clone())),
+        Arc::new(AuthEventPostgres::new(db_pool.clone())),
+    ));
+
+    let admin_only = Router::new()
+        .route("/brawlers/:id/role", put(set_role))
+        .route("/cards", post(add_card))
+        .route("/cards/:id", put(update_card).delete(delete_card))
+        .route("/auth-events", get(search_auth_events))
+        .layer(middleware::from_fn(require_role::<Admin>));
+
+    let moderation = Router::new()
+        .route("/missions/:id", delete(remove_mission))
+        .layer(middleware::from_fn(require_role::<Moderator>));
+
+    Router::new()
+        .merge(admin_only)
+        .merge(moderation)
+        .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+        .layer(middleware::from_fn_with_state(Scope::Admin, require_scope))
+        .with_state(admin_use_case)
+}
+
+pub async fn set_role(
+    State(use_case): State<Arc<AdminUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateRoleRequest>,
+) -> impl IntoResponse {
+    match use_case.set_role(user_id, id, payload.role).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn add_card(
+    State(use_case): State<Arc<AdminUseCaseImpl>>,
+    Json(payload): Json<CardDraftEntity>,
+) -> impl IntoResponse {
+    match use_case.add_card(payload).await {
+        Ok(card) => (StatusCode::CREATED, Json(card)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn update_card(
+    State(use_case): State<Arc<AdminUseCaseImpl>>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CardDraftEntity>,
+) -> impl IntoResponse {
+    match use_case.update_card(id, payload).await {
+        Ok(card) => (StatusCode::OK, Json(card)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn delete_card(
+    State(use_case): State<Arc<AdminUseCaseImpl>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.delete_card(id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn remove_mission(
+    State(use_case): State<Arc<AdminUseCaseImpl>>,
+    Path(id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.remove_mission(id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn search_auth_events(
+    State(use_case): State<Arc<AdminUseCaseImpl>>,
+    Query(filter): Query<AuthEventFilter>,
+) -> impl IntoResponse {
+    match use_case.search_auth_events(filter).await {
+        Ok(events) => (StatusCode::OK, Json(events)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+    routing::{delete, get},
+    Router,
+    middleware,
+};
+
+use crate::{
+    application::use_cases::api_tokens::ApiTokenUseCase,
+    domain::value_objects::api_token_model::CreateApiTokenModel,
+    infrastructure::{
+        database::{
+            repositories::api_tokens::ApiTokenPostgres,
+            postgresql_connection::PgPoolSquad,
+        },
+        http::middlewares::auth::auth,
+    },
+};
+
+// No scope is declared here, so tokens can only be managed from a signed-in session, never by another token.
+pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
+    let api_token_repository = ApiTokenPostgres::new(db_pool.clone());
+    let api_token_use_case = Arc::new(ApiTokenUseCase::new(Arc::new(api_token_repository)));
+
+    Router::new()
+        .route("/", get(list).post(create))
+        .route("/:id", delete(revoke))
+        .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+        .with_state(api_token_use_case)
+}
+
+pub async fn create(
+    State(use_case): State<Arc<ApiTokenUseCase<ApiTokenPostgres>>>,
+    Extension(user_id): Extension<i32>,
+    Json(payload): Json<CreateApiTokenModel>,
+) -> impl IntoResponse {
+    match use_case.create(user_id, payload).await {
+        Ok(created) => (StatusCode::CREATED, Json(created)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn list(
+    State(use_case): State<Arc<ApiTokenUseCase<ApiTokenPostgres>>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.list(user_id).await {
+        Ok(tokens) => (StatusCode::OK, Json(tokens)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn revoke(
+    State(use_case): State<Arc<ApiTokenUseCase<ApiTokenPostgres>>>,
+    Extension(user_id): Extension<i32>,
+    Path(token_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.revoke(user_id, token_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    }
+}
@@ -1,100 +1,524 @@
 use std::sync::Arc;
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Json},
-    routing::{post, get},
+    extract::{Extension, FromRef, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, post},
     Router,
+    middleware,
 };
+use axum_extra::extract::cookie::{Cookie, CookieJar, Key, SameSite, SignedCookieJar};
 use serde::Deserialize;
 
 use crate::{
-    application::use_cases::authentication::{AuthenticationUseCase, LoginModel},
+    application::use_cases::{
+        authentication::{AuthenticationUseCase, LoginModel, RefreshModel},
+        magic_link::MagicLinkUseCase,
+        oidc::OidcUseCase,
+        password_reset::PasswordResetUseCase,
+        sign_in::{LoginOutcome, Passport, SignInService, TooManyAttempts},
+    },
+    config::{auth_mode::AuthMode, config_loader::get_auth_mode, config_model::DotEnvyConfig},
+    domain::value_objects::{
+        auth_event_model::{AuthEventQuery, ClientContext},
+        identity_model::{OAuthFlow, OidcCallbackModel},
+        two_factor_model::TwoFactorLoginRequest,
+    },
     infrastructure::{
         database::{
-            repositories::brawlers::BrawlerPostgres,
+            repositories::{
+                auth_events::AuthEventPostgres,
+                brawler_identities::BrawlerIdentityPostgres,
+                brawlers::BrawlerPostgres,
+                login_attempts::LoginAttemptPostgres,
+                magic_link_tokens::MagicLinkTokenPostgres,
+                password_reset_tokens::PasswordResetTokenPostgres,
+                sessions::SessionPostgres,
+                two_factor::TwoFactorPostgres,
+            },
             postgresql_connection::PgPoolSquad,
         },
+        http::{
+            cookies::{
+                add_session_cookies, clear_session_cookies, csrf_token_matches, secure_cookies, signing_key,
+                CSRF_HEADER, REFRESH_COOKIE,
+            },
+            middlewares::auth::{auth, SessionId},
+        },
         services::{
             email_service::EmailService,
-            google_auth_service::GoogleAuthService,
+            oidc_service::OidcService,
+            token_service::TokenService,
         },
     },
 };
 
-pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
-    let brawler_repository = BrawlerPostgres::new(db_pool);
+pub type SignInServiceImpl = SignInService<SessionPostgres, TwoFactorPostgres, LoginAttemptPostgres, AuthEventPostgres>;
+pub type AuthUseCase = AuthenticationUseCase<BrawlerPostgres, SessionPostgres, TwoFactorPostgres, AuthEventPostgres, SignInServiceImpl>;
+type MagicLinkUseCaseImpl = MagicLinkUseCase<BrawlerPostgres, MagicLinkTokenPostgres, SignInServiceImpl>;
+type OidcUseCaseImpl = OidcUseCase<BrawlerPostgres, BrawlerIdentityPostgres, SignInServiceImpl>;
+type PasswordResetUseCaseImpl = PasswordResetUseCase<BrawlerPostgres, PasswordResetTokenPostgres, SignInServiceImpl>;
+
+const OAUTH_FLOW_COOKIE: &str = "oauth_flow";
+
+#[derive(Clone)]
+pub struct AuthState {
+    use_case: Arc<AuthUseCase>,
+    magic_link_use_case: Arc<MagicLinkUseCaseImpl>,
+    oidc_use_case: Arc<OidcUseCaseImpl>,
+    password_reset_use_case: Arc<PasswordResetUseCaseImpl>,
+    cookie_key: Key,
+}
+
+impl FromRef<AuthState> for Arc<AuthUseCase> {
+    fn from_ref(state: &AuthState) -> Self {
+        state.use_case.clone()
+    }
+}
+
+impl FromRef<AuthState> for Arc<MagicLinkUseCaseImpl> {
+    fn from_ref(state: &AuthState) -> Self {
+        state.magic_link_use_case.clone()
+    }
+}
+
+impl FromRef<AuthState> for Arc<OidcUseCaseImpl> {
+    fn from_ref(state: &AuthState) -> Self {
+        state.oidc_use_case.clone()
+    }
+}
+
+impl FromRef<AuthState> for Arc<PasswordResetUseCaseImpl> {
+    fn from_ref(state: &AuthState) -> Self {
+        state.password_reset_use_case.clone()
+    }
+}
+
+impl FromRef<AuthState> for Key {
+    fn from_ref(state: &AuthState) -> Self {
+        state.cookie_key.clone()
+    }
+}
+
+pub fn router(db_pool: Arc<PgPoolSquad>, config: Arc<DotEnvyConfig>) -> Router {
+    let sign_in = build_sign_in(db_pool.clone(), config.clone());
+    let brawler_repository = Arc::new(BrawlerPostgres::new(db_pool.clone()));
     let email_service = Arc::new(EmailService::new());
-    // In production, handle error properly. For now, panic if env vars missing is okay or default to mock.
-    let google_auth_service = Arc::new(GoogleAuthService::new().unwrap_or_else(|e| {
-        println!("Warning: Google Auth Service init failed: {}", e);
-        // Fallback to a safe dummy if initialization fails (e.g. malformed URL)
-        // This prevents the server from crashing loop.
-        match GoogleAuthService::new() {
-             Ok(s) => s,
-             Err(_) => panic!("Critical: Google Auth Service failed to initialize even with defaults. Check URL format."),
-        }
-    }));
+    let oidc_service = Arc::new(OidcService::from_env().expect("OIDC providers are valid"));
 
-    let auth_use_case = Arc::new(AuthenticationUseCase::new(
-        Arc::new(brawler_repository),
-        email_service,
-        google_auth_service,
-    ));
+    let auth_state = AuthState {
+        use_case: build_use_case(db_pool.clone(), sign_in.clone()),
+        magic_link_use_case: Arc::new(MagicLinkUseCase::new(
+            brawler_repository.clone(),
+            Arc::new(MagicLinkTokenPostgres::new(db_pool.clone())),
+            sign_in.clone(),
+            email_service.clone(),
+        )),
+        oidc_use_case: Arc::new(OidcUseCase::new(
+            brawler_repository.clone(),
+            Arc::new(BrawlerIdentityPostgres::new(db_pool.clone())),
+            sign_in.clone(),
+            email_service.clone(),
+            oidc_service,
+        )),
+        password_reset_use_case: Arc::new(PasswordResetUseCase::new(
+            brawler_repository,
+            Arc::new(PasswordResetTokenPostgres::new(db_pool.clone())),
+            sign_in,
+            email_service,
+        )),
+        cookie_key: signing_key(&config.cookie_secret),
+    };
 
     Router::new()
         .route("/login", post(login))
+        .route("/login/2fa", post(login_two_factor))
+        .route("/magic-link", post(request_magic_link))
+        .route("/magic-link/consume", post(consume_magic_link))
+        .route("/refresh", post(refresh))
+        .route("/logout", post(logout).layer(middleware::from_fn_with_state(db_pool.clone(), auth)))
+        // Kept for clients that predate the generic OIDC routes
         .route("/google/url", get(google_url))
         .route("/google/callback", post(google_callback))
+        .route("/oidc/providers", get(oidc_providers))
+        .route("/oidc/:provider/url", get(oidc_url))
+        .route("/oidc/:provider/callback", post(oidc_callback))
+        .route(
+            "/identities",
+            get(list_identities).layer(middleware::from_fn_with_state(db_pool.clone(), auth)),
+        )
+        .route(
+            "/identities/:provider",
+            post(link_identity)
+                .delete(unlink_identity)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth)),
+        )
+        .route(
+            "/sessions",
+            get(list_sessions)
+                .delete(revoke_other_sessions)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth)),
+        )
+        .route(
+            "/sessions/:id",
+            delete(revoke_session).layer(middleware::from_fn_with_state(db_pool.clone(), auth)),
+        )
+        .route("/events", get(list_events).layer(middleware::from_fn_with_state(db_pool.clone(), auth)))
         .route("/request-reset", post(request_reset))
         .route("/reset", post(reset_password))
-        .with_state(auth_use_case)
+        .with_state(auth_state)
+}
+
+// Shared by every login flow, so throttling and the audit trail behave the same whichever one is used.
+pub fn build_sign_in(db_pool: Arc<PgPoolSquad>, config: Arc<DotEnvyConfig>) -> Arc<SignInServiceImpl> {
+    Arc::new(SignInService::new(
+        Arc::new(SessionPostgres::new(db_pool.clone())),
+        Arc::new(TwoFactorPostgres::new(db_pool.clone())),
+        Arc::new(LoginAttemptPostgres::new(db_pool.clone())),
+        Arc::new(AuthEventPostgres::new(db_pool)),
+        config.login_throttle.clone(),
+        Arc::new(EmailService::new()),
+        TokenService::shared().expect("JWT keyring is valid"),
+    ))
+}
+
+pub fn build_use_case(db_pool: Arc<PgPoolSquad>, sign_in: Arc<SignInServiceImpl>) -> Arc<AuthUseCase> {
+    Arc::new(AuthenticationUseCase::new(
+        Arc::new(BrawlerPostgres::new(db_pool.clone())),
+        Arc::new(SessionPostgres::new(db_pool.clone())),
+        Arc::new(TwoFactorPostgres::new(db_pool.clone())),
+        Arc::new(AuthEventPostgres::new(db_pool)),
+        sign_in,
+        TokenService::shared().expect("JWT keyring is valid"),
+    ))
 }
 
 pub async fn login(
-    State(use_case): State<Arc<AuthenticationUseCase<BrawlerPostgres>>>,
+    State(use_case): State<Arc<AuthUseCase>>,
+    client: ClientContext,
+    jar: CookieJar,
     Json(payload): Json<LoginModel>,
 ) -> impl IntoResponse {
-    match use_case.login(payload).await {
-        Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
-        Err(e) => (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
+    match use_case.login(payload, client).await {
+        Ok(outcome) => login_outcome_response(jar, outcome),
+        Err(e) => login_error_response(e),
     }
 }
 
-pub async fn google_url(
-    State(use_case): State<Arc<AuthenticationUseCase<BrawlerPostgres>>>,
+pub async fn login_two_factor(
+    State(use_case): State<Arc<AuthUseCase>>,
+    client: ClientContext,
+    jar: CookieJar,
+    Json(payload): Json<TwoFactorLoginRequest>,
 ) -> impl IntoResponse {
-    let (url, _) = use_case.get_google_auth_url();
-    (StatusCode::OK, Json(serde_json::json!({ "url": url }))).into_response()
+    match use_case.verify_two_factor(payload.challenge_token, payload.code, client).await {
+        Ok(passport) => passport_response(jar, passport),
+        Err(e) => login_error_response(e),
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MagicLinkRequestModel {
+    pub username: String,
 }
 
 #[derive(Deserialize)]
-pub struct GoogleCallbackModel {
-    code: String,
+pub struct MagicLinkConsumeModel {
+    pub token: String,
+}
+
+// Always 202 for a well-formed address, so the endpoint cannot be used to probe for accounts.
+pub async fn request_magic_link(
+    State(use_case): State<Arc<MagicLinkUseCaseImpl>>,
+    client: ClientContext,
+    Json(payload): Json<MagicLinkRequestModel>,
+) -> impl IntoResponse {
+    match use_case.request_magic_link(payload.username, client).await {
+        Ok(_) => StatusCode::ACCEPTED.into_response(),
+        Err(e) if e.is::<TooManyAttempts>() => login_error_response(e),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn consume_magic_link(
+    State(use_case): State<Arc<MagicLinkUseCaseImpl>>,
+    client: ClientContext,
+    jar: CookieJar,
+    Json(payload): Json<MagicLinkConsumeModel>,
+) -> impl IntoResponse {
+    match use_case.consume_magic_link(payload.token, client).await {
+        Ok(outcome) => login_outcome_response(jar, outcome),
+        Err(e) => login_error_response(e),
+    }
+}
+
+// In cookie mode the tokens are set as HttpOnly cookies and never reach JavaScript; `both`
+// sets the cookies and still returns the tokens for clients that have not moved over yet.
+pub fn passport_response(jar: CookieJar, mut passport: Passport) -> Response {
+    let auth_mode = get_auth_mode();
+    if !auth_mode.accepts_cookie() {
+        return (StatusCode::OK, Json(passport)).into_response();
+    }
+
+    let refresh_ttl = match TokenService::shared() {
+        Ok(token_service) => token_service.refresh_ttl(),
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let (jar, csrf_token) = add_session_cookies(
+        jar,
+        passport.access_token.clone(),
+        passport.expires_in,
+        passport.refresh_token.clone(),
+        refresh_ttl,
+    );
+
+    if auth_mode == AuthMode::Cookie {
+        passport.access_token.clear();
+        passport.refresh_token.clear();
+    }
+
+    (jar, [(CSRF_HEADER, csrf_token)], Json(passport)).into_response()
+}
+
+pub fn login_outcome_response(jar: CookieJar, outcome: LoginOutcome) -> Response {
+    match outcome {
+        LoginOutcome::Passport(passport) => passport_response(jar, passport),
+        challenge => (StatusCode::OK, Json(challenge)).into_response(),
+    }
+}
+
+pub fn login_error_response(e: anyhow::Error) -> axum::response::Response {
+    match e.downcast_ref::<TooManyAttempts>() {
+        Some(throttled) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, throttled.retry_after.to_string())],
+            throttled.to_string(),
+        )
+            .into_response(),
+        None => (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
+    }
+}
+
+// Bearer clients post the refresh token; cookie clients send an empty body and the refresh cookie.
+pub async fn refresh(
+    State(use_case): State<Arc<AuthUseCase>>,
+    client: ClientContext,
+    headers: HeaderMap,
+    jar: CookieJar,
+    payload: Option<Json<RefreshModel>>,
+) -> impl IntoResponse {
+    let auth_mode = get_auth_mode();
+
+    let refresh_model = match payload {
+        Some(Json(payload)) if auth_mode.accepts_bearer() => payload,
+        _ if auth_mode.accepts_cookie() => {
+            let Some(refresh_token) = jar.get(REFRESH_COOKIE).map(|cookie| cookie.value().to_string()) else {
+                return (StatusCode::UNAUTHORIZED, "Missing refresh token").into_response();
+            };
+            if !csrf_token_matches(&headers, &jar) {
+                return (StatusCode::FORBIDDEN, "Missing or invalid CSRF token").into_response();
+            }
+            RefreshModel { refresh_token }
+        }
+        _ => return (StatusCode::BAD_REQUEST, "Missing refresh token").into_response(),
+    };
+
+    match use_case.refresh(refresh_model, client).await {
+        Ok(passport) => passport_response(jar, passport),
+        Err(e) => (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
+    }
+}
+
+pub async fn logout(
+    State(use_case): State<Arc<AuthUseCase>>,
+    Extension(user_id): Extension<i32>,
+    Extension(SessionId(session_id)): Extension<SessionId>,
+    client: ClientContext,
+    jar: CookieJar,
+) -> impl IntoResponse {
+    match use_case.logout(user_id, session_id, client).await {
+        Ok(_) => (clear_session_cookies(jar), StatusCode::NO_CONTENT).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn list_sessions(
+    State(use_case): State<Arc<AuthUseCase>>,
+    Extension(user_id): Extension<i32>,
+    Extension(SessionId(session_id)): Extension<SessionId>,
+) -> impl IntoResponse {
+    match use_case.list_sessions(user_id, session_id).await {
+        Ok(sessions) => (StatusCode::OK, Json(sessions)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn revoke_session(
+    State(use_case): State<Arc<AuthUseCase>>,
+    Extension(user_id): Extension<i32>,
+    Extension(SessionId(current_session_id)): Extension<SessionId>,
+    Path(session_id): Path<i32>,
+    client: ClientContext,
+    jar: CookieJar,
+) -> impl IntoResponse {
+    match use_case.revoke_session(user_id, session_id, client).await {
+        // Revoking the current session is a logout, so the cookies go too
+        Ok(_) if session_id == current_session_id => (clear_session_cookies(jar), StatusCode::NO_CONTENT).into_response(),
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    }
+}
+
+// Signs out everywhere except the device making the request.
+pub async fn revoke_other_sessions(
+    State(use_case): State<Arc<AuthUseCase>>,
+    Extension(user_id): Extension<i32>,
+    Extension(SessionId(session_id)): Extension<SessionId>,
+    client: ClientContext,
+) -> impl IntoResponse {
+    match use_case.revoke_other_sessions(user_id, session_id, client).await {
+        Ok(revoked) => (StatusCode::OK, Json(revoked)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn list_events(
+    State(use_case): State<Arc<AuthUseCase>>,
+    Extension(user_id): Extension<i32>,
+    Query(query): Query<AuthEventQuery>,
+) -> impl IntoResponse {
+    match use_case.list_auth_events(user_id, query.limit).await {
+        Ok(events) => (StatusCode::OK, Json(events)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn google_url(
+    State(use_case): State<Arc<OidcUseCaseImpl>>,
+    jar: SignedCookieJar,
+) -> impl IntoResponse {
+    oidc_url(State(use_case), jar, Path("google".to_string())).await
 }
 
 pub async fn google_callback(
-    State(use_case): State<Arc<AuthenticationUseCase<BrawlerPostgres>>>,
-    Json(payload): Json<GoogleCallbackModel>,
+    State(use_case): State<Arc<OidcUseCaseImpl>>,
+    jar: SignedCookieJar,
+    client: ClientContext,
+    cookie_jar: CookieJar,
+    Json(payload): Json<OidcCallbackModel>,
 ) -> impl IntoResponse {
-    match use_case.login_with_google(payload.code).await {
-        Ok(passport) => (StatusCode::OK, Json(passport)).into_response(),
+    oidc_callback(State(use_case), jar, Path("google".to_string()), client, cookie_jar, Json(payload)).await
+}
+
+pub async fn oidc_providers(
+    State(use_case): State<Arc<OidcUseCaseImpl>>,
+) -> impl IntoResponse {
+    (StatusCode::OK, Json(serde_json::json!({ "providers": use_case.oidc_providers() }))).into_response()
+}
+
+pub async fn oidc_url(
+    State(use_case): State<Arc<OidcUseCaseImpl>>,
+    jar: SignedCookieJar,
+    Path(provider): Path<String>,
+) -> axum::response::Response {
+    let (url, flow) = match use_case.begin_oidc(&provider).await {
+        Ok(started) => started,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    let flow_json = match serde_json::to_string(&flow) {
+        Ok(flow_json) => flow_json,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+
+    let cookie = Cookie::build((OAUTH_FLOW_COOKIE, flow_json))
+        .path("/api/v1/authentication")
+        .http_only(true)
+        .secure(secure_cookies())
+        .same_site(SameSite::Lax)
+        .max_age(cookie::time::Duration::minutes(10));
+
+    (jar.add(cookie), Json(serde_json::json!({ "url": url }))).into_response()
+}
+
+pub async fn oidc_callback(
+    State(use_case): State<Arc<OidcUseCaseImpl>>,
+    jar: SignedCookieJar,
+    Path(provider): Path<String>,
+    client: ClientContext,
+    cookie_jar: CookieJar,
+    Json(payload): Json<OidcCallbackModel>,
+) -> axum::response::Response {
+    let (jar, flow) = take_oauth_flow(jar);
+
+    match use_case.login_with_oidc(flow, &provider, payload, client).await {
+        Ok(outcome) => (jar, login_outcome_response(cookie_jar, outcome)).into_response(),
+        Err(e) => (jar, (StatusCode::UNAUTHORIZED, e.to_string())).into_response(),
+    }
+}
+
+// Each flow cookie is good for exactly one callback, whatever its outcome.
+fn take_oauth_flow(jar: SignedCookieJar) -> (SignedCookieJar, Option<OAuthFlow>) {
+    let flow = jar
+        .get(OAUTH_FLOW_COOKIE)
+        .and_then(|cookie| serde_json::from_str::<OAuthFlow>(cookie.value()).ok());
+    let jar = jar.remove(Cookie::build(OAUTH_FLOW_COOKIE).path("/api/v1/authentication"));
+
+    (jar, flow)
+}
+
+pub async fn list_identities(
+    State(use_case): State<Arc<OidcUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.list_identities(user_id).await {
+        Ok(identities) => (StatusCode::OK, Json(identities)).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
+pub async fn link_identity(
+    State(use_case): State<Arc<OidcUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    jar: SignedCookieJar,
+    Path(provider): Path<String>,
+    client: ClientContext,
+    Json(payload): Json<OidcCallbackModel>,
+) -> impl IntoResponse {
+    let (jar, flow) = take_oauth_flow(jar);
+
+    match use_case.link_identity(user_id, flow, &provider, payload, client).await {
+        Ok(_) => (jar, StatusCode::NO_CONTENT).into_response(),
+        Err(e) => (jar, (StatusCode::BAD_REQUEST, e.to_string())).into_response(),
+    }
+}
+
+pub async fn unlink_identity(
+    State(use_case): State<Arc<OidcUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(provider): Path<String>,
+    client: ClientContext,
+) -> impl IntoResponse {
+    match use_case.unlink_identity(user_id, &provider, client).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
 #[derive(Deserialize)]
 pub struct RequestResetModel {
     pub username: String,
 }
 
+// Always 200 for known and unknown usernames alike, so the endpoint cannot be used to probe for accounts.
 pub async fn request_reset(
-    State(use_case): State<Arc<AuthenticationUseCase<BrawlerPostgres>>>,
+    State(use_case): State<Arc<PasswordResetUseCaseImpl>>,
+    client: ClientContext,
     Json(payload): Json<RequestResetModel>,
 ) -> impl IntoResponse {
-    match use_case.request_password_reset(payload.username).await {
+    match use_case.request_password_reset(payload.username, client).await {
         Ok(_) => StatusCode::OK.into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
@@ -105,10 +529,11 @@ pub struct ResetPasswordModel {
 }
 
 pub async fn reset_password(
-    State(use_case): State<Arc<AuthenticationUseCase<BrawlerPostgres>>>,
+    State(use_case): State<Arc<PasswordResetUseCaseImpl>>,
+    client: ClientContext,
     Json(payload): Json<ResetPasswordModel>,
 ) -> impl IntoResponse {
-    match use_case.reset_password(payload.token, payload.new_password).await {
+    match use_case.reset_password(payload.token, payload.new_password, client).await {
         Ok(_) => StatusCode::OK.into_response(),
         Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
     }
@@ -1,64 +1,176 @@
 use std::sync::Arc;
 use axum::{
-    extract::{Extension, State},
-    http::StatusCode,
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
     response::{IntoResponse, Json},
-    routing::post,
+    routing::{get, post},
     Router,
     middleware,
 };
 
 use crate::{
-    application::use_cases::brawlers::BrawlersUseCase,
-    domain::value_objects::brawler_model::{RegisterBrawlerModel, AvatarUploadRequest, UpdateDisplayNameRequest},
+    application::use_cases::brawlers::{BrawlersUseCase, UsernameChangeCooldown, UsernameUnavailable},
+    domain::value_objects::{
+        api_token_model::Scope,
+        brawler_model::{
+            RegisterBrawlerModel, AvatarUploadRequest, ChangePasswordRequest, ChangeUsernameRequest, DirectoryQuery,
+            UpdateDisplayNameRequest, UsernameLookup, VerifyEmailRequest,
+        },
+        mission_filter::{MissionFilter, MyMissionsView},
+        pagination::InvalidCursor,
+    },
     infrastructure::{
         database::{
-            repositories::brawlers::BrawlerPostgres,
+            repositories::{
+                brawlers::BrawlerPostgres,
+                email_verification_tokens::EmailVerificationTokenPostgres,
+            },
             postgresql_connection::PgPoolSquad,
         },
-        http::middlewares::auth::auth,
-        services::email_service::EmailService,
+        http::middlewares::{auth::{auth, SessionId}, require_scope::require_scope},
+        password_policy::PasswordPolicyViolation,
+        username_policy::UsernamePolicyViolation,
+        image_pipeline::InvalidImage,
+        services::{email_service::EmailService, image_storage::ConfiguredImageStorage},
     },
 };
 
-pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
-    let brawler_repository = BrawlerPostgres::new(db_pool);
+pub fn router(db_pool: Arc<PgPoolSquad>, image_storage: Arc<ConfiguredImageStorage>) -> Router {
+    let brawler_repository = BrawlerPostgres::new(db_pool.clone());
+    let email_verification_token_repository = EmailVerificationTokenPostgres::new(db_pool.clone());
     let email_service = Arc::new(EmailService::new());
     let brawlers_use_case = Arc::new(BrawlersUseCase::new(
         Arc::new(brawler_repository),
+        Arc::new(email_verification_token_repository),
+        image_storage,
         email_service
     ));
 
     Router::new()
+        .route("/", get(directory))
         .route("/register", post(register))
-        .route("/avatar", post(upload_avatar).layer(middleware::from_fn(auth)))
-        .route("/update-name", post(update_display_name).layer(middleware::from_fn(auth)))
+        .route("/verify-email", post(verify_email))
+        .route("/avatar", post(upload_avatar).layer(middleware::from_fn_with_state(db_pool.clone(), auth)))
+        .route("/update-name", post(update_display_name).layer(middleware::from_fn_with_state(db_pool.clone(), auth)))
+        .route("/change-password", post(change_password).layer(middleware::from_fn_with_state(db_pool.clone(), auth)))
+        .route("/change-username", post(change_username).layer(middleware::from_fn_with_state(db_pool.clone(), auth)))
+        .route("/by-username/:username", get(get_profile_by_username))
+        .route(
+            "/me/missions",
+            get(my_missions)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+                .layer(middleware::from_fn_with_state(Scope::MissionsRead, require_scope)),
+        )
+        .route(
+            "/me/missions/:view",
+            get(my_missions_view)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+                .layer(middleware::from_fn_with_state(Scope::MissionsRead, require_scope)),
+        )
+        .route("/:id", get(get_profile))
         .with_state(brawlers_use_case)
 }
 
 pub async fn register(
-    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres>>>,
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
     Json(payload): Json<RegisterBrawlerModel>,
 ) -> impl IntoResponse {
     match use_case.register(payload).await {
         Ok(user_id) => (StatusCode::CREATED, user_id.to_string()).into_response(),
+        Err(e) if e.is::<PasswordPolicyViolation>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) if e.is::<UsernamePolicyViolation>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) if e.is::<UsernameUnavailable>() => (StatusCode::CONFLICT, e.to_string()).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn verify_email(
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
+    Json(payload): Json<VerifyEmailRequest>,
+) -> impl IntoResponse {
+    match use_case.verify_email(payload.token).await {
+        Ok(_) => StatusCode::OK.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn get_profile(
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
+    Path(brawler_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.get_profile(brawler_id).await {
+        Ok(profile) => (StatusCode::OK, Json(profile)).into_response(),
+        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    }
+}
+
+// Public search, e.g. to find a defender for /cards/battle: `?q=&sort=activity|card_power|missions_completed&cursor=&limit=`
+pub async fn directory(
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
+    Query(query): Query<DirectoryQuery>,
+) -> impl IntoResponse {
+    match use_case.directory(query).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) if e.is::<InvalidCursor>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+// A former username answers with 308 and a relative Location, so it works behind any mount point.
+pub async fn get_profile_by_username(
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
+    Path(username): Path<String>,
+) -> impl IntoResponse {
+    match use_case.find_by_username(username).await {
+        Ok(UsernameLookup::Profile(profile)) => (StatusCode::OK, Json(profile)).into_response(),
+        Ok(UsernameLookup::Redirect(current)) => {
+            let location: String = url::form_urlencoded::byte_serialize(current.as_bytes()).collect();
+            (StatusCode::PERMANENT_REDIRECT, [(header::LOCATION, location)]).into_response()
+        }
+        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    }
+}
+
+pub async fn my_missions(
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
+    Extension(user_id): Extension<i32>,
+    Query(filter): Query<MissionFilter>,
+) -> impl IntoResponse {
+    match use_case.my_missions(user_id, MyMissionsView::All, filter).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) if e.is::<InvalidCursor>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+// `leading`, `joined`, `upcoming` or `past`; anything else is rejected by the Path extractor.
+pub async fn my_missions_view(
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
+    Extension(user_id): Extension<i32>,
+    Path(view): Path<MyMissionsView>,
+    Query(filter): Query<MissionFilter>,
+) -> impl IntoResponse {
+    match use_case.my_missions(user_id, view, filter).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) if e.is::<InvalidCursor>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
 pub async fn upload_avatar(
-    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres>>>,
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
     Extension(user_id): Extension<i32>,
     Json(payload): Json<AvatarUploadRequest>,
 ) -> impl IntoResponse {
     match use_case.upload_avatar(user_id, payload.base64_string).await {
         Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(e) if e.is::<InvalidImage>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
 pub async fn update_display_name(
-    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres>>>,
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
     Extension(user_id): Extension<i32>,
     Json(payload): Json<UpdateDisplayNameRequest>,
 ) -> impl IntoResponse {
@@ -67,3 +179,33 @@ pub async fn update_display_name(
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
+
+pub async fn change_password(
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
+    Extension(user_id): Extension<i32>,
+    Extension(SessionId(session_id)): Extension<SessionId>,
+    Json(payload): Json<ChangePasswordRequest>,
+) -> impl IntoResponse {
+    match use_case
+        .change_password(user_id, session_id, payload.current_password, payload.new_password)
+        .await
+    {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) if e.is::<PasswordPolicyViolation>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
+    }
+}
+
+pub async fn change_username(
+    State(use_case): State<Arc<BrawlersUseCase<BrawlerPostgres, EmailVerificationTokenPostgres, ConfiguredImageStorage>>>,
+    Extension(user_id): Extension<i32>,
+    Json(payload): Json<ChangeUsernameRequest>,
+) -> impl IntoResponse {
+    match use_case.change_username(user_id, payload.username).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(e) if e.is::<UsernamePolicyViolation>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) if e.is::<UsernameUnavailable>() => (StatusCode::CONFLICT, e.to_string()).into_response(),
+        Err(e) if e.is::<UsernameChangeCooldown>() => (StatusCode::TOO_MANY_REQUESTS, e.to_string()).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
@@ -17,25 +17,47 @@ pub struct UpgradeRequest {
 
 use crate::{
     application::use_cases::cards::CardUseCase,
+    domain::value_objects::api_token_model::Scope,
     infrastructure::{
         database::{
             repositories::cards::CardPostgres,
             postgresql_connection::PgPoolSquad,
         },
-        http::middlewares::auth::auth,
+        http::middlewares::{auth::auth, require_scope::require_scope, verified_email::verified_email},
     },
 };
 
 pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
-    let card_repository = CardPostgres::new(db_pool);
+    let card_repository = CardPostgres::new(db_pool.clone());
     let card_use_case = Arc::new(CardUseCase::new(Arc::new(card_repository)));
 
     Router::new()
         .route("/", get(get_all_cards))
-        .route("/inventory", get(get_my_inventory).layer(middleware::from_fn(auth)))
-        .route("/gacha", post(draw_gacha).layer(middleware::from_fn(auth)))
-        .route("/upgrade", post(upgrade_card).layer(middleware::from_fn(auth)))
-        .route("/battle", post(battle).layer(middleware::from_fn(auth)))
+        .route(
+            "/inventory",
+            get(get_my_inventory)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+                .layer(middleware::from_fn_with_state(Scope::CardsRead, require_scope)),
+        )
+        .route(
+            "/gacha",
+            post(draw_gacha)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), verified_email))
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+                .layer(middleware::from_fn_with_state(Scope::CardsWrite, require_scope)),
+        )
+        .route(
+            "/upgrade",
+            post(upgrade_card)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+                .layer(middleware::from_fn_with_state(Scope::CardsWrite, require_scope)),
+        )
+        .route(
+            "/battle",
+            post(battle)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+                .layer(middleware::from_fn_with_state(Scope::CardsWrite, require_scope)),
+        )
         .with_state(card_use_case)
 }
 
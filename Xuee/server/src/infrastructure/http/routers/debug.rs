@@ -5,6 +5,7 @@ use axum::{
     response::IntoResponse,
     routing::post,
     Router,
+    middleware,
 };
 use crate::{
     domain::{
@@ -16,23 +17,34 @@ use crate::{
             brawlers::BrawlerRepository,
             missions::MissionRepository,
         },
-        value_objects::mission_statuses::MissionStatuses,
+        value_objects::{api_token_model::Scope, mission_statuses::MissionStatuses},
     },
-    infrastructure::database::{
-        repositories::{
-            brawlers::BrawlerPostgres,
-            missions::MissionPostgres,
+    infrastructure::{
+        argon2::hash,
+        database::{
+            repositories::{
+                brawlers::BrawlerPostgres,
+                missions::MissionPostgres,
+            },
+            postgresql_connection::PgPoolSquad,
+        },
+        http::middlewares::{
+            auth::auth,
+            require_role::{require_role, Admin},
+            require_scope::require_scope,
         },
-        postgresql_connection::PgPoolSquad,
     },
 };
 
 pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
     let brawler_repository = Arc::new(BrawlerPostgres::new(db_pool.clone()));
-    let mission_repository = Arc::new(MissionPostgres::new(db_pool));
+    let mission_repository = Arc::new(MissionPostgres::new(db_pool.clone()));
 
     Router::new()
         .route("/seed", post(seed))
+        .layer(middleware::from_fn(require_role::<Admin>))
+        .layer(middleware::from_fn_with_state(db_pool, auth))
+        .layer(middleware::from_fn_with_state(Scope::Admin, require_scope))
         .with_state((brawler_repository, mission_repository))
 }
 
@@ -40,7 +52,11 @@ pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
 pub async fn seed(
     State((brawler_repo, mission_repo)): State<(Arc<BrawlerPostgres>, Arc<MissionPostgres>)>,
 ) -> impl IntoResponse {
-    // 1. Create 10 Users
+    // 1. Create 10 Users, all signing in with "password"
+    let password_hash = match hash("password".to_string()) {
+        Ok(password_hash) => password_hash,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
     let mut user_ids = Vec::new();
     let names = vec![
         ("zen_master", "Zen Master"),
@@ -58,7 +74,7 @@ pub async fn seed(
     for (username, display_name) in names {
         let entity = RegisterBrawlerEntity {
             username: username.to_string(),
-            password: "$argon2id$v=19$m=19456,t=2,p=1$VE0eXwFzCjI$P1/o7tS9q+k".to_string(), // Dummy hash for "password"
+            password: password_hash.clone(),
             display_name: display_name.to_string(),
         };
         
@@ -89,7 +105,7 @@ pub async fn seed(
     for status in statuses {
         for i in 1..=2 {
             // Distribute chiefs among missions
-            let chief_index = (created_missions % user_ids.len()) as usize;
+            let chief_index = created_missions % user_ids.len();
             let chief_id = user_ids[chief_index];
             
             let entity = AddMissionEntity {
@@ -114,11 +130,9 @@ pub async fn seed(
                 
                 while crew_added < 2 && user_index < user_ids.len() {
                     let brawler_id = user_ids[user_index];
-                    if brawler_id != chief_id {
-                        if mission_repo.join(mission_id, brawler_id).await.is_ok() {
-                            crew_added += 1;
-                            total_crew += 1;
-                        }
+                    if brawler_id != chief_id && mission_repo.join(mission_id, brawler_id).await.is_ok() {
+                        crew_added += 1;
+                        total_crew += 1;
                     }
                     user_index += 1;
                 }
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json},
+    routing::{get, post},
+    Router,
+};
+use axum_extra::extract::cookie::CookieJar;
+use serde::Deserialize;
+use tracing::{error, warn};
+
+use crate::{
+    config::config_model::DotEnvyConfig,
+    domain::value_objects::auth_event_model::ClientContext,
+    infrastructure::{
+        database::postgresql_connection::PgPoolSquad,
+        http::routers::authentication::{build_sign_in, build_use_case, passport_response, AuthUseCase},
+        services::dev_identity_provider::DevIdentityProvider,
+    },
+};
+
+#[derive(Clone)]
+pub struct DevIdentityState {
+    use_case: Arc<AuthUseCase>,
+    provider: Arc<DevIdentityProvider>,
+}
+
+pub fn router(db_pool: Arc<PgPoolSquad>, config: Arc<DotEnvyConfig>) -> Option<Router> {
+    let provider = match DevIdentityProvider::from_env() {
+        Ok(Some(provider)) => provider,
+        Ok(None) => return None,
+        Err(e) => {
+            error!("Dev identity provider disabled: {}", e);
+            return None;
+        }
+    };
+    warn!("Dev identity provider is enabled; fixture users can sign in without a password");
+
+    let state = DevIdentityState {
+        use_case: build_use_case(db_pool.clone(), build_sign_in(db_pool, config)),
+        provider: Arc::new(provider),
+    };
+
+    Some(
+        Router::new()
+            .route("/users", get(list_identities))
+            .route("/login", post(login))
+            .with_state(state),
+    )
+}
+
+pub async fn list_identities(State(state): State<DevIdentityState>) -> impl IntoResponse {
+    (StatusCode::OK, Json(state.provider.identities().to_vec())).into_response()
+}
+
+#[derive(Deserialize)]
+pub struct DevLoginModel {
+    pub username: String,
+}
+
+pub async fn login(
+    State(state): State<DevIdentityState>,
+    client: ClientContext,
+    jar: CookieJar,
+    Json(payload): Json<DevLoginModel>,
+) -> impl IntoResponse {
+    match state.use_case.login_as_dev_identity(&state.provider, payload.username, client).await {
+        Ok(passport) => passport_response(jar, passport),
+        Err(e) => (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
+    }
+}
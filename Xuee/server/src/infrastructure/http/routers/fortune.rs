@@ -20,11 +20,11 @@ use crate::{
 };
 
 pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
-    let fortune_repository = FortunePostgres::new(db_pool);
+    let fortune_repository = FortunePostgres::new(db_pool.clone());
     let fortune_use_case = Arc::new(FortuneUseCase::new(Arc::new(fortune_repository)));
 
     Router::new()
-        .route("/daily", get(get_daily_fortune).layer(middleware::from_fn(auth)))
+        .route("/daily", get(get_daily_fortune).layer(middleware::from_fn_with_state(db_pool.clone(), auth)))
         .route("/draw", get(draw_fortune))
         .with_state(fortune_use_case)
 }
@@ -11,7 +11,10 @@ use axum::{
 use crate::{
     application::use_cases::mission_management::MissionManagementUseCase,
     domain::{
-        value_objects::mission_model::{AddMissionModel, EditMissionModel},
+        value_objects::{
+            api_token_model::Scope,
+            mission_model::{AddMissionModel, EditMissionModel},
+        },
         repositories::{
             mission_management::MissionManagementRepository,
             mission_viewing::MissionViewingRepository,
@@ -25,13 +28,13 @@ use crate::{
             },
             postgresql_connection::PgPoolSquad,
         },
-        http::middlewares::auth::auth,
+        http::middlewares::{auth::auth, require_scope::require_scope},
     },
 };
 
 pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
     let mission_management_repository = Arc::new(MisssionManagementPostgres::new(db_pool.clone()));
-    let mission_viewing_repository = Arc::new(MissionViewingPostgres::new(db_pool));
+    let mission_viewing_repository = Arc::new(MissionViewingPostgres::new(db_pool.clone()));
     
     let use_case = Arc::new(MissionManagementUseCase::new(
         mission_management_repository,
@@ -42,7 +45,8 @@ pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
         .route("/", post(add))
         .route("/:id", put(edit))
         .route("/:id", delete(remove))
-        .layer(middleware::from_fn(auth))
+        .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+        .layer(middleware::from_fn_with_state(Scope::MissionsWrite, require_scope))
         .with_state(use_case)
 }
 
@@ -10,7 +10,7 @@ use axum::{
 
 use crate::{
     application::use_cases::missions::MissionsUseCase,
-    domain::value_objects::mission_filter::MissionFilter,
+    domain::value_objects::{api_token_model::Scope, mission_filter::MissionFilter, pagination::InvalidCursor},
     infrastructure::{
         database::{
             repositories::{
@@ -19,13 +19,13 @@ use crate::{
             },
             postgresql_connection::PgPoolSquad,
         },
-        http::middlewares::auth::auth,
+        http::middlewares::{auth::auth, require_scope::require_scope, verified_email::verified_email},
     },
 };
 
 pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
     let mission_repository = MissionPostgres::new(db_pool.clone());
-    let mission_viewing_repository = MissionViewingPostgres::new(db_pool);
+    let mission_viewing_repository = MissionViewingPostgres::new(db_pool.clone());
     let missions_use_case = Arc::new(MissionsUseCase::new(
         Arc::new(mission_repository),
         Arc::new(mission_viewing_repository),
@@ -33,7 +33,19 @@ pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
 
     Router::new()
         .route("/", get(get_all))
-        .route("/:id/join", post(join).layer(middleware::from_fn(auth)))
+        .route(
+            "/friends",
+            get(get_friends_missions)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+                .layer(middleware::from_fn_with_state(Scope::MissionsRead, require_scope)),
+        )
+        .route(
+            "/:id/join",
+            post(join)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), verified_email))
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+                .layer(middleware::from_fn_with_state(Scope::MissionsWrite, require_scope)),
+        )
         .with_state(missions_use_case)
 }
 
@@ -42,7 +54,20 @@ async fn get_all(
     Query(filter): Query<MissionFilter>,
 ) -> impl IntoResponse {
     match use_case.get_all(filter).await {
-        Ok(missions) => (StatusCode::OK, Json(missions)).into_response(),
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) if e.is::<InvalidCursor>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn get_friends_missions(
+    State(use_case): State<Arc<MissionsUseCase<MissionPostgres, MissionViewingPostgres>>>,
+    Extension(user_id): Extension<i32>,
+    Query(filter): Query<MissionFilter>,
+) -> impl IntoResponse {
+    match use_case.get_friends_missions(user_id, filter).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) if e.is::<InvalidCursor>() => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
         Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
@@ -9,3 +9,11 @@ pub mod crew_operations;
 pub mod crew_operation;
 pub mod default_router;
 
+pub mod two_factor;
+#[cfg(feature = "dev-identity")]
+pub mod dev_identity;
+pub mod admin;
+pub mod api_tokens;
+pub mod passkeys;
+pub mod accounts;
+pub mod social;
@@ -0,0 +1,220 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Extension, FromRef, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    routing::{get, patch, post},
+    Router,
+    middleware,
+};
+use axum_extra::extract::cookie::{Cookie, CookieJar, Key, SameSite, SignedCookieJar};
+use serde::Serialize;
+
+use crate::{
+    application::use_cases::{passkey_login::PasskeyLoginUseCase, passkeys::PasskeyUseCase},
+    config::{config_loader::get_webauthn_env, config_model::DotEnvyConfig},
+    domain::value_objects::{
+        auth_event_model::ClientContext,
+        webauthn_model::{
+            PasskeyLoginOptionsRequest, PasskeyLoginRequest, RegisterPasskeyRequest, RenamePasskeyRequest,
+            WebAuthnCeremony,
+        },
+    },
+    infrastructure::{
+        database::{
+            repositories::{
+                brawlers::BrawlerPostgres,
+                webauthn_credentials::WebAuthnCredentialPostgres,
+            },
+            postgresql_connection::PgPoolSquad,
+        },
+        http::{
+            cookies::{secure_cookies, signing_key},
+            middlewares::auth::auth,
+            routers::authentication::{build_sign_in, login_error_response, login_outcome_response, SignInServiceImpl},
+        },
+    },
+};
+
+type PasskeyUseCaseImpl = PasskeyUseCase<BrawlerPostgres, WebAuthnCredentialPostgres>;
+type PasskeyLoginUseCaseImpl = PasskeyLoginUseCase<BrawlerPostgres, WebAuthnCredentialPostgres, SignInServiceImpl>;
+
+const CEREMONY_COOKIE: &str = "webauthn_ceremony";
+const CEREMONY_COOKIE_PATH: &str = "/api/v1/authentication/passkeys";
+
+#[derive(Clone)]
+pub struct PasskeyState {
+    use_case: Arc<PasskeyUseCaseImpl>,
+    login_use_case: Arc<PasskeyLoginUseCaseImpl>,
+    cookie_key: Key,
+}
+
+impl FromRef<PasskeyState> for Arc<PasskeyUseCaseImpl> {
+    fn from_ref(state: &PasskeyState) -> Self {
+        state.use_case.clone()
+    }
+}
+
+impl FromRef<PasskeyState> for Arc<PasskeyLoginUseCaseImpl> {
+    fn from_ref(state: &PasskeyState) -> Self {
+        state.login_use_case.clone()
+    }
+}
+
+impl FromRef<PasskeyState> for Key {
+    fn from_ref(state: &PasskeyState) -> Self {
+        state.cookie_key.clone()
+    }
+}
+
+pub fn router(db_pool: Arc<PgPoolSquad>, config: Arc<DotEnvyConfig>) -> Router {
+    let brawler_repository = Arc::new(BrawlerPostgres::new(db_pool.clone()));
+    let webauthn_credential_repository = Arc::new(WebAuthnCredentialPostgres::new(db_pool.clone()));
+    let passkey_state = PasskeyState {
+        use_case: Arc::new(PasskeyUseCase::new(
+            brawler_repository.clone(),
+            webauthn_credential_repository.clone(),
+        )),
+        login_use_case: Arc::new(PasskeyLoginUseCase::new(
+            brawler_repository,
+            webauthn_credential_repository,
+            build_sign_in(db_pool.clone(), config.clone()),
+        )),
+        cookie_key: signing_key(&config.cookie_secret),
+    };
+
+    Router::new()
+        .route("/login/options", post(login_options))
+        .route("/login", post(login))
+        .route(
+            "/register/options",
+            post(register_options).layer(middleware::from_fn_with_state(db_pool.clone(), auth)),
+        )
+        .route(
+            "/register",
+            post(register).layer(middleware::from_fn_with_state(db_pool.clone(), auth)),
+        )
+        .route("/", get(list).layer(middleware::from_fn_with_state(db_pool.clone(), auth)))
+        .route(
+            "/:id",
+            patch(rename)
+                .delete(remove)
+                .layer(middleware::from_fn_with_state(db_pool.clone(), auth)),
+        )
+        .with_state(passkey_state)
+}
+
+pub async fn register_options(
+    State(use_case): State<Arc<PasskeyUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    jar: SignedCookieJar,
+) -> Response {
+    match use_case.begin_registration(user_id).await {
+        Ok((options, ceremony)) => ceremony_response(jar, ceremony, options),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn register(
+    State(use_case): State<Arc<PasskeyUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    jar: SignedCookieJar,
+    Json(payload): Json<RegisterPasskeyRequest>,
+) -> Response {
+    let (jar, ceremony) = take_ceremony(jar);
+
+    match use_case.finish_registration(user_id, ceremony, payload).await {
+        Ok(passkey) => (jar, (StatusCode::CREATED, Json(passkey))).into_response(),
+        Err(e) => (jar, (StatusCode::BAD_REQUEST, e.to_string())).into_response(),
+    }
+}
+
+pub async fn list(
+    State(use_case): State<Arc<PasskeyUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.list(user_id).await {
+        Ok(passkeys) => (StatusCode::OK, Json(passkeys)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn rename(
+    State(use_case): State<Arc<PasskeyUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(passkey_id): Path<i32>,
+    Json(payload): Json<RenamePasskeyRequest>,
+) -> impl IntoResponse {
+    match use_case.rename(user_id, passkey_id, payload.name).await {
+        Ok(passkey) => (StatusCode::OK, Json(passkey)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn remove(
+    State(use_case): State<Arc<PasskeyUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(passkey_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.remove(user_id, passkey_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    }
+}
+
+pub async fn login_options(
+    State(use_case): State<Arc<PasskeyLoginUseCaseImpl>>,
+    jar: SignedCookieJar,
+    payload: Option<Json<PasskeyLoginOptionsRequest>>,
+) -> Response {
+    let request = payload.map(|Json(request)| request).unwrap_or_default();
+
+    match use_case.begin_passkey_login(request).await {
+        Ok((options, ceremony)) => ceremony_response(jar, ceremony, options),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn login(
+    State(use_case): State<Arc<PasskeyLoginUseCaseImpl>>,
+    jar: SignedCookieJar,
+    client: ClientContext,
+    cookie_jar: CookieJar,
+    Json(payload): Json<PasskeyLoginRequest>,
+) -> Response {
+    let (jar, ceremony) = take_ceremony(jar);
+
+    match use_case.login_with_passkey(ceremony, payload.credential, client).await {
+        Ok(outcome) => (jar, login_outcome_response(cookie_jar, outcome)).into_response(),
+        Err(e) => (jar, login_error_response(e)).into_response(),
+    }
+}
+
+// The challenge lives in a signed cookie between the options and finish calls, so it can only be
+// answered from the browser that asked for it. Expiry and single use are enforced by the use cases.
+fn ceremony_response<T: Serialize>(jar: SignedCookieJar, ceremony: WebAuthnCeremony, options: T) -> Response {
+    let ceremony_json = match serde_json::to_string(&ceremony) {
+        Ok(ceremony_json) => ceremony_json,
+        Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+    let timeout_ms = get_webauthn_env().map(|env| env.timeout_ms).unwrap_or(300_000);
+
+    let cookie = Cookie::build((CEREMONY_COOKIE, ceremony_json))
+        .path(CEREMONY_COOKIE_PATH)
+        .http_only(true)
+        .secure(secure_cookies())
+        .same_site(SameSite::Lax)
+        .max_age(cookie::time::Duration::milliseconds(timeout_ms as i64));
+
+    (jar.add(cookie), Json(options)).into_response()
+}
+
+// Clearing the cookie is a courtesy; a copied cookie is still refused once its challenge is consumed.
+fn take_ceremony(jar: SignedCookieJar) -> (SignedCookieJar, Option<WebAuthnCeremony>) {
+    let ceremony = jar
+        .get(CEREMONY_COOKIE)
+        .and_then(|cookie| serde_json::from_str::<WebAuthnCeremony>(cookie.value()).ok());
+    let jar = jar.remove(Cookie::build(CEREMONY_COOKIE).path(CEREMONY_COOKIE_PATH));
+
+    (jar, ceremony)
+}
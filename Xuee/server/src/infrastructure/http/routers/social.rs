@@ -0,0 +1,198 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+    routing::{delete, get, post},
+    Router,
+    middleware,
+};
+
+use crate::{
+    application::use_cases::social::SocialUseCase,
+    domain::value_objects::{
+        api_token_model::Scope,
+        social_model::{FriendRequestQuery, SendFriendRequestModel},
+    },
+    infrastructure::{
+        database::{
+            repositories::{brawlers::BrawlerPostgres, social::SocialPostgres},
+            postgresql_connection::PgPoolSquad,
+        },
+        http::middlewares::{auth::auth, require_scope::require_scope},
+    },
+};
+
+type SocialUseCaseImpl = SocialUseCase<BrawlerPostgres, SocialPostgres>;
+
+pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
+    let social_use_case = Arc::new(SocialUseCase::new(
+        Arc::new(BrawlerPostgres::new(db_pool.clone())),
+        Arc::new(SocialPostgres::new(db_pool.clone())),
+    ));
+
+    let read = Router::new()
+        .route("/followers", get(my_followers))
+        .route("/following", get(my_following))
+        .route("/friends", get(my_friends))
+        .route("/friend-requests", get(friend_requests))
+        .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+        .layer(middleware::from_fn_with_state(Scope::SocialRead, require_scope));
+
+    let write = Router::new()
+        .route("/follow/:id", post(follow).delete(unfollow))
+        .route("/friends/:id", delete(unfriend))
+        .route("/friend-requests", post(send_friend_request))
+        .route("/friend-requests/:id", delete(cancel_friend_request))
+        .route("/friend-requests/:id/accept", post(accept_friend_request))
+        .route("/friend-requests/:id/decline", post(decline_friend_request))
+        .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+        .layer(middleware::from_fn_with_state(Scope::SocialWrite, require_scope));
+
+    // Who follows whom is public, like the profile itself
+    Router::new()
+        .route("/brawlers/:id/followers", get(followers))
+        .route("/brawlers/:id/following", get(following))
+        .merge(read)
+        .merge(write)
+        .with_state(social_use_case)
+}
+
+pub async fn followers(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Path(brawler_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.followers(brawler_id).await {
+        Ok(brawlers) => (StatusCode::OK, Json(brawlers)).into_response(),
+        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    }
+}
+
+pub async fn following(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Path(brawler_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.following(brawler_id).await {
+        Ok(brawlers) => (StatusCode::OK, Json(brawlers)).into_response(),
+        Err(e) => (StatusCode::NOT_FOUND, e.to_string()).into_response(),
+    }
+}
+
+pub async fn my_followers(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.followers(user_id).await {
+        Ok(brawlers) => (StatusCode::OK, Json(brawlers)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn my_following(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.following(user_id).await {
+        Ok(brawlers) => (StatusCode::OK, Json(brawlers)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn my_friends(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.friends(user_id).await {
+        Ok(brawlers) => (StatusCode::OK, Json(brawlers)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+// `?direction=incoming` (default) or `?direction=outgoing`; only pending requests are listed.
+pub async fn friend_requests(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Query(query): Query<FriendRequestQuery>,
+) -> impl IntoResponse {
+    match use_case.friend_requests(user_id, query.direction).await {
+        Ok(requests) => (StatusCode::OK, Json(requests)).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+pub async fn follow(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(brawler_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.follow(user_id, brawler_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn unfollow(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(brawler_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.unfollow(user_id, brawler_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn unfriend(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(brawler_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.unfriend(user_id, brawler_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn send_friend_request(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Json(payload): Json<SendFriendRequestModel>,
+) -> impl IntoResponse {
+    match use_case.send_friend_request(user_id, payload.brawler_id).await {
+        Ok(response) => (StatusCode::OK, Json(response)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn accept_friend_request(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(request_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.accept_friend_request(user_id, request_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn decline_friend_request(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(request_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.decline_friend_request(user_id, request_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn cancel_friend_request(
+    State(use_case): State<Arc<SocialUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Path(request_id): Path<i32>,
+) -> impl IntoResponse {
+    match use_case.cancel_friend_request(user_id, request_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+    routing::post,
+    Router,
+    middleware,
+};
+
+use crate::{
+    application::use_cases::two_factor::TwoFactorUseCase,
+    domain::value_objects::two_factor_model::TwoFactorCodeRequest,
+    infrastructure::{
+        database::{
+            repositories::{
+                brawlers::BrawlerPostgres,
+                two_factor::TwoFactorPostgres,
+            },
+            postgresql_connection::PgPoolSquad,
+        },
+        http::middlewares::auth::auth,
+    },
+};
+
+type TwoFactorUseCaseImpl = TwoFactorUseCase<BrawlerPostgres, TwoFactorPostgres>;
+
+pub fn router(db_pool: Arc<PgPoolSquad>) -> Router {
+    let brawler_repository = BrawlerPostgres::new(db_pool.clone());
+    let two_factor_repository = TwoFactorPostgres::new(db_pool.clone());
+    let two_factor_use_case = Arc::new(TwoFactorUseCase::new(
+        Arc::new(brawler_repository),
+        Arc::new(two_factor_repository),
+    ));
+
+    Router::new()
+        .route("/enroll", post(enroll))
+        .route("/confirm", post(confirm))
+        .route("/disable", post(disable))
+        .route("/recovery-codes", post(regenerate_recovery_codes))
+        .layer(middleware::from_fn_with_state(db_pool.clone(), auth))
+        .with_state(two_factor_use_case)
+}
+
+pub async fn enroll(
+    State(use_case): State<Arc<TwoFactorUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+) -> impl IntoResponse {
+    match use_case.enroll(user_id).await {
+        Ok(enrollment) => (StatusCode::OK, Json(enrollment)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn confirm(
+    State(use_case): State<Arc<TwoFactorUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> impl IntoResponse {
+    match use_case.confirm(user_id, payload.code).await {
+        Ok(recovery_codes) => (StatusCode::OK, Json(recovery_codes)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn disable(
+    State(use_case): State<Arc<TwoFactorUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> impl IntoResponse {
+    match use_case.disable(user_id, payload.code).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn regenerate_recovery_codes(
+    State(use_case): State<Arc<TwoFactorUseCaseImpl>>,
+    Extension(user_id): Extension<i32>,
+    Json(payload): Json<TwoFactorCodeRequest>,
+) -> impl IntoResponse {
+    match use_case.regenerate_recovery_codes(user_id, payload.code).await {
+        Ok(recovery_codes) => (StatusCode::OK, Json(recovery_codes)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
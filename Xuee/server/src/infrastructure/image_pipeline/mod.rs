@@ -0,0 +1,207 @@
+// Turns an untrusted upload into a fixed set of square WebP thumbnails. Everything is decoded
+// and re-encoded, so metadata (EXIF location, ICC profiles) and trailing payloads are dropped.
+
+use std::fmt;
+use std::io::Cursor;
+use image::{imageops::FilterType, DynamicImage, ImageFormat, ImageReader, Limits};
+
+use crate::config::config_model::ImageUploadEnv;
+
+pub const AVATAR_SIZES: [u32; 3] = [512, 256, 64];
+
+const ALLOWED_FORMATS: [ImageFormat; 4] = [ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::WebP, ImageFormat::Gif];
+
+pub struct EncodedImage {
+    pub size: u32,
+    pub bytes: Vec<u8>,
+    pub content_type: &'static str,
+    pub extension: &'static str,
+}
+
+// Any upload the pipeline refuses; routers map it to 400.
+#[derive(Debug)]
+pub struct InvalidImage(pub String);
+
+impl fmt::Display for InvalidImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidImage {}
+
+fn invalid(message: impl Into<String>) -> anyhow::Error {
+    InvalidImage(message.into()).into()
+}
+
+pub fn process_avatar(bytes: &[u8], env: &ImageUploadEnv) -> anyhow::Result<Vec<EncodedImage>> {
+    if bytes.len() > env.max_bytes {
+        return Err(invalid(format!("Image must be at most {} KiB", env.max_bytes / 1024)));
+    }
+
+    // Sniff the real format from the bytes; whatever the client claimed is ignored
+    let format = image::guess_format(bytes).map_err(|_| invalid("Unrecognised image format"))?;
+    if !ALLOWED_FORMATS.contains(&format) {
+        return Err(invalid("Only PNG, JPEG, WebP and GIF images are accepted"));
+    }
+
+    let (width, height) = ImageReader::with_format(Cursor::new(bytes), format)
+        .into_dimensions()
+        .map_err(|_| invalid("Image header is corrupt"))?;
+    if width < env.min_dimension || height < env.min_dimension {
+        return Err(invalid(format!("Image must be at least {0}x{0} pixels", env.min_dimension)));
+    }
+    if width > env.max_dimension || height > env.max_dimension {
+        return Err(invalid(format!("Image must be at most {0}x{0} pixels", env.max_dimension)));
+    }
+
+    // Guards against decompression bombs whose header lies about the real size
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(env.max_dimension);
+    limits.max_image_height = Some(env.max_dimension);
+    let mut reader = ImageReader::with_format(Cursor::new(bytes), format);
+    reader.limits(limits);
+
+    let decoded = reader.decode().map_err(|_| invalid("Image could not be decoded"))?;
+    let square = crop_to_square(decoded);
+
+    AVATAR_SIZES
+        .iter()
+        .map(|&size| {
+            let resized = square.resize_exact(size, size, FilterType::Lanczos3).to_rgba8();
+
+            let mut bytes = Vec::new();
+            DynamicImage::ImageRgba8(resized).write_to(&mut Cursor::new(&mut bytes), ImageFormat::WebP)?;
+
+            Ok(EncodedImage {
+                size,
+                bytes,
+                content_type: "image/webp",
+                extension: "webp",
+            })
+        })
+        .collect()
+}
+
+// Storage key of one thumbnail, derived from the base id kept in `brawlers.avatar_public_id`.
+pub fn variant_key(public_id: &str, size: u32, extension: &str) -> String {
+    format!("{}_{}.{}", public_id, size, extension)
+}
+
+fn crop_to_square(image: DynamicImage) -> DynamicImage {
+    let side = image.width().min(image.height());
+    let x = (image.width() - side) / 2;
+    let y = (image.height() - side) / 2;
+
+    image.crop_imm(x, y, side, side)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn env() -> ImageUploadEnv {
+        ImageUploadEnv {
+            max_bytes: 5 * 1024 * 1024,
+            min_dimension: 64,
+            max_dimension: 4096,
+        }
+    }
+
+    fn encode(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let image = RgbaImage::from_pixel(width, height, Rgba([200, 40, 90, 255]));
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .to_rgb8()
+            .write_to(&mut Cursor::new(&mut bytes), format)
+            .unwrap();
+        bytes
+    }
+
+    fn rejection(bytes: &[u8], env: &ImageUploadEnv) -> String {
+        match process_avatar(bytes, env) {
+            Ok(_) => panic!("image was accepted"),
+            Err(e) => {
+                assert!(e.is::<InvalidImage>(), "{} is not an InvalidImage", e);
+                e.to_string()
+            }
+        }
+    }
+
+    #[test]
+    fn produces_every_avatar_size_as_square_webp() {
+        let encoded = process_avatar(&encode(640, 480, ImageFormat::Png), &env()).unwrap();
+
+        assert_eq!(encoded.iter().map(|image| image.size).collect::<Vec<_>>(), AVATAR_SIZES.to_vec());
+        for image in encoded {
+            assert_eq!(image.content_type, "image/webp");
+            assert_eq!(image.extension, "webp");
+            assert_eq!(image::guess_format(&image.bytes).unwrap(), ImageFormat::WebP);
+
+            let decoded = image::load_from_memory_with_format(&image.bytes, ImageFormat::WebP).unwrap();
+            assert_eq!((decoded.width(), decoded.height()), (image.size, image.size));
+        }
+    }
+
+    #[test]
+    fn accepts_each_allowed_format() {
+        for format in [ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::Gif] {
+            assert!(process_avatar(&encode(128, 128, format), &env()).is_ok(), "{:?} was rejected", format);
+        }
+    }
+
+    #[test]
+    fn rejects_uploads_over_the_byte_limit() {
+        let bytes = encode(128, 128, ImageFormat::Png);
+        let env = ImageUploadEnv {
+            max_bytes: bytes.len() - 1,
+            ..env()
+        };
+
+        assert!(rejection(&bytes, &env).contains("at most"));
+    }
+
+    #[test]
+    fn rejects_formats_outside_the_allowlist() {
+        // Recognised by their magic bytes, but not on the list
+        assert!(rejection(b"BM\x36\x00\x0c\x00\x00\x00\x00\x00\x36\x00\x00\x00", &env()).contains("Only PNG"));
+        assert!(rejection(b"II*\x00\x08\x00\x00\x00", &env()).contains("Only PNG"));
+        assert!(rejection(b"%PDF-1.7 not an image", &env()).contains("Unrecognised"));
+        assert!(rejection(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>", &env()).contains("Unrecognised"));
+    }
+
+    #[test]
+    fn rejects_a_corrupt_header() {
+        let mut bytes = encode(128, 128, ImageFormat::Png);
+        bytes.truncate(12);
+
+        assert!(rejection(&bytes, &env()).contains("corrupt"));
+    }
+
+    #[test]
+    fn rejects_images_outside_the_dimension_limits() {
+        assert!(rejection(&encode(32, 128, ImageFormat::Png), &env()).contains("at least 64x64"));
+        assert!(rejection(&encode(4097, 64, ImageFormat::Png), &env()).contains("at most 4096x4096"));
+    }
+
+    // A flat-colour image compresses to almost nothing, so its header is all that stops the
+    // decoder from allocating width * height * 4 bytes.
+    #[test]
+    fn rejects_small_files_that_decompress_to_large_images() {
+        let bytes = encode(4000, 4000, ImageFormat::Png);
+        let env = ImageUploadEnv {
+            max_bytes: 1024 * 1024,
+            min_dimension: 64,
+            max_dimension: 1024,
+        };
+
+        assert!(bytes.len() < env.max_bytes);
+        assert!(rejection(&bytes, &env).contains("at most 1024x1024"));
+    }
+
+    #[test]
+    fn variant_keys_carry_size_and_extension() {
+        assert_eq!(variant_key("avatars/7/abc", 256, "webp"), "avatars/7/abc_256.webp");
+    }
+}
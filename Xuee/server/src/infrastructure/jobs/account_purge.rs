@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Result;
+use tracing::{error, info};
+
+use crate::config::config_loader::get_account_deletion_env;
+use crate::infrastructure::database::postgresql_connection::PgPoolSquad;
+use crate::infrastructure::http::routers::accounts::build_use_case;
+use crate::infrastructure::services::image_storage::ConfiguredImageStorage;
+
+// Erases accounts whose deletion grace period has ended. Runs in-process on a fixed interval;
+// erasing is idempotent, so overlapping runs across several instances are harmless.
+pub fn spawn(db_pool: Arc<PgPoolSquad>, image_storage: Arc<ConfiguredImageStorage>) -> Result<()> {
+    let interval = Duration::from_secs(get_account_deletion_env()?.purge_interval.max(60));
+    let use_case = build_use_case(db_pool, image_storage);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match use_case.purge_due().await {
+                Ok(0) => {}
+                Ok(purged) => info!("Account purge erased {} account(s)", purged),
+                Err(e) => error!("Account purge failed: {}", e),
+            }
+        }
+    });
+
+    Ok(())
+}
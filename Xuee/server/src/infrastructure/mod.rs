@@ -1,5 +1,13 @@
 pub mod database;
 pub mod http;
+pub mod image_pipeline;
+pub mod jobs;
 pub mod argon2;
 pub mod services;
 pub mod jwt;
+pub mod opaque_token;
+pub mod password_policy;
+pub mod secret_box;
+pub mod totp;
+pub mod username_policy;
+pub mod webauthn;
@@ -0,0 +1,17 @@
+use rand::{distr::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+
+pub fn generate() -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(64)
+        .map(char::from)
+        .collect()
+}
+
+// Only the digest is persisted, so a leaked table cannot be replayed as tokens.
+pub fn hash(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    hex::encode(hasher.finalize())
+}
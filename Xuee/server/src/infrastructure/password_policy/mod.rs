@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::OnceLock;
+use anyhow::Result;
+
+use crate::config::config_loader::get_password_policy_env;
+
+// Bundled so the check works offline and never sends a password anywhere.
+const BREACHED_PASSWORDS: &str = include_str!("breached_passwords.txt");
+
+static BREACHED: OnceLock<HashSet<String>> = OnceLock::new();
+
+// Returned when a new password is rejected; routers map it to 400 rather than 500.
+#[derive(Debug)]
+pub struct PasswordPolicyViolation(pub String);
+
+impl fmt::Display for PasswordPolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for PasswordPolicyViolation {}
+
+fn breached() -> &'static HashSet<String> {
+    BREACHED.get_or_init(|| {
+        BREACHED_PASSWORDS
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_lowercase)
+            .collect()
+    })
+}
+
+pub fn check(password: &str, username: &str) -> Result<()> {
+    let policy = get_password_policy_env()?;
+    let length = password.chars().count();
+
+    if length < policy.min_length {
+        return Err(PasswordPolicyViolation(format!(
+            "Password must be at least {} characters long",
+            policy.min_length
+        ))
+        .into());
+    }
+    if length > policy.max_length {
+        return Err(PasswordPolicyViolation(format!(
+            "Password must be at most {} characters long",
+            policy.max_length
+        ))
+        .into());
+    }
+
+    let lowered = password.to_lowercase();
+    if lowered == username.trim().to_lowercase() {
+        return Err(PasswordPolicyViolation("Password must not match your username".to_string()).into());
+    }
+    if breached().contains(&lowered) {
+        return Err(PasswordPolicyViolation(
+            "This password has appeared in a data breach, please choose another".to_string(),
+        )
+        .into());
+    }
+
+    Ok(())
+}
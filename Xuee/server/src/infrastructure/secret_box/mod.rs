@@ -0,0 +1,102 @@
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use anyhow::{anyhow, Result};
+
+const NONCE_LEN: usize = 12;
+
+// Stored as hex(nonce || ciphertext) so a single column holds everything needed to decrypt.
+pub fn encrypt(key_hex: &str, plaintext: &str) -> Result<String> {
+    let cipher = cipher(key_hex)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| anyhow!("Failed to encrypt secret"))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(hex::encode(sealed))
+}
+
+pub fn decrypt(key_hex: &str, sealed_hex: &str) -> Result<String> {
+    let cipher = cipher(key_hex)?;
+    let sealed = hex::decode(sealed_hex)?;
+    if sealed.len() <= NONCE_LEN {
+        return Err(anyhow!("Sealed secret is too short"));
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Failed to decrypt secret"))?;
+
+    Ok(String::from_utf8(plaintext)?)
+}
+
+fn cipher(key_hex: &str) -> Result<Aes256Gcm> {
+    let key = hex::decode(key_hex)?;
+    if key.len() != 32 {
+        return Err(anyhow!("Encryption key must be 32 bytes of hex"));
+    }
+
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+    const OTHER_KEY: &str = "1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100";
+
+    #[test]
+    fn round_trips_a_secret() {
+        let sealed = encrypt(KEY, "JBSWY3DPEHPK3PXP").unwrap();
+
+        assert!(!sealed.contains("JBSWY3DPEHPK3PXP"));
+        assert_eq!(decrypt(KEY, &sealed).unwrap(), "JBSWY3DPEHPK3PXP");
+    }
+
+    #[test]
+    fn uses_a_fresh_nonce_every_time() {
+        let first = encrypt(KEY, "same secret").unwrap();
+        let second = encrypt(KEY, "same secret").unwrap();
+
+        assert_ne!(first[..NONCE_LEN * 2], second[..NONCE_LEN * 2]);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn refuses_to_open_with_another_key() {
+        let sealed = encrypt(KEY, "secret").unwrap();
+
+        assert!(decrypt(OTHER_KEY, &sealed).is_err());
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let mut sealed = hex::decode(encrypt(KEY, "secret").unwrap()).unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+
+        assert!(decrypt(KEY, &hex::encode(&sealed)).is_err());
+    }
+
+    #[test]
+    fn rejects_input_too_short_to_hold_a_nonce() {
+        let error = decrypt(KEY, &hex::encode([0u8; NONCE_LEN])).unwrap_err();
+        assert_eq!(error.to_string(), "Sealed secret is too short");
+
+        assert!(decrypt(KEY, "not hex").is_err());
+    }
+
+    #[test]
+    fn requires_a_256_bit_key() {
+        let error = encrypt(&KEY[..32], "secret").unwrap_err();
+        assert_eq!(error.to_string(), "Encryption key must be 32 bytes of hex");
+
+        assert!(encrypt("zz", "secret").is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::env;
+
+use crate::config::{config_loader::get_stage, stage::Stage};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DevIdentity {
+    pub username: String,
+    pub display_name: String,
+}
+
+// Signs in fixture users without a password. Only compiled with the `dev-identity`
+// feature and only handed out when the server runs as `Stage::Local`.
+#[derive(Debug, Clone)]
+pub struct DevIdentityProvider {
+    identities: Vec<DevIdentity>,
+}
+
+impl DevIdentityProvider {
+    pub fn from_env() -> Result<Option<Self>> {
+        if get_stage() != Stage::Local {
+            return Ok(None);
+        }
+
+        let path = env::var("DEV_IDENTITY_FIXTURES").unwrap_or_else(|_| "dev_identities.json".to_string());
+        let fixtures = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Failed to read dev identity fixtures '{}': {}", path, e))?;
+        let identities: Vec<DevIdentity> = serde_json::from_str(&fixtures)?;
+
+        Ok(Some(Self { identities }))
+    }
+
+    pub fn identities(&self) -> &[DevIdentity] {
+        &self.identities
+    }
+
+    pub fn find(&self, username: &str) -> Result<&DevIdentity> {
+        self.identities
+            .iter()
+            .find(|identity| identity.username == username)
+            .ok_or_else(|| anyhow!("Unknown dev identity '{}'", username))
+    }
+}
@@ -2,7 +2,7 @@ use lettre::{Message, SmtpTransport, Transport};
 use lettre::transport::smtp::authentication::Credentials;
 use std::env;
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct EmailService;
 
 impl EmailService {
@@ -11,68 +11,100 @@ impl EmailService {
     }
 
     pub async fn send_welcome_email(&self, to_email: &str, username: &str) -> anyhow::Result<()> {
-        let smtp_host = env::var("SMTP_HOST").unwrap_or_else(|_| "smtp.gmail.com".to_string());
-        let smtp_user = env::var("SMTP_USER").unwrap_or_else(|_| "test@example.com".to_string());
-        let smtp_pass = env::var("SMTP_PASS").unwrap_or_else(|_| "password".to_string());
-        let from_email = env::var("SMTP_FROM").unwrap_or_else(|_| "no-reply@nebula.com".to_string());
+        self.send(
+            to_email,
+            "Welcome to Nebula!",
+            format!("Hello {}, welcome to Nebula! We are glad to have you.", username),
+        )
+        .await
+    }
 
-        // If credentials are defaults/dummy, just log and return (mock mode)
-        if smtp_user == "test@example.com" {
-            println!("--------------------------------------------------");
-            println!("(Mock) Sending Welcome Email to: {}", to_email);
-            println!("Subject: Welcome to Nebula!");
-            println!("Body: Hello {}, welcome to our platform!", username);
-            println!("--------------------------------------------------");
-            return Ok(());
-        }
+    pub async fn send_password_reset_email(&self, to_email: &str, username: &str, token: &str) -> anyhow::Result<()> {
+        let reset_url = format!("{}/reset-password?token={}", Self::frontend_url(), token);
 
-        let email = Message::builder()
-            .from(from_email.parse()?)
-            .to(to_email.parse()?)
-            .subject("Welcome to Nebula!")
-            .body(format!("Hello {}, welcome to Nebula! We are glad to have you.", username))?;
+        self.send(
+            to_email,
+            "Reset your Nebula Password",
+            format!(
+                "Hello {},\n\nYou requested a password reset. Please click the link below to reset your password:\n\n{}\n\nIf you did not request this, please ignore this email.",
+                username, reset_url
+            ),
+        )
+        .await
+    }
 
-        let creds = Credentials::new(smtp_user, smtp_pass);
+    pub async fn send_magic_link_email(&self, to_email: &str, username: &str, token: &str, ttl_minutes: i64) -> anyhow::Result<()> {
+        let sign_in_url = format!("{}/magic-link?token={}", Self::frontend_url(), token);
+
+        self.send(
+            to_email,
+            "Your Nebula sign-in link",
+            format!(
+                "Hello {},\n\nClick the link below to sign in to Nebula. It works once and expires in {} minutes:\n\n{}\n\nIf you did not ask to sign in, you can ignore this email.",
+                username, ttl_minutes, sign_in_url
+            ),
+        )
+        .await
+    }
 
-        // Open a remote connection to gmail
-        let mailer = SmtpTransport::relay(&smtp_host)?
-            .credentials(creds)
-            .build();
+    pub async fn send_verification_email(&self, to_email: &str, username: &str, token: &str) -> anyhow::Result<()> {
+        let verify_url = format!("{}/verify-email?token={}", Self::frontend_url(), token);
+
+        self.send(
+            to_email,
+            "Verify your Nebula email address",
+            format!(
+                "Hello {},\n\nPlease confirm that this address belongs to you by clicking the link below:\n\n{}\n\nIf you did not create a Nebula account, please ignore this email.",
+                username, verify_url
+            ),
+        )
+        .await
+    }
 
-        // Send the email
-        // Note: SmtpTransport::send is blocking, but we are in async. 
-        // In a real app, use AsyncSmtpTransport or spawn_blocking.
-        // For now, spawn_blocking is safer.
-        let mailer = std::sync::Arc::new(mailer);
-        let email = std::sync::Arc::new(email);
-        
-        let mailer_clone = mailer.clone();
-        let email_clone = email.clone();
+    pub async fn send_account_locked_email(&self, to_email: &str, username: &str, lockout_minutes: i64) -> anyhow::Result<()> {
+        let reset_url = format!("{}/forgot-password", Self::frontend_url());
+
+        self.send(
+            to_email,
+            "Your Nebula account has been temporarily locked",
+            format!(
+                "Hello {},\n\nWe locked your account for {} minutes after several failed sign-in attempts.\n\nIf this was not you, we recommend resetting your password:\n\n{}",
+                username, lockout_minutes, reset_url
+            ),
+        )
+        .await
+    }
 
-        tokio::task::spawn_blocking(move || {
-            match mailer_clone.send(&email_clone) {
-                Ok(_) => println!("Email sent successfully!"),
-                Err(e) => println!("Could not send email: {:?}", e),
-            }
-        }).await?;
+    pub async fn send_account_deletion_email(&self, to_email: &str, username: &str, scheduled_for: &str) -> anyhow::Result<()> {
+        let settings_url = format!("{}/settings/account", Self::frontend_url());
+
+        self.send(
+            to_email,
+            "Your Nebula account is scheduled for deletion",
+            format!(
+                "Hello {},\n\nYour account and its data will be permanently deleted on {} (UTC).\n\nIf you change your mind, sign in before then and cancel the deletion here:\n\n{}",
+                username, scheduled_for, settings_url
+            ),
+        )
+        .await
+    }
 
-        Ok(())
+    fn frontend_url() -> String {
+        env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:4200".to_string())
     }
 
-    pub async fn send_password_reset_email(&self, to_email: &str, username: &str, token: &str) -> anyhow::Result<()> {
+    async fn send(&self, to_email: &str, subject: &str, body: String) -> anyhow::Result<()> {
         let smtp_host = env::var("SMTP_HOST").unwrap_or_else(|_| "smtp.gmail.com".to_string());
         let smtp_user = env::var("SMTP_USER").unwrap_or_else(|_| "test@example.com".to_string());
         let smtp_pass = env::var("SMTP_PASS").unwrap_or_else(|_| "password".to_string());
         let from_email = env::var("SMTP_FROM").unwrap_or_else(|_| "no-reply@nebula.com".to_string());
-        let frontend_url = env::var("FRONTEND_URL").unwrap_or_else(|_| "http://localhost:4200".to_string());
-
-        let reset_url = format!("{}/reset-password?token={}", frontend_url, token);
 
+        // If credentials are defaults/dummy, just log and return (mock mode)
         if smtp_user == "test@example.com" {
             println!("--------------------------------------------------");
-            println!("(Mock) Sending Password Reset Email to: {}", to_email);
-            println!("Subject: Reset your password");
-            println!("Body: Hello {}, click here to reset your password: {}", username, reset_url);
+            println!("(Mock) Sending Email to: {}", to_email);
+            println!("Subject: {}", subject);
+            println!("Body: {}", body);
             println!("--------------------------------------------------");
             return Ok(());
         }
@@ -80,27 +112,30 @@ impl EmailService {
         let email = Message::builder()
             .from(from_email.parse()?)
             .to(to_email.parse()?)
-            .subject("Reset your Nebula Password")
-            .body(format!(
-                "Hello {},\n\nYou requested a password reset. Please click the link below to reset your password:\n\n{}\n\nIf you did not request this, please ignore this email.",
-                username, reset_url
-            ))?;
+            .subject(subject)
+            .body(body)?;
 
         let creds = Credentials::new(smtp_user, smtp_pass);
+
+        // Open a remote connection to gmail
         let mailer = SmtpTransport::relay(&smtp_host)?
             .credentials(creds)
             .build();
 
+        // Send the email
+        // Note: SmtpTransport::send is blocking, but we are in async.
+        // In a real app, use AsyncSmtpTransport or spawn_blocking.
+        // For now, spawn_blocking is safer.
         let mailer = std::sync::Arc::new(mailer);
         let email = std::sync::Arc::new(email);
-        
+
         let mailer_clone = mailer.clone();
         let email_clone = email.clone();
 
         tokio::task::spawn_blocking(move || {
             match mailer_clone.send(&email_clone) {
-                Ok(_) => println!("Reset email sent successfully!"),
-                Err(e) => println!("Could not send reset email: {:?}", e),
+                Ok(_) => println!("Email sent successfully!"),
+                Err(e) => println!("Could not send email: {:?}", e),
             }
         }).await?;
 
@@ -0,0 +1,126 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use data_encoding::BASE64;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use sha1::{Digest, Sha1};
+
+use super::ImageStorage;
+use crate::config::config_loader::get_cloudinary_env;
+use crate::config::config_model::CloudinaryEnv;
+
+#[derive(Debug, Deserialize)]
+struct CloudinaryResponse {
+    secure_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DestroyResponse {
+    result: String,
+}
+
+pub struct CloudinaryImageStorage {
+    client: Client,
+    config: CloudinaryEnv,
+}
+
+impl CloudinaryImageStorage {
+    pub fn from_env() -> Result<Self> {
+        let config = get_cloudinary_env().map_err(|e| anyhow!("Failed to load Cloudinary config: {}", e))?;
+
+        Ok(Self {
+            client: Client::new(),
+            config,
+        })
+    }
+
+    fn endpoint(&self, action: &str) -> String {
+        format!("https://api.cloudinary.com/v1_1/{}/image/{}", self.config.cloud_name, action)
+    }
+
+    // Cloudinary signs the alphabetically sorted parameters followed by the API secret
+    fn sign(&self, public_id: &str, timestamp: &str) -> String {
+        let string_to_sign = format!("public_id={}&timestamp={}{}", public_id, timestamp, self.config.api_secret);
+
+        let mut hasher = Sha1::new();
+        hasher.update(string_to_sign.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+// Cloudinary adds its own extension, so the public id is the key without one.
+fn public_id(key: &str) -> &str {
+    key.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(key)
+}
+
+#[async_trait]
+impl ImageStorage for CloudinaryImageStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<String> {
+        let public_id = public_id(key);
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+
+        let params = json!({
+            "file": format!("data:{};base64,{}", content_type, BASE64.encode(&bytes)),
+            "public_id": public_id,
+            "api_key": self.config.api_key,
+            "timestamp": timestamp,
+            "signature": self.sign(public_id, &timestamp),
+        });
+
+        let response = self
+            .client
+            .post(self.endpoint("upload"))
+            .json(&params)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to send request to Cloudinary: {}", e))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Cloudinary upload failed: {}", error_text));
+        }
+
+        let cloudinary_response: CloudinaryResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse Cloudinary response: {}", e))?;
+
+        Ok(cloudinary_response.secure_url)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let public_id = public_id(key);
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+
+        let params = json!({
+            "public_id": public_id,
+            "api_key": self.config.api_key,
+            "timestamp": timestamp,
+            "signature": self.sign(public_id, &timestamp),
+        });
+
+        let response = self
+            .client
+            .post(self.endpoint("destroy"))
+            .json(&params)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to send request to Cloudinary: {}", e))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Cloudinary delete failed: {}", error_text));
+        }
+
+        // "not found" means it is already gone, which is what we wanted
+        let destroy_response: DestroyResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse Cloudinary response: {}", e))?;
+        match destroy_response.result.as_str() {
+            "ok" | "not found" => Ok(()),
+            other => Err(anyhow!("Cloudinary delete failed: {}", other)),
+        }
+    }
+}
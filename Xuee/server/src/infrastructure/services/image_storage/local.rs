@@ -0,0 +1,51 @@
+use std::io::ErrorKind;
+use std::path::{Component, Path, PathBuf};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use super::ImageStorage;
+
+pub struct LocalImageStorage {
+    root: PathBuf,
+    public_base_url: String,
+}
+
+impl LocalImageStorage {
+    pub fn new(root: impl Into<PathBuf>, public_base_url: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            public_base_url: public_base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    // Keys are generated server-side, but never let one escape the storage root
+    fn path_for(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        if key.is_empty() || !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+            return Err(anyhow!("Invalid storage key: {}", key));
+        }
+
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait]
+impl ImageStorage for LocalImageStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<String> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, bytes).await?;
+
+        Ok(format!("{}/{}", self.public_base_url, key))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match tokio::fs::remove_file(self.path_for(key)?).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::config::config_loader::get_image_storage_env;
+use crate::config::config_model::ImageStorageBackend;
+
+pub mod cloudinary;
+pub mod local;
+
+pub use cloudinary::CloudinaryImageStorage;
+pub use local::LocalImageStorage;
+
+// Keys are relative paths such as `avatars/12/k3j9x_256.webp`; each backend maps them to its own
+// naming and returns the public URL.
+#[async_trait]
+#[automock]
+pub trait ImageStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<String>;
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+// Built once in main from IMAGE_STORAGE and handed to the routers, so use cases stay generic over a
+// single concrete type and a misconfigured backend stops the server at startup.
+pub enum ConfiguredImageStorage {
+    Local(LocalImageStorage),
+    Cloudinary(CloudinaryImageStorage),
+}
+
+impl ConfiguredImageStorage {
+    pub fn from_env() -> Result<Self> {
+        let env = get_image_storage_env()?;
+
+        Ok(match env.backend {
+            ImageStorageBackend::Local => Self::Local(LocalImageStorage::new(env.local_dir, env.public_base_url)),
+            ImageStorageBackend::Cloudinary => Self::Cloudinary(CloudinaryImageStorage::from_env()?),
+        })
+    }
+}
+
+#[async_trait]
+impl ImageStorage for ConfiguredImageStorage {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<String> {
+        match self {
+            Self::Local(storage) => storage.put(key, bytes, content_type).await,
+            Self::Cloudinary(storage) => storage.put(key, bytes, content_type).await,
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        match self {
+            Self::Local(storage) => storage.delete(key).await,
+            Self::Cloudinary(storage) => storage.delete(key).await,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::future::IntoFuture;
+use axum::{
+    extract::{Form, State},
+    http::StatusCode,
+    response::{IntoResponse, Json},
+    routing::{get, post},
+    Router,
+};
+use chrono::Utc;
+use data_encoding::BASE64URL_NOPAD;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use p256::ecdsa::SigningKey;
+use p256::pkcs8::EncodePrivateKey;
+use serde_json::{json, Value};
+
+use crate::config::config_model::OidcProviderEnv;
+
+pub const CLIENT_ID: &str = "nebula-test";
+pub const VALID_CODE: &str = "valid-code";
+pub const NONCE: &str = "expected-nonce";
+
+const KEY_ID: &str = "mock-key";
+
+// What the mock identity provider says about the signed-in account, plus knobs for the ways a
+// real provider (or an attacker in front of one) can get things wrong.
+#[derive(Debug, Clone)]
+pub struct MockOidcSettings {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+    pub nonce: String,
+    // Overrides for the issuer reported by discovery, the `iss` in the ID token and the userinfo `sub`
+    pub discovery_issuer: Option<String>,
+    pub id_token_issuer: Option<String>,
+    pub userinfo_subject: Option<String>,
+}
+
+impl Default for MockOidcSettings {
+    fn default() -> Self {
+        Self {
+            subject: "mock-subject".to_string(),
+            email: Some("robin@example.com".to_string()),
+            email_verified: true,
+            name: Some("Robin".to_string()),
+            preferred_username: Some("robin".to_string()),
+            nonce: NONCE.to_string(),
+            discovery_issuer: None,
+            id_token_issuer: None,
+            userinfo_subject: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct MockState {
+    issuer: String,
+    settings: MockOidcSettings,
+}
+
+// An in-process OIDC provider serving discovery, JWKS, token and userinfo on a random local port.
+pub struct MockOidcProvider {
+    pub issuer: String,
+}
+
+impl MockOidcProvider {
+    pub async fn start(settings: MockOidcSettings) -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.expect("bind mock provider");
+        let issuer = format!("http://{}", listener.local_addr().expect("local address"));
+
+        let app = Router::new()
+            .route("/.well-known/openid-configuration", get(discovery))
+            .route("/jwks", get(jwks))
+            .route("/token", post(token))
+            .route("/userinfo", get(userinfo))
+            .with_state(MockState {
+                issuer: issuer.clone(),
+                settings,
+            });
+        tokio::spawn(axum::serve(listener, app).into_future());
+
+        Self { issuer }
+    }
+
+    pub fn provider_env(&self, name: &str) -> OidcProviderEnv {
+        OidcProviderEnv {
+            name: name.to_string(),
+            issuer_url: Some(self.issuer.clone()),
+            authorization_url: None,
+            token_url: None,
+            userinfo_url: None,
+            client_id: CLIENT_ID.to_string(),
+            client_secret: "mock-secret".to_string(),
+            redirect_url: "http://localhost:4200/oidc-callback/mock".to_string(),
+            scopes: vec!["openid".to_string(), "email".to_string(), "profile".to_string()],
+        }
+    }
+}
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_slice(&[9u8; 32]).expect("valid P-256 scalar")
+}
+
+async fn discovery(State(state): State<MockState>) -> impl IntoResponse {
+    Json(json!({
+        "issuer": state.settings.discovery_issuer.clone().unwrap_or_else(|| state.issuer.clone()),
+        "authorization_endpoint": format!("{}/authorize", state.issuer),
+        "token_endpoint": format!("{}/token", state.issuer),
+        "userinfo_endpoint": format!("{}/userinfo", state.issuer),
+        "jwks_uri": format!("{}/jwks", state.issuer),
+    }))
+}
+
+async fn jwks() -> impl IntoResponse {
+    let point = signing_key().verifying_key().to_encoded_point(false);
+
+    Json(json!({
+        "keys": [{
+            "kty": "EC",
+            "crv": "P-256",
+            "use": "sig",
+            "alg": "ES256",
+            "kid": KEY_ID,
+            "x": BASE64URL_NOPAD.encode(point.x().expect("uncompressed point")),
+            "y": BASE64URL_NOPAD.encode(point.y().expect("uncompressed point")),
+        }]
+    }))
+}
+
+async fn token(State(state): State<MockState>, Form(form): Form<HashMap<String, String>>) -> impl IntoResponse {
+    if form.get("code").map(String::as_str) != Some(VALID_CODE) || !form.contains_key("code_verifier") {
+        return (StatusCode::BAD_REQUEST, Json(json!({ "error": "invalid_grant" }))).into_response();
+    }
+
+    let now = Utc::now().timestamp();
+    let claims = json!({
+        "iss": state.settings.id_token_issuer.clone().unwrap_or_else(|| state.issuer.clone()),
+        "aud": CLIENT_ID,
+        "sub": state.settings.subject,
+        "iat": now,
+        "exp": now + 300,
+        "nonce": state.settings.nonce,
+    });
+    let mut header = Header::new(Algorithm::ES256);
+    header.kid = Some(KEY_ID.to_string());
+    let key_der = signing_key().to_pkcs8_der().expect("encodable key");
+    let id_token = encode(&header, &claims, &EncodingKey::from_ec_der(key_der.as_bytes())).expect("signed ID token");
+
+    Json(json!({
+        "access_token": "mock-access-token",
+        "token_type": "Bearer",
+        "expires_in": 3600,
+        "id_token": id_token,
+    }))
+    .into_response()
+}
+
+async fn userinfo(State(state): State<MockState>) -> impl IntoResponse {
+    let settings = state.settings;
+    let mut claims = json!({
+        "sub": settings.userinfo_subject.unwrap_or(settings.subject),
+        "email_verified": settings.email_verified,
+    });
+    for (key, value) in [
+        ("email", settings.email),
+        ("name", settings.name),
+        ("preferred_username", settings.preferred_username),
+    ] {
+        if let Some(value) = value {
+            claims[key] = Value::String(value);
+        }
+    }
+
+    Json(claims)
+}
@@ -1,3 +1,8 @@
 pub mod image_storage;
 pub mod email_service;
-pub mod google_auth_service;
+pub mod oidc_service;
+pub mod token_service;
+#[cfg(feature = "dev-identity")]
+pub mod dev_identity_provider;
+#[cfg(test)]
+pub mod mock_oidc_provider;
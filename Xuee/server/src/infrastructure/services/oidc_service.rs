@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+use anyhow::{anyhow, Result};
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use oauth2::{
+    basic::{BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse, BasicTokenType},
+    AuthUrl, AuthorizationCode, Client, ClientId, ClientSecret, CsrfToken, ExtraTokenFields, PkceCodeChallenge,
+    PkceCodeVerifier, RedirectUrl, Scope, StandardRevocableToken, StandardTokenResponse, TokenResponse, TokenUrl,
+};
+use oauth2::reqwest::async_http_client;
+use rand::{distr::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::OnceCell;
+
+use crate::config::config_loader::get_oidc_env;
+use crate::config::config_model::OidcProviderEnv;
+
+#[derive(Debug, Clone)]
+pub struct OidcUserInfo {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+    pub preferred_username: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    pub url: String,
+    pub state: String,
+    pub pkce_verifier: String,
+    // Only OIDC providers (those with an issuer) get a nonce; plain OAuth has no ID token to bind it to.
+    pub nonce: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscoveryDocument {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    userinfo_endpoint: Option<String>,
+    jwks_uri: Option<String>,
+}
+
+#[derive(Debug)]
+struct ProviderEndpoints {
+    authorization_url: String,
+    token_url: String,
+    userinfo_url: String,
+    // Set when the provider was discovered; the ID token is then required and checked against it.
+    id_token: Option<IdTokenIssuer>,
+}
+
+#[derive(Debug)]
+struct IdTokenIssuer {
+    issuer: String,
+    jwks_url: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct IdTokenFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id_token: Option<String>,
+}
+
+impl ExtraTokenFields for IdTokenFields {}
+
+type OidcClient = Client<
+    BasicErrorResponse,
+    StandardTokenResponse<IdTokenFields, BasicTokenType>,
+    BasicTokenType,
+    BasicTokenIntrospectionResponse,
+    StandardRevocableToken,
+    BasicRevocationErrorResponse,
+>;
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+}
+
+// Asymmetric only: the JWKS is public, so an HMAC "key" taken from it would prove nothing.
+const ID_TOKEN_ALGORITHMS: [Algorithm; 6] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::ES256,
+    Algorithm::ES384,
+];
+
+struct OidcProvider {
+    env: OidcProviderEnv,
+    // Discovery runs on first use so a provider that is down does not block startup.
+    endpoints: OnceCell<ProviderEndpoints>,
+}
+
+pub struct OidcService {
+    providers: HashMap<String, OidcProvider>,
+    http_client: reqwest::Client,
+}
+
+impl OidcService {
+    pub fn new(provider_envs: Vec<OidcProviderEnv>) -> Self {
+        let providers = provider_envs
+            .into_iter()
+            .map(|env| {
+                (
+                    env.name.clone(),
+                    OidcProvider {
+                        env,
+                        endpoints: OnceCell::new(),
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            providers,
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Ok(Self::new(get_oidc_env()?))
+    }
+
+    pub fn provider_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.providers.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    pub async fn authorization_url(&self, provider_name: &str) -> Result<AuthorizationRequest> {
+        let provider = self.provider(provider_name)?;
+        let client = self.client(provider).await?;
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+        let nonce = self.endpoints(provider).await?.id_token.as_ref().map(|_| {
+            rand::rng()
+                .sample_iter(&Alphanumeric)
+                .take(32)
+                .map(char::from)
+                .collect::<String>()
+        });
+
+        let mut request = client
+            .authorize_url(CsrfToken::new_random)
+            .set_pkce_challenge(pkce_challenge);
+        for scope in &provider.env.scopes {
+            request = request.add_scope(Scope::new(scope.clone()));
+        }
+        if let Some(nonce) = nonce.as_ref() {
+            request = request.add_extra_param("nonce", nonce.clone());
+        }
+        let (auth_url, csrf_token) = request.url();
+
+        Ok(AuthorizationRequest {
+            url: auth_url.to_string(),
+            state: csrf_token.secret().clone(),
+            pkce_verifier: pkce_verifier.secret().clone(),
+            nonce,
+        })
+    }
+
+    pub async fn exchange_code(
+        &self,
+        provider_name: &str,
+        code: String,
+        pkce_verifier: String,
+        nonce: Option<String>,
+    ) -> Result<OidcUserInfo> {
+        let provider = self.provider(provider_name)?;
+        let client = self.client(provider).await?;
+
+        let token_response = client
+            .exchange_code(AuthorizationCode::new(code))
+            .set_pkce_verifier(PkceCodeVerifier::new(pkce_verifier))
+            .request_async(async_http_client)
+            .await
+            .map_err(|e| anyhow!("Token exchange failed: {}", e))?;
+
+        let endpoints = self.endpoints(provider).await?;
+        let id_token_subject = match &endpoints.id_token {
+            Some(issuer) => {
+                let id_token = token_response
+                    .extra_fields()
+                    .id_token
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("Identity provider returned no ID token"))?;
+                Some(self.verify_id_token(provider, issuer, id_token, nonce.as_deref()).await?)
+            }
+            None => None,
+        };
+
+        let claims: Value = self
+            .http_client
+            .get(&endpoints.userinfo_url)
+            .bearer_auth(token_response.access_token().secret())
+            // Some providers (GitHub) reject requests without a user agent
+            .header(reqwest::header::USER_AGENT, "nebula-server")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let user_info = Self::to_user_info(&claims)?;
+        // Userinfo must describe the same account the ID token was issued for
+        if id_token_subject.is_some_and(|subject| subject != user_info.subject) {
+            return Err(anyhow!("ID token and userinfo subjects do not match"));
+        }
+
+        Ok(user_info)
+    }
+
+    // Returns the token's subject once the signature, issuer, audience, expiry and nonce all check out.
+    async fn verify_id_token(
+        &self,
+        provider: &OidcProvider,
+        issuer: &IdTokenIssuer,
+        id_token: &str,
+        nonce: Option<&str>,
+    ) -> Result<String> {
+        let header = decode_header(id_token)?;
+        if !ID_TOKEN_ALGORITHMS.contains(&header.alg) {
+            return Err(anyhow!("ID token uses an unsupported algorithm"));
+        }
+
+        let jwks: JwkSet = self
+            .http_client
+            .get(&issuer.jwks_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let jwk = match header.kid.as_deref() {
+            Some(kid) => jwks.find(kid),
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or_else(|| anyhow!("ID token was signed with an unknown key"))?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_audience(&[&provider.env.client_id]);
+        validation.set_issuer(&[&issuer.issuer]);
+        let claims = decode::<IdTokenClaims>(id_token, &DecodingKey::from_jwk(jwk)?, &validation)
+            .map_err(|e| anyhow!("ID token is invalid: {}", e))?
+            .claims;
+
+        // Ties the token to the browser that started the flow, so a token issued elsewhere cannot be injected
+        if nonce.is_none() || claims.nonce.as_deref() != nonce {
+            return Err(anyhow!("ID token nonce does not match"));
+        }
+
+        Ok(claims.sub)
+    }
+
+    fn provider(&self, provider_name: &str) -> Result<&OidcProvider> {
+        self.providers
+            .get(provider_name)
+            .ok_or_else(|| anyhow!("Unknown identity provider '{}'", provider_name))
+    }
+
+    async fn client(&self, provider: &OidcProvider) -> Result<OidcClient> {
+        let endpoints = self.endpoints(provider).await?;
+
+        Ok(OidcClient::new(
+            ClientId::new(provider.env.client_id.clone()),
+            Some(ClientSecret::new(provider.env.client_secret.clone())),
+            AuthUrl::new(endpoints.authorization_url.clone())?,
+            Some(TokenUrl::new(endpoints.token_url.clone())?),
+        )
+        .set_redirect_uri(RedirectUrl::new(provider.env.redirect_url.clone())?))
+    }
+
+    async fn endpoints<'a>(&self, provider: &'a OidcProvider) -> Result<&'a ProviderEndpoints> {
+        provider
+            .endpoints
+            .get_or_try_init(|| async {
+                let env = &provider.env;
+                let discovered = match &env.issuer_url {
+                    Some(issuer_url) => {
+                        let document = self.discover(issuer_url).await?;
+                        // A document for some other issuer means discovery was pointed somewhere it should not be
+                        if document.issuer.trim_end_matches('/') != issuer_url.trim_end_matches('/') {
+                            return Err(anyhow!(
+                                "Identity provider '{}' reported issuer '{}', expected '{}'",
+                                env.name,
+                                document.issuer,
+                                issuer_url
+                            ));
+                        }
+                        Some(document)
+                    }
+                    None => None,
+                };
+
+                // Explicit endpoints win over discovery, which covers OAuth-only providers such as GitHub.
+                let pick = |explicit: &Option<String>, discovered: Option<String>, field: &str| {
+                    explicit
+                        .clone()
+                        .or(discovered)
+                        .ok_or_else(|| anyhow!("Identity provider '{}' has no {}", env.name, field))
+                };
+
+                Ok::<_, anyhow::Error>(ProviderEndpoints {
+                    authorization_url: pick(
+                        &env.authorization_url,
+                        discovered.as_ref().map(|d| d.authorization_endpoint.clone()),
+                        "authorization endpoint",
+                    )?,
+                    token_url: pick(
+                        &env.token_url,
+                        discovered.as_ref().map(|d| d.token_endpoint.clone()),
+                        "token endpoint",
+                    )?,
+                    userinfo_url: pick(
+                        &env.userinfo_url,
+                        discovered.as_ref().and_then(|d| d.userinfo_endpoint.clone()),
+                        "userinfo endpoint",
+                    )?,
+                    id_token: match discovered {
+                        Some(document) => Some(IdTokenIssuer {
+                            jwks_url: document
+                                .jwks_uri
+                                .ok_or_else(|| anyhow!("Identity provider '{}' has no JWKS endpoint", env.name))?,
+                            issuer: document.issuer,
+                        }),
+                        None => None,
+                    },
+                })
+            })
+            .await
+    }
+
+    async fn discover(&self, issuer_url: &str) -> Result<DiscoveryDocument> {
+        let discovery_url = format!("{}/.well-known/openid-configuration", issuer_url.trim_end_matches('/'));
+
+        let document = self
+            .http_client
+            .get(&discovery_url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<DiscoveryDocument>()
+            .await?;
+
+        Ok(document)
+    }
+
+    fn to_user_info(claims: &Value) -> Result<OidcUserInfo> {
+        // Standard OIDC uses `sub`; plain OAuth providers such as GitHub expose a numeric `id`.
+        let subject = match (&claims["sub"], &claims["id"]) {
+            (Value::String(sub), _) => sub.clone(),
+            (_, Value::String(id)) => id.clone(),
+            (_, Value::Number(id)) => id.to_string(),
+            _ => return Err(anyhow!("Identity provider returned no subject")),
+        };
+
+        let email_verified = match &claims["email_verified"] {
+            Value::Bool(verified) => *verified,
+            Value::String(verified) => verified == "true",
+            _ => false,
+        };
+
+        let name = ["name", "preferred_username", "login"]
+            .iter()
+            .find_map(|key| claims[*key].as_str().map(str::to_string));
+
+        Ok(OidcUserInfo {
+            subject,
+            email: claims["email"].as_str().map(str::to_string),
+            email_verified,
+            name,
+            preferred_username: ["preferred_username", "login"]
+                .iter()
+                .find_map(|key| claims[*key].as_str().map(str::to_string)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::infrastructure::services::mock_oidc_provider::{MockOidcProvider, MockOidcSettings, NONCE, VALID_CODE};
+
+    async fn service(settings: MockOidcSettings) -> OidcService {
+        let provider = MockOidcProvider::start(settings).await;
+        OidcService::new(vec![provider.provider_env("mock")])
+    }
+
+    async fn exchange(service: &OidcService, code: &str, nonce: Option<&str>) -> Result<OidcUserInfo> {
+        service
+            .exchange_code("mock", code.to_string(), "verifier".to_string(), nonce.map(str::to_string))
+            .await
+    }
+
+    #[tokio::test]
+    async fn authorization_url_carries_state_pkce_and_nonce() {
+        let service = service(MockOidcSettings::default()).await;
+
+        let request = service.authorization_url("mock").await.unwrap();
+
+        let nonce = request.nonce.expect("OIDC providers get a nonce");
+        assert!(request.url.contains("/authorize?"));
+        assert!(request.url.contains(&format!("state={}", request.state)));
+        assert!(request.url.contains("code_challenge_method=S256"));
+        assert!(request.url.contains(&format!("nonce={}", nonce)));
+    }
+
+    #[tokio::test]
+    async fn exchanges_the_code_and_reads_userinfo() {
+        let service = service(MockOidcSettings::default()).await;
+
+        let user_info = exchange(&service, VALID_CODE, Some(NONCE)).await.unwrap();
+
+        assert_eq!(user_info.subject, "mock-subject");
+        assert_eq!(user_info.email.as_deref(), Some("robin@example.com"));
+        assert!(user_info.email_verified);
+        assert_eq!(user_info.preferred_username.as_deref(), Some("robin"));
+    }
+
+    #[tokio::test]
+    async fn rejects_a_bad_code() {
+        let service = service(MockOidcSettings::default()).await;
+
+        let e = exchange(&service, "stolen-code", Some(NONCE)).await.unwrap_err();
+        assert!(e.to_string().contains("Token exchange failed"));
+    }
+
+    #[tokio::test]
+    async fn rejects_a_nonce_mismatch() {
+        let service = service(MockOidcSettings::default()).await;
+
+        let e = exchange(&service, VALID_CODE, Some("some-other-nonce")).await.unwrap_err();
+        assert!(e.to_string().contains("nonce"));
+
+        let e = exchange(&service, VALID_CODE, None).await.unwrap_err();
+        assert!(e.to_string().contains("nonce"));
+    }
+
+    #[tokio::test]
+    async fn rejects_an_id_token_from_an_unknown_issuer() {
+        let service = service(MockOidcSettings {
+            id_token_issuer: Some("https://evil.example.com".to_string()),
+            ..MockOidcSettings::default()
+        })
+        .await;
+
+        let e = exchange(&service, VALID_CODE, Some(NONCE)).await.unwrap_err();
+        assert!(e.to_string().contains("ID token is invalid"));
+    }
+
+    #[tokio::test]
+    async fn rejects_discovery_for_an_unknown_issuer() {
+        let service = service(MockOidcSettings {
+            discovery_issuer: Some("https://evil.example.com".to_string()),
+            ..MockOidcSettings::default()
+        })
+        .await;
+
+        let e = service.authorization_url("mock").await.unwrap_err();
+        assert!(e.to_string().contains("reported issuer"));
+    }
+
+    #[tokio::test]
+    async fn rejects_userinfo_for_a_different_subject() {
+        let service = service(MockOidcSettings {
+            userinfo_subject: Some("someone-else".to_string()),
+            ..MockOidcSettings::default()
+        })
+        .await;
+
+        let e = exchange(&service, VALID_CODE, Some(NONCE)).await.unwrap_err();
+        assert!(e.to_string().contains("subjects do not match"));
+    }
+
+    #[tokio::test]
+    async fn rejects_an_unknown_provider() {
+        let service = service(MockOidcSettings::default()).await;
+
+        let e = service
+            .exchange_code("nope", VALID_CODE.to_string(), "verifier".to_string(), Some(NONCE.to_string()))
+            .await
+            .unwrap_err();
+        assert!(e.to_string().contains("Unknown identity provider"));
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use anyhow::{anyhow, Result};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+
+use crate::config::config_loader::get_jwt_env;
+use crate::config::config_model::{JwtEnv, JwtKeyEnv};
+use crate::domain::value_objects::roles::Role;
+use crate::infrastructure::jwt::jwt_model::Claims;
+
+static TOKEN_SERVICE: OnceLock<Arc<TokenService>> = OnceLock::new();
+
+struct JwtKey {
+    algorithm: Algorithm,
+    // Retired keys keep only their verification half until every token they signed has expired.
+    encoding_key: Option<EncodingKey>,
+    decoding_key: DecodingKey,
+}
+
+pub struct TokenService {
+    active_kid: String,
+    keys: HashMap<String, JwtKey>,
+    ttl: i64,
+    refresh_ttl: i64,
+}
+
+impl TokenService {
+    pub fn new(jwt_env: &JwtEnv) -> Result<Self> {
+        let mut keys = HashMap::new();
+        for key_env in &jwt_env.keys {
+            keys.insert(key_env.kid.clone(), Self::load_key(key_env)?);
+        }
+
+        let active_key = keys
+            .get(&jwt_env.active_kid)
+            .ok_or_else(|| anyhow!("Active JWT key '{}' is not in the keyring", jwt_env.active_kid))?;
+        if active_key.encoding_key.is_none() {
+            return Err(anyhow!("Active JWT key '{}' has no signing material", jwt_env.active_kid));
+        }
+
+        Ok(Self {
+            active_kid: jwt_env.active_kid.clone(),
+            keys,
+            ttl: jwt_env.ttl,
+            refresh_ttl: jwt_env.refresh_ttl,
+        })
+    }
+
+    // Keyring shared by the use cases and the `auth` middleware, loaded once from the environment.
+    pub fn shared() -> Result<Arc<Self>> {
+        if let Some(service) = TOKEN_SERVICE.get() {
+            return Ok(service.clone());
+        }
+
+        let service = Arc::new(Self::new(&get_jwt_env())?);
+        Ok(TOKEN_SERVICE.get_or_init(|| service).clone())
+    }
+
+    // A single HS256 key, for use case tests that need to issue or check tokens.
+    #[cfg(test)]
+    pub fn fixture() -> Arc<Self> {
+        let jwt_env = JwtEnv {
+            ttl: 900,
+            refresh_ttl: 86_400,
+            active_kid: "test".to_string(),
+            keys: vec![JwtKeyEnv {
+                kid: "test".to_string(),
+                algorithm: "HS256".to_string(),
+                secret: Some("test-secret-that-is-long-enough-for-hs256".to_string()),
+                private_key_path: None,
+                public_key_path: None,
+            }],
+        };
+
+        Arc::new(Self::new(&jwt_env).expect("test keyring is valid"))
+    }
+
+    pub fn ttl(&self) -> i64 {
+        self.ttl
+    }
+
+    pub fn refresh_ttl(&self) -> i64 {
+        self.refresh_ttl
+    }
+
+    // The role rides along in the access token, so a role change applies from the next refresh.
+    pub fn issue_access_token(&self, user_id: i32, session_id: i32, role: Role) -> Result<String> {
+        self.sign(user_id, Some(session_id), None, Some(role), self.ttl)
+    }
+
+    pub fn issue(&self, user_id: i32, session_id: Option<i32>, ttl_seconds: i64) -> Result<String> {
+        self.sign(user_id, session_id, None, None, ttl_seconds)
+    }
+
+    // Purpose tokens (e.g. a pending 2FA login) carry no session, so `auth` never accepts them.
+    pub fn issue_for_purpose(&self, user_id: i32, purpose: &str, ttl_seconds: i64) -> Result<String> {
+        self.sign(user_id, None, Some(purpose.to_string()), None, ttl_seconds)
+    }
+
+    pub fn verify_for_purpose(&self, token: &str, purpose: &str) -> Result<Claims> {
+        let claims = self.verify(token)?;
+        if claims.purpose.as_deref() != Some(purpose) {
+            return Err(anyhow!("Token was not issued for '{}'", purpose));
+        }
+
+        Ok(claims)
+    }
+
+    fn sign(
+        &self,
+        user_id: i32,
+        session_id: Option<i32>,
+        purpose: Option<String>,
+        role: Option<Role>,
+        ttl_seconds: i64,
+    ) -> Result<String> {
+        let now = Utc::now();
+        let expiration = now
+            .checked_add_signed(Duration::seconds(ttl_seconds))
+            .expect("valid timestamp")
+            .timestamp();
+
+        let claims = Claims {
+            sub: user_id.to_string(),
+            exp: expiration as usize,
+            iat: now.timestamp() as usize,
+            sid: session_id,
+            purpose,
+            role: role.map(|role| role.to_string()),
+        };
+
+        let key = &self.keys[&self.active_kid];
+        let encoding_key = key.encoding_key.as_ref().expect("active key can sign");
+
+        let mut header = Header::new(key.algorithm);
+        header.kid = Some(self.active_kid.clone());
+
+        Ok(encode(&header, &claims, encoding_key)?)
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims> {
+        let header = decode_header(token)?;
+
+        // Tokens minted before kid headers existed were all signed with the active key.
+        let kid = header.kid.unwrap_or_else(|| self.active_kid.clone());
+        let key = self
+            .keys
+            .get(&kid)
+            .ok_or_else(|| anyhow!("Unknown signing key '{}'", kid))?;
+
+        let token_data = decode::<Claims>(token, &key.decoding_key, &Validation::new(key.algorithm))?;
+
+        Ok(token_data.claims)
+    }
+
+    fn load_key(key_env: &JwtKeyEnv) -> Result<JwtKey> {
+        let algorithm = match key_env.algorithm.as_str() {
+            "HS256" => Algorithm::HS256,
+            "RS256" => Algorithm::RS256,
+            "EdDSA" => Algorithm::EdDSA,
+            other => return Err(anyhow!("Unsupported JWT algorithm '{}' for key '{}'", other, key_env.kid)),
+        };
+
+        if algorithm == Algorithm::HS256 {
+            let secret = key_env
+                .secret
+                .as_ref()
+                .ok_or_else(|| anyhow!("JWT key '{}' is missing its secret", key_env.kid))?;
+
+            return Ok(JwtKey {
+                algorithm,
+                encoding_key: Some(EncodingKey::from_secret(secret.as_bytes())),
+                decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            });
+        }
+
+        let public_key_path = key_env
+            .public_key_path
+            .as_ref()
+            .ok_or_else(|| anyhow!("JWT key '{}' is missing its public key", key_env.kid))?;
+        let public_pem = std::fs::read(public_key_path)?;
+
+        let decoding_key = match algorithm {
+            Algorithm::RS256 => DecodingKey::from_rsa_pem(&public_pem)?,
+            _ => DecodingKey::from_ed_pem(&public_pem)?,
+        };
+
+        let encoding_key = match &key_env.private_key_path {
+            Some(path) => {
+                let private_pem = std::fs::read(path)?;
+                Some(match algorithm {
+                    Algorithm::RS256 => EncodingKey::from_rsa_pem(&private_pem)?,
+                    _ => EncodingKey::from_ed_pem(&private_pem)?,
+                })
+            }
+            None => None,
+        };
+
+        Ok(JwtKey {
+            algorithm,
+            encoding_key,
+            decoding_key,
+        })
+    }
+}
@@ -0,0 +1,145 @@
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+// RFC 6238 defaults, which is what every authenticator app assumes.
+const PERIOD: i64 = 30;
+const DIGITS: u32 = 6;
+const SKEW: i64 = 1;
+
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 20];
+    rand::rng().fill_bytes(&mut bytes);
+    BASE32_NOPAD.encode(&bytes)
+}
+
+pub fn provisioning_uri(issuer: &str, account: &str, secret: &str) -> String {
+    let label: String = url::form_urlencoded::byte_serialize(format!("{}:{}", issuer, account).as_bytes()).collect();
+    let issuer: String = url::form_urlencoded::byte_serialize(issuer.as_bytes()).collect();
+
+    format!(
+        "otpauth://totp/{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        label, secret, issuer, DIGITS, PERIOD
+    )
+}
+
+// Returns the matching time step so callers can refuse to accept it twice.
+pub fn verify(secret: &str, code: &str) -> Result<Option<i64>> {
+    let key = BASE32_NOPAD
+        .decode(secret.as_bytes())
+        .map_err(|e| anyhow!("Invalid TOTP secret: {}", e))?;
+
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    let current_step = Utc::now().timestamp() / PERIOD;
+    for step in (current_step - SKEW)..=(current_step + SKEW) {
+        if generate_code(&key, step)? == code {
+            return Ok(Some(step));
+        }
+    }
+
+    Ok(None)
+}
+
+fn generate_code(key: &[u8], step: i64) -> Result<String> {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).map_err(|e| anyhow!(e.to_string()))?;
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+
+    Ok(format!("{:0width$}", binary % 10u32.pow(DIGITS), width = DIGITS as usize))
+}
+
+// The code an authenticator app would show right now; lets callers' tests log in.
+#[cfg(test)]
+pub fn current_code(secret: &str) -> String {
+    let key = BASE32_NOPAD.decode(secret.as_bytes()).expect("valid TOTP secret");
+    generate_code(&key, Utc::now().timestamp() / PERIOD).expect("HMAC accepts any key length")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 appendix B uses this ASCII key for its SHA1 vectors.
+    const RFC_KEY: &[u8] = b"12345678901234567890";
+
+    fn code_at_offset(secret: &str, offset: i64) -> String {
+        let key = BASE32_NOPAD.decode(secret.as_bytes()).unwrap();
+        generate_code(&key, Utc::now().timestamp() / PERIOD + offset).unwrap()
+    }
+
+    #[test]
+    fn matches_the_rfc_6238_test_vectors() {
+        // The RFC lists 8-digit codes; these are their last six digits.
+        assert_eq!(generate_code(RFC_KEY, 59 / PERIOD).unwrap(), "287082");
+        assert_eq!(generate_code(RFC_KEY, 1111111109 / PERIOD).unwrap(), "081804");
+        assert_eq!(generate_code(RFC_KEY, 1234567890 / PERIOD).unwrap(), "005924");
+        assert_eq!(generate_code(RFC_KEY, 2000000000 / PERIOD).unwrap(), "279037");
+    }
+
+    #[test]
+    fn accepts_the_current_code_and_reports_its_step() {
+        let secret = generate_secret();
+        let step = Utc::now().timestamp() / PERIOD;
+
+        // A step boundary can pass between the two calls, which still lands inside the skew window
+        let matched = verify(&secret, &current_code(&secret)).unwrap().unwrap();
+        assert!((step..=step + 1).contains(&matched));
+    }
+
+    #[test]
+    fn tolerates_one_step_of_clock_skew_but_no_more() {
+        let secret = generate_secret();
+
+        assert!(verify(&secret, &code_at_offset(&secret, -1)).unwrap().is_some());
+        assert!(verify(&secret, &code_at_offset(&secret, 1)).unwrap().is_some());
+        assert_eq!(verify(&secret, &code_at_offset(&secret, -3)).unwrap(), None);
+        assert_eq!(verify(&secret, &code_at_offset(&secret, 3)).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_malformed_codes_without_erroring() {
+        let secret = generate_secret();
+
+        for code in ["", "12345", "1234567", "12a456", "12 456"] {
+            assert_eq!(verify(&secret, code).unwrap(), None, "{code:?}");
+        }
+    }
+
+    #[test]
+    fn refuses_a_secret_that_is_not_base32() {
+        assert!(verify("not base32!", "123456").is_err());
+    }
+
+    #[test]
+    fn generates_distinct_base32_secrets() {
+        let secret = generate_secret();
+
+        assert_eq!(BASE32_NOPAD.decode(secret.as_bytes()).unwrap().len(), 20);
+        assert_ne!(secret, generate_secret());
+    }
+
+    #[test]
+    fn provisioning_uri_escapes_the_label_and_issuer() {
+        let uri = provisioning_uri("Nebula Arena", "robin@example.com", "ABCDEF");
+
+        assert_eq!(
+            uri,
+            "otpauth://totp/Nebula+Arena%3Arobin%40example.com?secret=ABCDEF&issuer=Nebula+Arena&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::OnceLock;
+use anyhow::Result;
+
+use crate::config::config_loader::get_username_policy_env;
+
+const RESERVED_USERNAMES: &str = include_str!("reserved_usernames.txt");
+const BANNED_WORDS: &str = include_str!("banned_words.txt");
+
+// Account erasure renames brawlers to "deleted-{id}", so nobody may pick that shape themselves.
+const TOMBSTONE_PREFIX: &str = "deleted-";
+
+static RESERVED: OnceLock<HashSet<String>> = OnceLock::new();
+static BANNED: OnceLock<Vec<String>> = OnceLock::new();
+
+// Returned when a username is malformed, reserved or banned; routers map it to 400 rather than 500.
+#[derive(Debug)]
+pub struct UsernamePolicyViolation(pub String);
+
+impl fmt::Display for UsernamePolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for UsernamePolicyViolation {}
+
+fn word_list(source: &str) -> impl Iterator<Item = String> + '_ {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+}
+
+fn reserved() -> &'static HashSet<String> {
+    RESERVED.get_or_init(|| word_list(RESERVED_USERNAMES).collect())
+}
+
+fn banned() -> &'static [String] {
+    BANNED.get_or_init(|| word_list(BANNED_WORDS).collect())
+}
+
+// Usernames double as sign-in emails, so '@' and '+' are allowed alongside the usual separators.
+fn is_allowed_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '@' | '+')
+}
+
+// Returns the trimmed username to store; uniqueness is the database's job.
+pub fn check(username: &str) -> Result<String> {
+    let policy = get_username_policy_env()?;
+    let username = username.trim();
+    let length = username.chars().count();
+
+    if length < policy.min_length {
+        return Err(UsernamePolicyViolation(format!(
+            "Username must be at least {} characters long",
+            policy.min_length
+        ))
+        .into());
+    }
+    if length > policy.max_length {
+        return Err(UsernamePolicyViolation(format!(
+            "Username must be at most {} characters long",
+            policy.max_length
+        ))
+        .into());
+    }
+    if !username.chars().all(is_allowed_char) {
+        return Err(UsernamePolicyViolation(
+            "Username may only contain letters, digits and . _ - @ +".to_string(),
+        )
+        .into());
+    }
+
+    let lowered = username.to_lowercase();
+    let local_part = lowered.split('@').next().unwrap_or_default();
+    if lowered.starts_with(TOMBSTONE_PREFIX) || reserved().contains(&lowered) || reserved().contains(local_part) {
+        return Err(UsernamePolicyViolation("This username is reserved".to_string()).into());
+    }
+
+    // Whole words only, so "Scunthorpe" or "Nazir" stay usable
+    if lowered
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .any(|token| banned().iter().any(|word| word == token))
+    {
+        return Err(UsernamePolicyViolation("This username is not allowed".to_string()).into());
+    }
+
+    Ok(username.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refused(username: &str) -> String {
+        let e = check(username).expect_err(username);
+        assert!(e.is::<UsernamePolicyViolation>());
+        e.to_string()
+    }
+
+    #[test]
+    fn accepts_and_trims_ordinary_names() {
+        assert_eq!(check("  robin.hood+nebula@example.com ").unwrap(), "robin.hood+nebula@example.com");
+        assert_eq!(check("Robin_Hood-42").unwrap(), "Robin_Hood-42");
+    }
+
+    #[test]
+    fn refuses_names_outside_the_length_limits() {
+        assert!(refused("ab").contains("at least"));
+        assert!(refused(&"a".repeat(256)).contains("at most"));
+    }
+
+    #[test]
+    fn refuses_unexpected_characters() {
+        assert!(refused("robin hood").contains("may only contain"));
+        assert!(refused("robin/hood").contains("may only contain"));
+    }
+
+    #[test]
+    fn refuses_reserved_names_and_their_mailboxes() {
+        assert!(refused("Admin").contains("reserved"));
+        assert!(refused("admin@example.com").contains("reserved"));
+        assert!(refused("deleted-17").contains("reserved"));
+    }
+
+    #[test]
+    fn refuses_banned_words_between_separators() {
+        assert!(refused("nazi").contains("not allowed"));
+        assert!(refused("Big.Nazi@example.com").contains("not allowed"));
+        assert!(refused("hitler88").contains("not allowed"));
+    }
+
+    #[test]
+    fn allows_banned_words_inside_longer_words() {
+        for username in ["scunthorpe", "nazir@example.com", "shitake.farm", "slutsky"] {
+            assert!(check(username).is_ok(), "{} was refused", username);
+        }
+    }
+}
@@ -0,0 +1,595 @@
+// Minimal WebAuthn relying-party checks (W3C WebAuthn Level 2, sections 7.1 and 7.2) for
+// ES256 passkeys. Attestation statements are not verified: we ask for `attestation: "none"`
+// and only trust the credential key the authenticator hands back.
+
+use std::io::Cursor;
+use anyhow::{Result, anyhow, bail};
+use ciborium::value::Value;
+use data_encoding::BASE64URL_NOPAD;
+use p256::ecdsa::{signature::Verifier, DerSignature, VerifyingKey};
+use rand::Rng;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::config::config_model::WebAuthnEnv;
+use crate::domain::value_objects::webauthn_model::{AuthenticationCredential, RegistrationCredential};
+
+pub const ES256: i64 = -7;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+pub struct VerifiedRegistration {
+    pub credential_id: String,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub aaguid: String,
+    pub user_verified: bool,
+}
+
+pub struct VerifiedAssertion {
+    pub sign_count: i64,
+    pub user_verified: bool,
+}
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+struct AuthenticatorData {
+    rp_id_hash: Vec<u8>,
+    flags: u8,
+    sign_count: u32,
+    attested: Option<AttestedCredential>,
+}
+
+struct AttestedCredential {
+    aaguid: Vec<u8>,
+    credential_id: Vec<u8>,
+    public_key: VerifyingKey,
+}
+
+pub fn generate_challenge() -> String {
+    let mut challenge = [0u8; 32];
+    rand::rng().fill(&mut challenge);
+    BASE64URL_NOPAD.encode(&challenge)
+}
+
+pub fn encode(bytes: &[u8]) -> String {
+    BASE64URL_NOPAD.encode(bytes)
+}
+
+// Some browser helpers pad, some do not; accept both.
+pub fn decode(value: &str) -> Result<Vec<u8>> {
+    BASE64URL_NOPAD
+        .decode(value.trim_end_matches('=').as_bytes())
+        .map_err(|_| anyhow!("Invalid base64url value"))
+}
+
+pub fn verify_registration(
+    env: &WebAuthnEnv,
+    expected_challenge: &str,
+    credential: &RegistrationCredential,
+) -> Result<VerifiedRegistration> {
+    if credential.kind != "public-key" {
+        bail!("Unsupported credential type");
+    }
+
+    let client_data_json = decode(&credential.response.client_data_json)?;
+    verify_client_data(env, &client_data_json, "webauthn.create", expected_challenge)?;
+
+    let attestation_object: Value = ciborium::de::from_reader(decode(&credential.response.attestation_object)?.as_slice())
+        .map_err(|_| anyhow!("Malformed attestation object"))?;
+    let auth_data = map_get(&attestation_object, "authData")
+        .and_then(Value::as_bytes)
+        .ok_or_else(|| anyhow!("Attestation object has no authenticator data"))?;
+
+    let auth_data = parse_authenticator_data(auth_data)?;
+    verify_authenticator_data(env, &auth_data)?;
+
+    let attested = auth_data
+        .attested
+        .ok_or_else(|| anyhow!("Authenticator did not return a credential"))?;
+    let credential_id = encode(&attested.credential_id);
+    if credential_id != credential.id.trim_end_matches('=') {
+        bail!("Credential id does not match the authenticator data");
+    }
+
+    Ok(VerifiedRegistration {
+        credential_id,
+        public_key: attested.public_key.to_encoded_point(false).as_bytes().to_vec(),
+        sign_count: auth_data.sign_count.into(),
+        aaguid: format_aaguid(&attested.aaguid),
+        user_verified: auth_data.flags & FLAG_USER_VERIFIED != 0,
+    })
+}
+
+pub fn verify_assertion(
+    env: &WebAuthnEnv,
+    expected_challenge: &str,
+    public_key: &[u8],
+    stored_sign_count: i64,
+    credential: &AuthenticationCredential,
+) -> Result<VerifiedAssertion> {
+    if credential.kind != "public-key" {
+        bail!("Unsupported credential type");
+    }
+
+    let client_data_json = decode(&credential.response.client_data_json)?;
+    verify_client_data(env, &client_data_json, "webauthn.get", expected_challenge)?;
+
+    let raw_auth_data = decode(&credential.response.authenticator_data)?;
+    let auth_data = parse_authenticator_data(&raw_auth_data)?;
+    verify_authenticator_data(env, &auth_data)?;
+
+    let verifying_key = VerifyingKey::from_sec1_bytes(public_key).map_err(|_| anyhow!("Stored passkey is corrupt"))?;
+    let signature = DerSignature::try_from(decode(&credential.response.signature)?.as_slice())
+        .map_err(|_| anyhow!("Malformed signature"))?;
+
+    // The authenticator signs authenticatorData || SHA-256(clientDataJSON)
+    let mut signed = raw_auth_data.clone();
+    signed.extend_from_slice(&Sha256::digest(&client_data_json));
+    verifying_key
+        .verify(&signed, &signature)
+        .map_err(|_| anyhow!("Passkey signature is invalid"))?;
+
+    // Authenticators that keep a counter must move it forward; a repeat means a cloned key.
+    // Synced passkeys always report zero, which is allowed.
+    let sign_count = i64::from(auth_data.sign_count);
+    if (sign_count != 0 || stored_sign_count != 0) && sign_count <= stored_sign_count {
+        bail!("Passkey sign counter did not increase");
+    }
+
+    Ok(VerifiedAssertion {
+        sign_count,
+        user_verified: auth_data.flags & FLAG_USER_VERIFIED != 0,
+    })
+}
+
+fn verify_client_data(env: &WebAuthnEnv, client_data_json: &[u8], expected_type: &str, expected_challenge: &str) -> Result<()> {
+    let client_data: ClientData = serde_json::from_slice(client_data_json).map_err(|_| anyhow!("Malformed client data"))?;
+
+    if client_data.kind != expected_type {
+        bail!("Unexpected client data type");
+    }
+    if client_data.challenge.trim_end_matches('=') != expected_challenge {
+        bail!("Challenge does not match");
+    }
+    if !env.origins.iter().any(|origin| origin == &client_data.origin) {
+        bail!("Origin {} is not allowed", client_data.origin);
+    }
+
+    Ok(())
+}
+
+fn verify_authenticator_data(env: &WebAuthnEnv, auth_data: &AuthenticatorData) -> Result<()> {
+    if auth_data.rp_id_hash != Sha256::digest(env.rp_id.as_bytes()).as_slice() {
+        bail!("Passkey belongs to another relying party");
+    }
+    if auth_data.flags & FLAG_USER_PRESENT == 0 {
+        bail!("User presence was not confirmed");
+    }
+
+    Ok(())
+}
+
+fn parse_authenticator_data(data: &[u8]) -> Result<AuthenticatorData> {
+    if data.len() < 37 {
+        bail!("Authenticator data is too short");
+    }
+
+    let rp_id_hash = data[..32].to_vec();
+    let flags = data[32];
+    let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+
+    let attested = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+        let rest = &data[37..];
+        if rest.len() < 18 {
+            bail!("Attested credential data is too short");
+        }
+        let aaguid = rest[..16].to_vec();
+        let id_len = u16::from_be_bytes([rest[16], rest[17]]) as usize;
+        let credential_id = rest
+            .get(18..18 + id_len)
+            .ok_or_else(|| anyhow!("Credential id is truncated"))?
+            .to_vec();
+
+        // The COSE key is followed by optional extension data, so read exactly one CBOR item
+        let mut cursor = Cursor::new(&rest[18 + id_len..]);
+        let cose_key: Value = ciborium::de::from_reader(&mut cursor).map_err(|_| anyhow!("Malformed credential public key"))?;
+
+        Some(AttestedCredential {
+            aaguid,
+            credential_id,
+            public_key: parse_cose_key(&cose_key)?,
+        })
+    } else {
+        None
+    };
+
+    Ok(AuthenticatorData { rp_id_hash, flags, sign_count, attested })
+}
+
+// COSE_Key (RFC 9052) for an EC2 P-256 key: kty 2, alg -7, crv 1, x and y coordinates.
+fn parse_cose_key(key: &Value) -> Result<VerifyingKey> {
+    let entries = key.as_map().ok_or_else(|| anyhow!("Credential public key is not a map"))?;
+    let field = |label: i64| {
+        entries.iter().find_map(|(k, v)| match k {
+            Value::Integer(i) if i128::from(*i) == i128::from(label) => Some(v),
+            _ => None,
+        })
+    };
+    let int_field = |label: i64| field(label).and_then(Value::as_integer).map(i128::from);
+
+    if int_field(1) != Some(2) || int_field(3) != Some(ES256.into()) || int_field(-1) != Some(1) {
+        bail!("Only ES256 (P-256) passkeys are supported");
+    }
+
+    let x = field(-2).and_then(Value::as_bytes).ok_or_else(|| anyhow!("Public key has no x coordinate"))?;
+    let y = field(-3).and_then(Value::as_bytes).ok_or_else(|| anyhow!("Public key has no y coordinate"))?;
+    if x.len() != 32 || y.len() != 32 {
+        bail!("Public key coordinates have the wrong length");
+    }
+
+    let mut sec1 = Vec::with_capacity(65);
+    sec1.push(0x04);
+    sec1.extend_from_slice(x);
+    sec1.extend_from_slice(y);
+
+    VerifyingKey::from_sec1_bytes(&sec1).map_err(|_| anyhow!("Public key is not on the P-256 curve"))
+}
+
+fn map_get<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    value
+        .as_map()?
+        .iter()
+        .find_map(|(k, v)| (k.as_text() == Some(key)).then_some(v))
+}
+
+fn format_aaguid(aaguid: &[u8]) -> String {
+    let hex = hex::encode(aaguid);
+    if hex.len() != 32 {
+        return hex;
+    }
+
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+// The opaque WebAuthn user handle; never the username, so it leaks nothing if a passkey is exported.
+pub fn user_handle(brawler_id: i32) -> String {
+    encode(&brawler_id.to_be_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ciborium::value::Integer;
+    use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    use crate::domain::value_objects::webauthn_model::{AssertionResponse, AttestationResponse};
+
+    const RP_ID: &str = "xuee.test";
+    const ORIGIN: &str = "https://xuee.test";
+    const CHALLENGE: &str = "c29mdHdhcmUtYXV0aGVudGljYXRvci1jaGFsbGVuZ2U";
+
+    fn env() -> WebAuthnEnv {
+        WebAuthnEnv {
+            rp_id: RP_ID.to_string(),
+            rp_name: "Xuee".to_string(),
+            origins: vec![ORIGIN.to_string()],
+            timeout_ms: 300_000,
+        }
+    }
+
+    fn int(value: i64) -> Value {
+        Value::Integer(Integer::from(value))
+    }
+
+    fn to_cbor(value: &Value) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(value, &mut bytes).unwrap();
+        bytes
+    }
+
+    fn client_data(kind: &str, challenge: &str, origin: &str) -> Vec<u8> {
+        serde_json::json!({ "type": kind, "challenge": challenge, "origin": origin })
+            .to_string()
+            .into_bytes()
+    }
+
+    // A P-256 authenticator in memory, producing the same structures a browser hands back.
+    struct SoftwareAuthenticator {
+        key: SigningKey,
+        credential_id: Vec<u8>,
+    }
+
+    impl SoftwareAuthenticator {
+        fn new() -> Self {
+            Self {
+                key: SigningKey::from_slice(&[7u8; 32]).unwrap(),
+                credential_id: b"software-credential".to_vec(),
+            }
+        }
+
+        fn public_key(&self) -> Vec<u8> {
+            self.key.verifying_key().to_encoded_point(false).as_bytes().to_vec()
+        }
+
+        fn cose_key(&self, alg: i64) -> Value {
+            let point = self.key.verifying_key().to_encoded_point(false);
+            Value::Map(vec![
+                (int(1), int(2)),
+                (int(3), int(alg)),
+                (int(-1), int(1)),
+                (int(-2), Value::Bytes(point.x().unwrap().to_vec())),
+                (int(-3), Value::Bytes(point.y().unwrap().to_vec())),
+            ])
+        }
+
+        fn auth_data(&self, rp_id: &str, flags: u8, sign_count: u32, cose_key: Option<Value>) -> Vec<u8> {
+            let mut data = Sha256::digest(rp_id.as_bytes()).to_vec();
+            data.push(flags);
+            data.extend_from_slice(&sign_count.to_be_bytes());
+
+            if let Some(cose_key) = cose_key {
+                data[32] |= FLAG_ATTESTED_CREDENTIAL;
+                data.extend_from_slice(&[0xAA; 16]);
+                data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+                data.extend_from_slice(&self.credential_id);
+                data.extend_from_slice(&to_cbor(&cose_key));
+            }
+
+            data
+        }
+
+        fn registration(&self, client_data_json: &[u8], auth_data: Vec<u8>) -> RegistrationCredential {
+            let attestation_object = Value::Map(vec![
+                (Value::Text("fmt".to_string()), Value::Text("none".to_string())),
+                (Value::Text("attStmt".to_string()), Value::Map(Vec::new())),
+                (Value::Text("authData".to_string()), Value::Bytes(auth_data)),
+            ]);
+
+            RegistrationCredential {
+                id: encode(&self.credential_id),
+                kind: "public-key".to_string(),
+                response: AttestationResponse {
+                    client_data_json: encode(client_data_json),
+                    attestation_object: encode(&to_cbor(&attestation_object)),
+                },
+            }
+        }
+
+        fn assertion(&self, client_data_json: &[u8], auth_data: Vec<u8>) -> AuthenticationCredential {
+            let mut signed = auth_data.clone();
+            signed.extend_from_slice(&Sha256::digest(client_data_json));
+            let signature: Signature = self.key.sign(&signed);
+
+            AuthenticationCredential {
+                id: encode(&self.credential_id),
+                kind: "public-key".to_string(),
+                response: AssertionResponse {
+                    client_data_json: encode(client_data_json),
+                    authenticator_data: encode(&auth_data),
+                    signature: encode(signature.to_der().as_bytes()),
+                    user_handle: Some(user_handle(1)),
+                },
+            }
+        }
+
+        fn register(&self) -> RegistrationCredential {
+            self.registration(
+                &client_data("webauthn.create", CHALLENGE, ORIGIN),
+                self.auth_data(RP_ID, FLAG_USER_PRESENT | FLAG_USER_VERIFIED, 0, Some(self.cose_key(ES256))),
+            )
+        }
+
+        fn assert(&self, sign_count: u32) -> AuthenticationCredential {
+            self.assertion(
+                &client_data("webauthn.get", CHALLENGE, ORIGIN),
+                self.auth_data(RP_ID, FLAG_USER_PRESENT | FLAG_USER_VERIFIED, sign_count, None),
+            )
+        }
+    }
+
+    fn assert_rejected<T>(result: Result<T>, message: &str) {
+        match result {
+            Ok(_) => panic!("expected rejection containing {:?}", message),
+            Err(e) => assert!(e.to_string().contains(message), "{:?} does not contain {:?}", e.to_string(), message),
+        }
+    }
+
+    #[test]
+    fn registration_accepts_a_valid_credential() {
+        let authenticator = SoftwareAuthenticator::new();
+
+        let verified = verify_registration(&env(), CHALLENGE, &authenticator.register()).unwrap();
+
+        assert_eq!(verified.credential_id, encode(&authenticator.credential_id));
+        assert_eq!(verified.public_key, authenticator.public_key());
+        assert_eq!(verified.sign_count, 0);
+        assert_eq!(verified.aaguid, "aaaaaaaa-aaaa-aaaa-aaaa-aaaaaaaaaaaa");
+        assert!(verified.user_verified);
+    }
+
+    #[test]
+    fn assertion_accepts_a_valid_signature() {
+        let authenticator = SoftwareAuthenticator::new();
+
+        let verified = verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 4, &authenticator.assert(5)).unwrap();
+
+        assert_eq!(verified.sign_count, 5);
+        assert!(verified.user_verified);
+    }
+
+    #[test]
+    fn assertion_accepts_synced_passkeys_without_a_counter() {
+        let authenticator = SoftwareAuthenticator::new();
+
+        let verified = verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 0, &authenticator.assert(0)).unwrap();
+
+        assert_eq!(verified.sign_count, 0);
+    }
+
+    #[test]
+    fn rejects_wrong_origin() {
+        let authenticator = SoftwareAuthenticator::new();
+        let registration = authenticator.registration(
+            &client_data("webauthn.create", CHALLENGE, "https://evil.test"),
+            authenticator.auth_data(RP_ID, FLAG_USER_PRESENT, 0, Some(authenticator.cose_key(ES256))),
+        );
+        let assertion = authenticator.assertion(
+            &client_data("webauthn.get", CHALLENGE, "https://evil.test"),
+            authenticator.auth_data(RP_ID, FLAG_USER_PRESENT, 1, None),
+        );
+
+        assert_rejected(verify_registration(&env(), CHALLENGE, &registration), "Origin");
+        assert_rejected(verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 0, &assertion), "Origin");
+    }
+
+    #[test]
+    fn rejects_wrong_rp_id() {
+        let authenticator = SoftwareAuthenticator::new();
+        let registration = authenticator.registration(
+            &client_data("webauthn.create", CHALLENGE, ORIGIN),
+            authenticator.auth_data("evil.test", FLAG_USER_PRESENT, 0, Some(authenticator.cose_key(ES256))),
+        );
+        let assertion = authenticator.assertion(
+            &client_data("webauthn.get", CHALLENGE, ORIGIN),
+            authenticator.auth_data("evil.test", FLAG_USER_PRESENT, 1, None),
+        );
+
+        assert_rejected(verify_registration(&env(), CHALLENGE, &registration), "relying party");
+        assert_rejected(
+            verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 0, &assertion),
+            "relying party",
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_challenge() {
+        let authenticator = SoftwareAuthenticator::new();
+        let other_challenge = generate_challenge();
+
+        assert_rejected(verify_registration(&env(), &other_challenge, &authenticator.register()), "Challenge");
+        assert_rejected(
+            verify_assertion(&env(), &other_challenge, &authenticator.public_key(), 0, &authenticator.assert(1)),
+            "Challenge",
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_type() {
+        let authenticator = SoftwareAuthenticator::new();
+        let registration = authenticator.registration(
+            &client_data("webauthn.get", CHALLENGE, ORIGIN),
+            authenticator.auth_data(RP_ID, FLAG_USER_PRESENT, 0, Some(authenticator.cose_key(ES256))),
+        );
+        let assertion = authenticator.assertion(
+            &client_data("webauthn.create", CHALLENGE, ORIGIN),
+            authenticator.auth_data(RP_ID, FLAG_USER_PRESENT, 1, None),
+        );
+        let mut not_public_key = authenticator.assert(1);
+        not_public_key.kind = "password".to_string();
+
+        assert_rejected(verify_registration(&env(), CHALLENGE, &registration), "client data type");
+        assert_rejected(
+            verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 0, &assertion),
+            "client data type",
+        );
+        assert_rejected(
+            verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 0, &not_public_key),
+            "credential type",
+        );
+    }
+
+    #[test]
+    fn rejects_missing_user_presence() {
+        let authenticator = SoftwareAuthenticator::new();
+        let registration = authenticator.registration(
+            &client_data("webauthn.create", CHALLENGE, ORIGIN),
+            authenticator.auth_data(RP_ID, FLAG_USER_VERIFIED, 0, Some(authenticator.cose_key(ES256))),
+        );
+        let assertion = authenticator.assertion(
+            &client_data("webauthn.get", CHALLENGE, ORIGIN),
+            authenticator.auth_data(RP_ID, FLAG_USER_VERIFIED, 1, None),
+        );
+
+        assert_rejected(verify_registration(&env(), CHALLENGE, &registration), "User presence");
+        assert_rejected(
+            verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 0, &assertion),
+            "User presence",
+        );
+    }
+
+    #[test]
+    fn rejects_counter_regression() {
+        let authenticator = SoftwareAuthenticator::new();
+        let public_key = authenticator.public_key();
+
+        assert_rejected(verify_assertion(&env(), CHALLENGE, &public_key, 5, &authenticator.assert(3)), "counter");
+        assert_rejected(verify_assertion(&env(), CHALLENGE, &public_key, 5, &authenticator.assert(5)), "counter");
+        assert_rejected(verify_assertion(&env(), CHALLENGE, &public_key, 5, &authenticator.assert(0)), "counter");
+    }
+
+    #[test]
+    fn rejects_truncated_authenticator_data() {
+        let authenticator = SoftwareAuthenticator::new();
+        let client_data_json = client_data("webauthn.get", CHALLENGE, ORIGIN);
+        let mut auth_data = authenticator.auth_data(RP_ID, FLAG_USER_PRESENT, 1, None);
+        auth_data.truncate(36);
+
+        assert_rejected(
+            verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 0, &authenticator.assertion(&client_data_json, auth_data)),
+            "too short",
+        );
+
+        let mut auth_data = authenticator.auth_data(RP_ID, FLAG_USER_PRESENT, 0, Some(authenticator.cose_key(ES256)));
+        auth_data.truncate(37 + 18 + 4);
+        let registration = authenticator.registration(&client_data("webauthn.create", CHALLENGE, ORIGIN), auth_data);
+
+        assert_rejected(verify_registration(&env(), CHALLENGE, &registration), "truncated");
+    }
+
+    #[test]
+    fn rejects_non_es256_keys() {
+        let authenticator = SoftwareAuthenticator::new();
+        let client_data_json = client_data("webauthn.create", CHALLENGE, ORIGIN);
+
+        // RS256
+        let registration = authenticator.registration(
+            &client_data_json,
+            authenticator.auth_data(RP_ID, FLAG_USER_PRESENT, 0, Some(authenticator.cose_key(-257))),
+        );
+        assert_rejected(verify_registration(&env(), CHALLENGE, &registration), "ES256");
+
+        // ES256 algorithm on the P-384 curve
+        let mut cose_key = authenticator.cose_key(ES256);
+        if let Value::Map(entries) = &mut cose_key {
+            entries[2].1 = int(2);
+        }
+        let registration = authenticator.registration(
+            &client_data_json,
+            authenticator.auth_data(RP_ID, FLAG_USER_PRESENT, 0, Some(cose_key)),
+        );
+        assert_rejected(verify_registration(&env(), CHALLENGE, &registration), "ES256");
+    }
+
+    #[test]
+    fn rejects_a_signature_from_another_key() {
+        let authenticator = SoftwareAuthenticator::new();
+        let other = SoftwareAuthenticator {
+            key: SigningKey::from_slice(&[9u8; 32]).unwrap(),
+            credential_id: authenticator.credential_id.clone(),
+        };
+
+        assert_rejected(
+            verify_assertion(&env(), CHALLENGE, &authenticator.public_key(), 0, &other.assert(1)),
+            "signature is invalid",
+        );
+    }
+}
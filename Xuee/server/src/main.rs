@@ -2,7 +2,12 @@ use std::sync::Arc;
 
 use server::{
     config::config_loader,
-    infrastructure::{database::postgresql_connection, http::http_serv::start},
+    infrastructure::{
+        database::postgresql_connection,
+        http::http_serv::start,
+        jobs::account_purge,
+        services::{image_storage::ConfiguredImageStorage, token_service::TokenService},
+    },
 };
 use tracing::{error, info};
 
@@ -22,6 +27,16 @@ async fn main() {
 
     info!(".ENV LOADED");
 
+    if let Err(e) = TokenService::shared() {
+        error!("Failed to load JWT keyring: {}", e);
+        std::process::exit(1);
+    }
+
+    if let Err(e) = config_loader::get_email_verification_env() {
+        error!("Invalid email verification settings: {}", e);
+        std::process::exit(1);
+    }
+
     let postgres_pool = match postgresql_connection::establish_connection(&dotenvy_env.database.url)
         .await
     {
@@ -33,7 +48,21 @@ async fn main() {
     };
     info!("Connected DB");
 
-    start(Arc::new(dotenvy_env), Arc::new(postgres_pool))
+    let image_storage = match ConfiguredImageStorage::from_env() {
+        Ok(storage) => Arc::new(storage),
+        Err(e) => {
+            error!("Failed to configure image storage: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let postgres_pool = Arc::new(postgres_pool);
+    if let Err(e) = account_purge::spawn(postgres_pool.clone(), image_storage.clone()) {
+        error!("Failed to start account purge job: {}", e);
+        std::process::exit(1);
+    }
+
+    start(Arc::new(dotenvy_env), postgres_pool, image_storage)
         .await
         .expect("Failed to start server");
 }